@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 pub struct CLIArgs {
@@ -8,7 +8,10 @@ pub struct CLIArgs {
 
 #[derive(Subcommand)]
 pub enum Commands {
-    Verify(InputArgs),
+    Verify(VerifyArgs),
+    #[command(name = "verify-plan")]
+    VerifyPlan(VerifyPlanArgs),
+    Fmt(FmtArgs),
     Metadata(InputArgs),
     #[command(name = "to_json")] 
     Serialize(InputArgs)
@@ -22,4 +25,56 @@ pub struct InputArgs {
     pub problem_path: Option<String>,
     #[arg(short, long)]
     pub output_file: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct VerifyArgs {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: Option<String>,
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    // report features used without their requirement as errors
+    #[arg(long)]
+    pub strict: bool,
+    // verify plain PDDL files (without tasks and methods), and check that the goal is reachable
+    #[arg(long)]
+    pub classical: bool,
+    // report the predicates that are used in preconditions, but changed by no action
+    #[arg(long)]
+    pub immutable_predicates: bool,
+}
+
+#[derive(Parser)]
+pub struct VerifyPlanArgs {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(index = 2)]
+    pub problem_path: String,
+    #[arg(index = 3)]
+    pub plan_path: String,
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Parser)]
+pub struct FmtArgs {
+    #[arg(index = 1, required = true)]
+    pub paths: Vec<String>,
+    #[arg(long, conflicts_with = "in_place")]
+    pub check: bool,
+    #[arg(short, long)]
+    pub in_place: bool,
+    #[arg(long, default_value_t = 2)]
+    pub indent_width: usize,
+    #[arg(long, default_value_t = 100)]
+    pub max_line_length: usize,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
 }
\ No newline at end of file
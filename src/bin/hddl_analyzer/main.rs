@@ -1,14 +1,14 @@
 use clap::Parser;
-use hddl_analyzer::HDDLAnalyzer;
-use std::{env, fs};
+use hddl_analyzer::{DiagnosticRenderer, FormatOptions, HDDLAnalyzer, VerificationReport, VerifyOptions};
+use std::io::IsTerminal;
+use std::{env, fs, process};
 
 mod cli_args;
 
-use cli_args::{CLIArgs, Commands};
+use cli_args::{CLIArgs, Commands, OutputFormat};
 
 pub fn main() {
     // ANSI escape color codes
-    let yellow = "\x1b[33m";
     let green = "\x1b[32m";
     let red = "\x1b[31m";
     // ANSI escape code to reset text color
@@ -33,52 +33,140 @@ pub fn main() {
             }
         }
         Commands::Verify(input) => {
-            let domain = fs::read(input.domain_path);
+            let domain = fs::read(&input.domain_path);
             match domain {
-                Ok(domain_content) => match input.problem_path {
-                    Some(problem_path) => {
-                        let problem = fs::read(problem_path);
-                        match problem {
-                            Ok(problem_content) => {
-                                let output =
-                                    HDDLAnalyzer::verify(&domain_content, Some(&problem_content));
-                                match output {
-                                    Ok(warnings) => {
-                                        for warning in warnings {
-                                            println!("{}[Warning]{} {}", yellow, reset, warning);
-                                        }
-                                        println!("{}[Ok]{}", green, reset);
-                                    }
-                                    Err(parsing_error) => {
-                                        eprintln!("{}[Error]{} {}", red, reset, parsing_error)
-                                    }
-                                }
-                            }
+                Ok(domain_content) => {
+                    let problem_content = match &input.problem_path {
+                        Some(problem_path) => match fs::read(problem_path) {
+                            Ok(problem_content) => Some(problem_content),
                             Err(read_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, read_error)
+                                eprintln!("{}[Error]{} {}", red, reset, read_error);
+                                process::exit(1);
                             }
+                        },
+                        None => None,
+                    };
+                    let options = VerifyOptions {
+                        strict_requirements: input.strict,
+                        classical: input.classical,
+                        immutable_predicates: input.immutable_predicates,
+                    };
+                    let report = HDDLAnalyzer::verify_with(&domain_content, problem_content.as_ref(), options);
+                    let problem_path = input.problem_path.as_deref();
+                    match input.format {
+                        OutputFormat::Text => {
+                            print_report(
+                                &report,
+                                (&input.domain_path, &domain_content),
+                                problem_path.zip(problem_content.as_ref()),
+                            );
                         }
-                    }
-                    None => {
-                        let output = HDDLAnalyzer::verify(&domain_content, None);
-                        match output {
-                            Ok(warnings) => {
-                                for warning in warnings {
-                                    println!("{}[Warning]{} {}", yellow, reset, warning);
-                                }
-                                println!("{}[Ok]{}", green, reset);
-                            }
-                            Err(parsing_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, parsing_error)
-                            }
+                        OutputFormat::Json => {
+                            println!("{}", report.to_json(&input.domain_path, problem_path));
+                        }
+                        OutputFormat::Sarif => {
+                            println!("{}", report.to_sarif(&input.domain_path, problem_path));
                         }
                     }
-                },
+                    if report.has_errors() {
+                        process::exit(1);
+                    }
+                }
                 Err(read_error) => {
-                    eprintln!("{}[Error]{} {}", red, reset, read_error)
+                    eprintln!("{}[Error]{} {}", red, reset, read_error);
+                    process::exit(1);
                 }
             }
         }
+        Commands::VerifyPlan(input) => {
+            let mut contents = vec![];
+            for path in [&input.domain_path, &input.problem_path, &input.plan_path] {
+                match fs::read(path) {
+                    Ok(content) => contents.push(content),
+                    Err(read_error) => {
+                        eprintln!("{}[Error]{} {}", red, reset, read_error);
+                        process::exit(1);
+                    }
+                }
+            }
+            let report = match HDDLAnalyzer::verify_plan(&contents[0], &contents[1], &contents[2]) {
+                Ok(report) => report,
+                Err(parsing_error) => {
+                    eprintln!("{}[Error]{} {}", red, reset, parsing_error);
+                    process::exit(1);
+                }
+            };
+            match input.format {
+                OutputFormat::Text => {
+                    // the errors go to stderr, and "[Ok]" to stdout
+                    let error_color = std::io::stderr().is_terminal();
+                    let color = std::io::stdout().is_terminal();
+                    let renderer =
+                        DiagnosticRenderer::new(&input.plan_path, &contents[2], error_color);
+                    for error in report.errors.iter() {
+                        eprintln!("{}", renderer.render_plan_error(error));
+                    }
+                    if report.is_valid() {
+                        if color {
+                            println!("{}[Ok]{}", green, reset);
+                        } else {
+                            println!("[Ok]");
+                        }
+                    }
+                }
+                OutputFormat::Json => {
+                    println!("{}", report.to_json(&input.plan_path));
+                }
+                OutputFormat::Sarif => {
+                    println!("{}", report.to_sarif(&input.plan_path));
+                }
+            }
+            if !report.is_valid() {
+                process::exit(1);
+            }
+        }
+        Commands::Fmt(input) => {
+            let options = FormatOptions {
+                indent_width: input.indent_width,
+                use_tabs: false,
+                max_line_length: input.max_line_length,
+            };
+            let mut has_unformatted_files = false;
+            for path in input.paths.iter() {
+                let content = match fs::read(path) {
+                    Ok(content) => content,
+                    Err(read_error) => {
+                        eprintln!("{}[Error]{} {}: {}", red, reset, path, read_error);
+                        process::exit(1);
+                    }
+                };
+                let formatted = match HDDLAnalyzer::format(&content, options) {
+                    Ok(formatted) => formatted,
+                    Err(parsing_error) => {
+                        eprintln!("{}[Error]{} {}: {}", red, reset, path, parsing_error);
+                        process::exit(1);
+                    }
+                };
+                if input.check {
+                    if formatted.as_bytes() != content.as_slice() {
+                        println!("{} is not formatted", path);
+                        has_unformatted_files = true;
+                    }
+                } else if input.in_place {
+                    if formatted.as_bytes() != content.as_slice() {
+                        if let Err(write_error) = fs::write(path, formatted) {
+                            eprintln!("{}[Error]{} {}: {}", red, reset, path, write_error);
+                            process::exit(1);
+                        }
+                    }
+                } else {
+                    print!("{}", formatted);
+                }
+            }
+            if has_unformatted_files {
+                process::exit(1);
+            }
+        }
         Commands::Serialize(args) => {
             let domain_bytes = fs::read(args.domain_path);
             match domain_bytes {
@@ -157,3 +245,43 @@ pub fn main() {
         }
     }
 }
+
+
+// renders every error and warning next to the source that it refers to
+fn print_report(
+    report: &VerificationReport,
+    domain: (&str, &Vec<u8>),
+    problem: Option<(&str, &Vec<u8>)>,
+) {
+    let green = "\x1b[32m";
+    let reset = "\x1b[0m";
+    // errors go to stderr and warnings to stdout, so each is colored only
+    // when its own stream is a terminal
+    let error_color = std::io::stderr().is_terminal();
+    let color = std::io::stdout().is_terminal();
+    let domain_errors = DiagnosticRenderer::new(domain.0, domain.1, error_color);
+    let domain_warnings = DiagnosticRenderer::new(domain.0, domain.1, color);
+    for error in report.domain_errors.iter() {
+        eprintln!("{}", domain_errors.render_error(error));
+    }
+    for warning in report.domain_warnings.iter() {
+        println!("{}", domain_warnings.render_warning(warning));
+    }
+    if let Some((problem_path, problem_content)) = problem {
+        let problem_errors = DiagnosticRenderer::new(problem_path, problem_content, error_color);
+        let problem_warnings = DiagnosticRenderer::new(problem_path, problem_content, color);
+        for error in report.problem_errors.iter() {
+            eprintln!("{}", problem_errors.render_error(error));
+        }
+        for warning in report.problem_warnings.iter() {
+            println!("{}", problem_warnings.render_warning(warning));
+        }
+    }
+    if !report.has_errors() {
+        if color {
+            println!("{}[Ok]{}", green, reset);
+        } else {
+            println!("[Ok]");
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// a line comment of the source (e.g. "; move the truck")
+pub struct Comment {
+    pub offset: usize,
+    // starts with ';', without the line break
+    pub text: String,
+    // whether the comment follows some code on the same line
+    pub is_trailing: bool,
+}
+
+pub fn collect_comments(program: &[u8]) -> Vec<Comment> {
+    let mut comments = vec![];
+    let mut line_has_code = false;
+    let mut cursor = 0;
+    while cursor < program.len() {
+        match program[cursor] {
+            b';' => {
+                let end = program[cursor..]
+                    .iter()
+                    .position(|c| *c == b'\n')
+                    .map_or(program.len(), |length| cursor + length);
+                let text = String::from_utf8_lossy(&program[cursor..end]);
+                comments.push(Comment {
+                    offset: cursor,
+                    text: text.trim_end().to_string(),
+                    is_trailing: line_has_code,
+                });
+                cursor = end;
+            }
+            b'\n' => {
+                line_has_code = false;
+                cursor += 1;
+            }
+            c => {
+                if !c.is_ascii_whitespace() {
+                    line_has_code = true;
+                }
+                cursor += 1;
+            }
+        }
+    }
+    comments
+}
+
+// offset of the first character that is neither a whitespace nor part of a comment
+pub fn code_start(program: &[u8]) -> usize {
+    let mut cursor = 0;
+    while cursor < program.len() {
+        match program[cursor] {
+            b';' => {
+                while cursor < program.len() && program[cursor] != b'\n' {
+                    cursor += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => cursor += 1,
+            _ => return cursor,
+        }
+    }
+    cursor
+}
+
+// offset of the '(' that opens the block (e.g. "(:action") in which the offset lies
+pub fn block_start(program: &[u8], offset: usize) -> usize {
+    let mut cursor = offset.min(program.len());
+    while cursor > 0 {
+        cursor -= 1;
+        if program[cursor] == b'(' {
+            let next = program[cursor + 1..]
+                .iter()
+                .find(|c| !c.is_ascii_whitespace());
+            if next == Some(&b':') {
+                return cursor;
+            }
+        }
+    }
+    offset
+}
+
+// offset of the depth-th '(' before the offset (that is not part of a comment)
+pub fn opening_parenthesis(program: &[u8], comments: &[Comment], offset: usize, depth: usize) -> usize {
+    let mut depth = depth;
+    let mut cursor = offset.min(program.len());
+    while cursor > 0 {
+        cursor -= 1;
+        let is_comment = comments
+            .iter()
+            .any(|comment| comment.offset <= cursor && cursor < comment.offset + comment.text.len());
+        if program[cursor] == b'(' && !is_comment {
+            depth -= 1;
+            if depth == 0 {
+                return cursor;
+            }
+        }
+    }
+    offset
+}
+
+// spans of the blocks of the definition (i.e. the parenthesized groups at depth 2)
+pub fn block_spans(program: &[u8]) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut depth: usize = 0;
+    let mut start = 0;
+    let mut cursor = 0;
+    while cursor < program.len() {
+        match program[cursor] {
+            b';' => {
+                while cursor < program.len() && program[cursor] != b'\n' {
+                    cursor += 1;
+                }
+                continue;
+            }
+            b'(' => {
+                depth += 1;
+                if depth == 2 {
+                    start = cursor;
+                }
+            }
+            b')' => {
+                if depth == 2 {
+                    spans.push((start, cursor + 1));
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+        cursor += 1;
+    }
+    spans
+}
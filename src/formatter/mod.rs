@@ -0,0 +1,13 @@
+mod comments;
+mod options;
+mod printer;
+#[cfg(test)]
+mod tests;
+
+use crate::lexical_analyzer::RequirementType;
+use crate::syntactic_analyzer::*;
+use comments::*;
+
+pub use comments::block_spans;
+pub use options::FormatOptions;
+pub use printer::{parameter_list, PrettyPrinter};
@@ -0,0 +1,28 @@
+#[derive(Debug, Clone, Copy)]
+pub struct FormatOptions {
+    // number of spaces per level of indentation
+    pub indent_width: usize,
+    pub use_tabs: bool,
+    // parameter lists that do not fit in a line are split into one line per type
+    pub max_line_length: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            indent_width: 2,
+            use_tabs: false,
+            max_line_length: 100,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn indentation(&self, level: usize) -> String {
+        if self.use_tabs {
+            "\t".repeat(level)
+        } else {
+            " ".repeat(level * self.indent_width)
+        }
+    }
+}
@@ -0,0 +1,893 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// names that are printed as variables (i.e. with a leading '?')
+type Scope<'a> = HashSet<&'a str>;
+
+// the top-level blocks of a document, printed in the order of their appearance
+enum Block<'b, 'a> {
+    Requirements(&'b Vec<RequirementType>),
+    Types(&'b Vec<Symbol<'a>>),
+    Constants(&'b Vec<Symbol<'a>>),
+    Predicates(&'b Vec<Predicate<'a>>),
+    Functions(&'b Vec<Predicate<'a>>),
+    Task(&'b Task<'a>),
+    Method(&'b Method<'a>),
+    Action(&'b Action<'a>),
+    Objects(&'b Vec<Symbol<'a>>),
+    InitialTaskNetwork(&'b InitialTaskNetwork<'a>),
+    InitialState(&'b Vec<Predicate<'a>>, &'b Vec<FunctionValue<'a>>),
+    Goal(&'b Formula<'a>),
+    Metric(&'b Metric<'a>),
+}
+
+// writes syntax trees back to HDDL with canonical keywords and indentation,
+// and (if the source is given) keeps the comments of the source in place
+pub struct PrettyPrinter<'a> {
+    options: FormatOptions,
+    program: Option<&'a Vec<u8>>,
+    comments: Vec<Comment>,
+    next_comment: usize,
+    output: String,
+    indent: usize,
+    // offset of the symbol that the last printed line starts with
+    last_offset: Option<usize>,
+}
+
+impl<'a> PrettyPrinter<'a> {
+    pub fn new(options: FormatOptions) -> PrettyPrinter<'a> {
+        PrettyPrinter {
+            options,
+            program: None,
+            comments: vec![],
+            next_comment: 0,
+            output: String::new(),
+            indent: 0,
+            last_offset: None,
+        }
+    }
+
+    // the syntax tree must be parsed from the program
+    pub fn with_comments(program: &'a Vec<u8>, options: FormatOptions) -> PrettyPrinter<'a> {
+        PrettyPrinter {
+            program: Some(program),
+            comments: collect_comments(program),
+            ..PrettyPrinter::new(options)
+        }
+    }
+
+    pub fn print_domain(mut self, domain: &DomainAST) -> String {
+        let blocks = domain_blocks(domain);
+        self.header();
+        self.line(None, &format!("(define (domain {})", domain.name));
+        self.blocks(blocks);
+        self.output
+    }
+
+    pub fn print_problem(mut self, problem: &ProblemAST) -> String {
+        let blocks = self.problem_blocks(problem);
+        self.header();
+        self.line(None, &format!("(define (problem {})", problem.name));
+        self.indent += 1;
+        self.line(None, &format!("(:domain {})", problem.domain_name));
+        self.indent -= 1;
+        self.blocks(blocks);
+        self.output
+    }
+
+    // formats the blocks of the source that overlap the byte range, and returns
+    // the spans of the source that are replaced along with their new text
+    pub fn print_domain_range(self, domain: &DomainAST, range: (usize, usize)) -> Vec<(usize, usize, String)> {
+        let blocks = domain_blocks(domain);
+        self.print_range(blocks, range)
+    }
+
+    pub fn print_problem_range(self, problem: &ProblemAST, range: (usize, usize)) -> Vec<(usize, usize, String)> {
+        let blocks = self.problem_blocks(problem);
+        self.print_range(blocks, range)
+    }
+
+    fn print_range(self, blocks: Vec<(Option<usize>, Block)>, range: (usize, usize)) -> Vec<(usize, usize, String)> {
+        let Some(program) = self.program else {
+            return vec![];
+        };
+        let spans = block_spans(program);
+        let mut edits = vec![];
+        for (offset, block) in blocks.iter() {
+            let Some(offset) = offset else {
+                continue;
+            };
+            let start = block_start(program, *offset);
+            let Some((start, end)) = spans.iter().find(|(span_start, _)| *span_start == start) else {
+                continue;
+            };
+            let (start, end) = (*start, *end);
+            if end < range.0 || range.1 < start {
+                continue;
+            }
+            // blocks that are merged with another block of the source are left as they are
+            if element_offsets(block).iter().any(|offset| *offset < start || end <= *offset) {
+                continue;
+            }
+            let mut printer = PrettyPrinter {
+                program: self.program,
+                comments: collect_comments(&program[..end])
+                    .into_iter()
+                    .filter(|comment| start <= comment.offset)
+                    .collect(),
+                indent: 1,
+                ..PrettyPrinter::new(self.options)
+            };
+            printer.block(block);
+            printer.flush_comments(end);
+            // the first line is indented by the text before the block
+            let text = printer.output.trim().to_string();
+            edits.push((start, end, text));
+        }
+        edits
+    }
+
+    fn problem_blocks<'b, 'c>(&self, problem: &'b ProblemAST<'c>) -> Vec<(Option<usize>, Block<'b, 'c>)> {
+        let mut blocks = vec![];
+        if !problem.requirements.is_empty() {
+            let offset = problem.requirement_positions.first().map(|pos| pos.offset);
+            blocks.push((offset, Block::Requirements(&problem.requirements)));
+        }
+        if !problem.objects.is_empty() {
+            blocks.push((symbols_offset(&problem.objects), Block::Objects(&problem.objects)));
+        }
+        if let Some(init_tn) = &problem.init_tn {
+            let offset = init_tn
+                .parameters
+                .as_ref()
+                .and_then(symbols_offset)
+                .or(init_tn.tn.subtasks.first().map(subtask_offset));
+            blocks.push((offset, Block::InitialTaskNetwork(init_tn)));
+        }
+        let offset = problem.init_state.first().map(|p| p.name_pos.offset);
+        let value_offset = problem.init_values.first().map(|value| value.function.name_pos.offset);
+        let offset = offset.into_iter().chain(value_offset).min();
+        blocks.push((offset, Block::InitialState(&problem.init_state, &problem.init_values)));
+        if let Some(goal) = &problem.goal {
+            blocks.push((self.formula_start(goal), Block::Goal(goal)));
+        }
+        if let Some(metric) = &problem.metric {
+            let offset = numeric_offset(&metric.expression).map(|(offset, _)| offset);
+            blocks.push((offset, Block::Metric(metric)));
+        }
+        blocks
+    }
+
+    // comments before the definition
+    fn header(&mut self) {
+        if let Some(program) = self.program {
+            self.flush_comments(code_start(program));
+        }
+    }
+
+    fn blocks(&mut self, mut blocks: Vec<(Option<usize>, Block)>) {
+        // blocks without a position stay after their predecessor
+        let mut last_offset = 0;
+        for (offset, _) in blocks.iter_mut() {
+            let start = offset.map(|offset| self.block_start(offset));
+            last_offset = start.unwrap_or(last_offset);
+            *offset = Some(last_offset);
+        }
+        blocks.sort_by_key(|(offset, _)| *offset);
+        self.indent += 1;
+        for (index, (offset, block)) in blocks.iter().enumerate() {
+            if index != 0 {
+                self.output.push('\n');
+            }
+            self.flush_comments(offset.unwrap());
+            self.block(block);
+        }
+        // comments that come after the last block
+        self.flush_comments(usize::MAX);
+        self.indent -= 1;
+        self.line(None, ")");
+    }
+
+    fn block(&mut self, block: &Block) {
+        match block {
+            Block::Requirements(requirements) => {
+                let requirements: Vec<String> =
+                    requirements.iter().map(|req| req.to_string()).collect();
+                self.line(None, &format!("(:requirements {})", requirements.join(" ")));
+            }
+            Block::Types(types) => self.typed_block(":types", types),
+            Block::Constants(constants) => self.typed_block(":constants", constants),
+            Block::Objects(objects) => self.typed_block(":objects", objects),
+            Block::Predicates(predicates) => {
+                self.line(None, "(:predicates");
+                self.indent += 1;
+                for predicate in predicates.iter() {
+                    let head = format!("({}", predicate.name);
+                    self.parameters(Some(predicate.name_pos.offset), &head, &predicate.variables, ")");
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+            Block::Functions(functions) => {
+                self.line(None, "(:functions");
+                self.indent += 1;
+                for function in functions.iter() {
+                    let head = format!("({}", function.name);
+                    self.parameters(Some(function.name_pos.offset), &head, &function.variables, ") - number");
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+            Block::Task(task) => {
+                let head = format!("(:task {} :parameters (", task.name);
+                self.parameters(None, &head, &task.parameters, "))");
+            }
+            Block::Method(method) => self.method(method),
+            Block::Action(action) => self.action(action),
+            Block::InitialTaskNetwork(init_tn) => {
+                let mut scope = Scope::new();
+                self.line(None, "(:htn");
+                self.indent += 1;
+                if let Some(parameters) = &init_tn.parameters {
+                    scope.extend(parameters.iter().map(|parameter| parameter.name));
+                    self.parameters(symbols_offset(parameters), ":parameters (", parameters, ")");
+                }
+                self.network(&init_tn.tn, &scope);
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+            Block::InitialState(facts, values) => {
+                if facts.is_empty() && values.is_empty() {
+                    self.line(None, "(:init)");
+                    return;
+                }
+                // facts and values stay in the order of the source
+                let mut lines: Vec<(usize, String)> = facts
+                    .iter()
+                    .map(|fact| (fact.name_pos.offset, atom(fact, &Scope::new())))
+                    .collect();
+                lines.extend(values.iter().map(|value| {
+                    let text = format!("(= {} {})", atom(&value.function, &Scope::new()), value.value);
+                    (value.function.name_pos.offset, text)
+                }));
+                lines.sort_by_key(|(offset, _)| *offset);
+                self.line(None, "(:init");
+                self.indent += 1;
+                for (offset, text) in lines.iter() {
+                    self.line(Some(*offset), text);
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+            Block::Goal(goal) => self.formula(None, "(:goal ", goal, &Scope::new(), ")"),
+            Block::Metric(metric) => {
+                let expression = numeric(&metric.expression, &Scope::new());
+                self.line(None, &format!("(:metric {} {})", metric.optimization, expression));
+            }
+        }
+    }
+
+    fn typed_block(&mut self, keyword: &str, symbols: &Vec<Symbol>) {
+        self.line(None, &format!("({}", keyword));
+        self.indent += 1;
+        for (group, symbol_type) in typed_groups(symbols) {
+            let names: Vec<&str> = group.iter().map(|symbol| symbol.name).collect();
+            let text = match symbol_type {
+                Some(symbol_type) => format!("{} - {}", names.join(" "), symbol_type),
+                None => names.join(" "),
+            };
+            self.line(Some(group[0].name_pos.offset), &text);
+        }
+        self.indent -= 1;
+        self.line(None, ")");
+    }
+
+    fn method(&mut self, method: &Method) {
+        let scope: Scope = method.params.iter().map(|param| param.name).collect();
+        self.line(None, &format!("(:method {}", method.name.name));
+        self.indent += 1;
+        self.parameters(symbols_offset(&method.params), ":parameters (", &method.params, ")");
+        let task_terms = terms(&method.task_terms, &scope);
+        let text = format!(":task ({})", join(method.task.name, &task_terms));
+        self.line(Some(method.task.name_pos.offset), &text);
+        if let Some(precondition) = &method.precondition {
+            self.formula(self.formula_start(precondition), ":precondition ", precondition, &scope, "");
+        }
+        self.network(&method.tn, &scope);
+        self.indent -= 1;
+        self.line(None, ")");
+    }
+
+    fn action(&mut self, action: &Action) {
+        let scope: Scope = action.parameters.iter().map(|param| param.name).collect();
+        let keyword = match action.timing {
+            Some(_) => ":durative-action",
+            None => ":action",
+        };
+        self.line(None, &format!("({} {}", keyword, action.name));
+        self.indent += 1;
+        self.parameters(symbols_offset(&action.parameters), ":parameters (", &action.parameters, ")");
+        if let Some(timing) = &action.timing {
+            self.timing(action, timing, &scope);
+            self.indent -= 1;
+            self.line(None, ")");
+            return;
+        }
+        if let Some(precondition) = &action.preconditions {
+            self.formula(self.formula_start(precondition), ":precondition ", precondition, &scope, "");
+        }
+        if let Some(effect) = &action.effects {
+            self.effect(self.effect_start(effect), ":effect ", effect, &scope, "");
+        }
+        self.indent -= 1;
+        self.line(None, ")");
+    }
+
+    // prints the duration, and the timed conditions and effects of a durative action
+    fn timing(&mut self, action: &Action, timing: &Timing, scope: &Scope) {
+        let duration: Vec<String> = timing
+            .duration
+            .iter()
+            .map(|constraint| format!("({} ?duration {})", constraint.operation, numeric(&constraint.value, scope)))
+            .collect();
+        match &duration[..] {
+            [] => self.line(None, ":duration ()"),
+            [constraint] => self.line(None, &format!(":duration {}", constraint)),
+            _ => self.line(None, &format!(":duration (and {})", duration.join(" "))),
+        }
+        if action.preconditions.is_some() {
+            if timing.conditions.is_empty() {
+                self.line(None, ":condition ()");
+            } else {
+                self.line(None, ":condition (and");
+                self.indent += 1;
+                for (time, condition) in timing.conditions.iter() {
+                    if inline(condition, scope).is_some() {
+                        let head = format!("({} ", time);
+                        self.formula(self.formula_start(condition), &head, condition, scope, ")");
+                        continue;
+                    }
+                    self.line(None, &format!("({}", time));
+                    self.indent += 1;
+                    self.formula(self.formula_start(condition), "", condition, scope, "");
+                    self.indent -= 1;
+                    self.line(None, ")");
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+        }
+        if action.effects.is_some() {
+            if timing.effects.is_empty() {
+                self.line(None, ":effect ()");
+            } else {
+                self.line(None, ":effect (and");
+                self.indent += 1;
+                for (time, effect) in timing.effects.iter() {
+                    if inline_effect(effect, scope).is_some() {
+                        let head = format!("({} ", time);
+                        self.effect(self.effect_start(effect), &head, effect, scope, ")");
+                        continue;
+                    }
+                    self.line(None, &format!("({}", time));
+                    self.indent += 1;
+                    self.effect(self.effect_start(effect), "", effect, scope, "");
+                    self.indent -= 1;
+                    self.line(None, ")");
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+        }
+    }
+
+    // prints "<head><parameters><tail>", or one line per type if it is too long
+    fn parameters(&mut self, offset: Option<usize>, head: &str, parameters: &Vec<Symbol>, tail: &str) {
+        let groups = parameter_groups(parameters);
+        let separator = if head.ends_with('(') || groups.is_empty() { "" } else { " " };
+        let text = format!("{}{}{}{}", head, separator, groups.join(" "), tail);
+        let indentation = self.options.indentation(self.indent).replace('\t', &" ".repeat(self.options.indent_width));
+        if indentation.len() + text.len() <= self.options.max_line_length || groups.len() < 2 {
+            self.line(offset, &text);
+            return;
+        }
+        self.line(offset, head);
+        self.indent += 1;
+        for group in groups.iter() {
+            self.line(None, group);
+        }
+        self.indent -= 1;
+        self.line(None, tail);
+    }
+
+    fn network(&mut self, tn: &HTN, scope: &Scope) {
+        let keyword = match tn.orderings {
+            TaskOrdering::Total => ":ordered-subtasks",
+            TaskOrdering::Partial(_) => ":subtasks",
+        };
+        if tn.subtasks.is_empty() {
+            self.line(None, &format!("{} (and)", keyword));
+        } else {
+            self.line(None, &format!("{} (and", keyword));
+            self.indent += 1;
+            for subtask in tn.subtasks.iter() {
+                let task_terms = terms(&subtask.terms, scope);
+                let task = format!("({})", join(subtask.task.name, &task_terms));
+                let text = match &subtask.id {
+                    Some(id) => format!("({} {})", id.name, task),
+                    None => task,
+                };
+                self.line(Some(subtask_offset(subtask)), &text);
+            }
+            self.indent -= 1;
+            self.line(None, ")");
+        }
+        if let TaskOrdering::Partial(orderings) = &tn.orderings {
+            if !orderings.is_empty() {
+                self.line(tn.ordering_pos.map(|pos| pos.offset), ":ordering (and");
+                self.indent += 1;
+                for (first, second) in orderings.iter() {
+                    self.line(None, &format!("(< {} {})", first, second));
+                }
+                self.indent -= 1;
+                self.line(None, ")");
+            }
+        }
+        if let Some(constraints) = &tn.constraints {
+            if constraints.is_empty() {
+                self.line(None, ":constraints ()");
+                return;
+            }
+            self.line(None, ":constraints (and");
+            self.indent += 1;
+            for constraint in constraints.iter() {
+                let text = match constraint {
+                    Constraint::Equal(a, b) => {
+                        format!("(= {} {})", term(a, scope), term(b, scope))
+                    }
+                    Constraint::NotEqual(a, b) => {
+                        format!("(not (= {} {}))", term(a, scope), term(b, scope))
+                    }
+                    Constraint::Temporal(operation, lhs, rhs) => {
+                        format!("({} {} {})", operation, lhs, rhs)
+                    }
+                };
+                self.line(None, &text);
+            }
+            self.indent -= 1;
+            self.line(None, ")");
+        }
+    }
+
+    // prints "<head><formula><tail>", where the operands of a
+    // connective are placed on separate lines
+    fn formula(
+        &mut self,
+        offset: Option<usize>,
+        head: &str,
+        formula: &Formula,
+        scope: &Scope,
+        tail: &str,
+    ) {
+        if let Some(text) = inline(formula, scope) {
+            self.line(offset, &format!("{}{}{}", head, text, tail));
+            return;
+        }
+        match formula {
+            Formula::And(operands) | Formula::Or(operands) | Formula::Xor(operands) => {
+                let connective = match formula {
+                    Formula::And(_) => "and",
+                    Formula::Or(_) => "or",
+                    _ => "oneof",
+                };
+                self.line(offset, &format!("{}({}", head, connective));
+                self.operands(operands, scope);
+                self.line(None, &format!("){}", tail));
+            }
+            Formula::Not(operand) => {
+                self.line(offset, &format!("{}(not", head));
+                self.operands(std::slice::from_ref(operand), scope);
+                self.line(None, &format!("){}", tail));
+            }
+            Formula::Imply(antecedent, consequent) => {
+                self.line(offset, &format!("{}(imply", head));
+                self.indent += 1;
+                for operands in [antecedent, consequent] {
+                    match &operands[..] {
+                        [operand] => {
+                            self.formula(self.formula_start(operand), "", operand, scope, "");
+                        }
+                        _ => {
+                            self.line(None, "(and");
+                            self.operands(operands, scope);
+                            self.line(None, ")");
+                        }
+                    }
+                }
+                self.indent -= 1;
+                self.line(None, &format!("){}", tail));
+            }
+            Formula::Exists(variables, operand) | Formula::ForAll(variables, operand) => {
+                let quantifier = match formula {
+                    Formula::Exists(..) => "exists",
+                    _ => "forall",
+                };
+                let text = format!("{}({} ({})", head, quantifier, parameter_list(variables));
+                self.line(offset, &text);
+                let mut scope = scope.clone();
+                scope.extend(variables.iter().map(|variable| variable.name));
+                self.operands(std::slice::from_ref(operand), &scope);
+                self.line(None, &format!("){}", tail));
+            }
+            // printed inline
+            Formula::Empty | Formula::Atom(_) | Formula::Equals(..) | Formula::Comparison(..) => {}
+        }
+    }
+
+    // prints "<head><effect><tail>", where the operands of a
+    // conjunction (or of a non-deterministic effect) are placed on separate lines
+    fn effect(&mut self, offset: Option<usize>, head: &str, effect: &Effect, scope: &Scope, tail: &str) {
+        if let Some(text) = inline_effect(effect, scope) {
+            self.line(offset, &format!("{}{}{}", head, text, tail));
+            return;
+        }
+        match effect {
+            Effect::And(operands) | Effect::OneOf(operands) => {
+                let connective = match effect {
+                    Effect::And(_) => "and",
+                    _ => "oneof",
+                };
+                self.line(offset, &format!("{}({}", head, connective));
+                self.indent += 1;
+                for operand in operands.iter() {
+                    self.effect(self.effect_start(operand), "", operand, scope, "");
+                }
+                self.indent -= 1;
+                self.line(None, &format!("){}", tail));
+            }
+            Effect::ForAll(variables, operand) => {
+                self.line(offset, &format!("{}(forall ({})", head, parameter_list(variables)));
+                let mut scope = scope.clone();
+                scope.extend(variables.iter().map(|variable| variable.name));
+                self.indent += 1;
+                self.effect(self.effect_start(operand), "", operand, &scope, "");
+                self.indent -= 1;
+                self.line(None, &format!("){}", tail));
+            }
+            Effect::When(condition, operand) => {
+                self.line(offset, &format!("{}(when", head));
+                self.indent += 1;
+                self.formula(self.formula_start(condition), "", condition, scope, "");
+                self.effect(self.effect_start(operand), "", operand, scope, "");
+                self.indent -= 1;
+                self.line(None, &format!("){}", tail));
+            }
+            // printed inline
+            Effect::Empty | Effect::Add(_) | Effect::Delete(_) | Effect::Numeric(..) => {}
+        }
+    }
+
+    fn operands(&mut self, operands: &[Box<Formula>], scope: &Scope) {
+        self.indent += 1;
+        for operand in operands.iter() {
+            self.formula(self.formula_start(operand), "", operand, scope, "");
+        }
+        self.indent -= 1;
+    }
+
+    fn line(&mut self, offset: Option<usize>, text: &str) {
+        match offset {
+            Some(offset) => self.flush_comments(offset),
+            None => self.flush_trailing_comments(),
+        }
+        self.output.push_str(&self.options.indentation(self.indent));
+        self.output.push_str(text);
+        self.output.push('\n');
+        self.last_offset = offset;
+    }
+
+    // prints the comments that come before the offset
+    fn flush_comments(&mut self, offset: usize) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.offset >= offset {
+                break;
+            }
+            self.print_comment();
+        }
+    }
+
+    // prints the comments at the end of the source line of the last printed line
+    fn flush_trailing_comments(&mut self) {
+        let (Some(program), Some(last_offset)) = (self.program, self.last_offset) else {
+            return;
+        };
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let is_same_line = comment.offset >= last_offset
+                && !program[last_offset..comment.offset].contains(&b'\n');
+            if !comment.is_trailing || !is_same_line {
+                break;
+            }
+            self.print_comment();
+        }
+    }
+
+    fn print_comment(&mut self) {
+        let comment = &self.comments[self.next_comment];
+        if comment.is_trailing && self.output.ends_with('\n') {
+            // keep the comment at the end of the last non-empty line
+            let content_length = self.output.trim_end_matches('\n').len();
+            let line_breaks = self.output.split_off(content_length);
+            self.output.push(' ');
+            self.output.push_str(&comment.text);
+            self.output.push_str(&line_breaks);
+        } else {
+            self.output.push_str(&self.options.indentation(self.indent));
+            self.output.push_str(&comment.text);
+            self.output.push('\n');
+        }
+        self.next_comment += 1;
+    }
+
+    // offset of the '(' that opens the formula
+    fn formula_start(&self, formula: &Formula) -> Option<usize> {
+        let (offset, depth) = formula_offset(formula)?;
+        match self.program {
+            Some(program) => Some(opening_parenthesis(program, &self.comments, offset, depth)),
+            None => Some(offset),
+        }
+    }
+
+    // offset of the '(' that opens the effect
+    fn effect_start(&self, effect: &Effect) -> Option<usize> {
+        let (offset, depth) = effect_offset(effect)?;
+        match self.program {
+            Some(program) => Some(opening_parenthesis(program, &self.comments, offset, depth)),
+            None => Some(offset),
+        }
+    }
+
+    fn block_start(&self, offset: usize) -> usize {
+        match self.program {
+            Some(program) => block_start(program, offset),
+            None => offset,
+        }
+    }
+}
+
+// formulas that fit on a single line
+fn inline(formula: &Formula, scope: &Scope) -> Option<String> {
+    match formula {
+        Formula::Empty => Some("()".to_string()),
+        Formula::Atom(predicate) => Some(atom(predicate, scope)),
+        Formula::Equals(a, b) => Some(format!("(= {} {})", term(a.name, scope), term(b.name, scope))),
+        Formula::Comparison(operation, lhs, rhs) => Some(format!(
+            "({} {} {})",
+            operation,
+            numeric(lhs, scope),
+            numeric(rhs, scope)
+        )),
+        Formula::Not(operand) => match &**operand {
+            Formula::Atom(_) | Formula::Equals(..) | Formula::Comparison(..) => {
+                Some(format!("(not {})", inline(operand, scope)?))
+            }
+            _ => None,
+        },
+        Formula::And(operands) if operands.is_empty() => Some("(and)".to_string()),
+        Formula::Or(operands) if operands.is_empty() => Some("(or)".to_string()),
+        Formula::Xor(operands) if operands.is_empty() => Some("(oneof)".to_string()),
+        _ => None,
+    }
+}
+
+// effects that fit on a single line
+fn inline_effect(effect: &Effect, scope: &Scope) -> Option<String> {
+    match effect {
+        Effect::Empty => Some("()".to_string()),
+        Effect::Add(predicate) => Some(atom(predicate, scope)),
+        Effect::Delete(predicate) => Some(format!("(not {})", atom(predicate, scope))),
+        Effect::Numeric(operation, function, value) => Some(format!(
+            "({} {} {})",
+            operation,
+            atom(function, scope),
+            numeric(value, scope)
+        )),
+        Effect::And(operands) if operands.is_empty() => Some("(and)".to_string()),
+        Effect::OneOf(operands) if operands.is_empty() => Some("(oneof)".to_string()),
+        _ => None,
+    }
+}
+
+fn atom(predicate: &Predicate, scope: &Scope) -> String {
+    format!("({})", join(predicate.name, &terms(&predicate.variables, scope)))
+}
+
+fn numeric(expression: &NumericExpression, scope: &Scope) -> String {
+    match expression {
+        NumericExpression::Number(value, _) => value.to_string(),
+        NumericExpression::Function(function) => atom(function, scope),
+        NumericExpression::Operation(operation, operands) => {
+            let operands: Vec<String> = operands.iter().map(|operand| numeric(operand, scope)).collect();
+            format!("({} {})", operation, operands.join(" "))
+        }
+    }
+}
+
+fn join(name: &str, rest: &str) -> String {
+    if rest.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, rest)
+    }
+}
+
+fn term(name: &str, scope: &Scope) -> String {
+    if scope.contains(name) {
+        format!("?{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn terms(symbols: &Vec<Symbol>, scope: &Scope) -> String {
+    let terms: Vec<String> = symbols
+        .iter()
+        .map(|symbol| {
+            // undeclared variables keep their '?' as well
+            let lexeme_length = symbol.name_pos.end_offset - symbol.name_pos.offset;
+            if lexeme_length == symbol.name.len() + 1 {
+                format!("?{}", symbol.name)
+            } else {
+                term(symbol.name, scope)
+            }
+        })
+        .collect();
+    terms.join(" ")
+}
+
+fn domain_blocks<'b, 'a>(domain: &'b DomainAST<'a>) -> Vec<(Option<usize>, Block<'b, 'a>)> {
+    let mut blocks = vec![];
+    if !domain.requirements.is_empty() {
+        let offset = domain.requirement_positions.first().map(|pos| pos.offset);
+        blocks.push((offset, Block::Requirements(&domain.requirements)));
+    }
+    if let Some(types) = &domain.types {
+        blocks.push((symbols_offset(types), Block::Types(types)));
+    }
+    if let Some(constants) = &domain.constants {
+        blocks.push((symbols_offset(constants), Block::Constants(constants)));
+    }
+    if !domain.predicates.is_empty() {
+        let offset = domain.predicates.first().map(|p| p.name_pos.offset);
+        blocks.push((offset, Block::Predicates(&domain.predicates)));
+    }
+    if !domain.functions.is_empty() {
+        let offset = domain.functions.first().map(|f| f.name_pos.offset);
+        blocks.push((offset, Block::Functions(&domain.functions)));
+    }
+    for task in domain.compound_tasks.iter() {
+        blocks.push((Some(task.name_pos.offset), Block::Task(task)));
+    }
+    for method in domain.methods.iter() {
+        blocks.push((Some(method.name.name_pos.offset), Block::Method(method)));
+    }
+    for action in domain.actions.iter() {
+        blocks.push((Some(action.name_pos.offset), Block::Action(action)));
+    }
+    blocks
+}
+
+// offsets of the elements of the blocks that collect the elements of several blocks of the source
+fn element_offsets(block: &Block) -> Vec<usize> {
+    match block {
+        Block::Types(symbols) | Block::Constants(symbols) | Block::Objects(symbols) => {
+            symbols.iter().map(|symbol| symbol.name_pos.offset).collect()
+        }
+        Block::Predicates(predicates) | Block::Functions(predicates) => {
+            predicates.iter().map(|predicate| predicate.name_pos.offset).collect()
+        }
+        Block::InitialState(facts, values) => facts
+            .iter()
+            .map(|fact| fact.name_pos.offset)
+            .chain(values.iter().map(|value| value.function.name_pos.offset))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// e.g. "?a ?b - location ?v - vehicle"
+pub fn parameter_list(symbols: &Vec<Symbol>) -> String {
+    parameter_groups(symbols).join(" ")
+}
+
+// e.g. ["?a ?b - location", "?v - vehicle"]
+fn parameter_groups(symbols: &Vec<Symbol>) -> Vec<String> {
+    typed_groups(symbols)
+        .iter()
+        .map(|(group, symbol_type)| {
+            let names: Vec<String> = group
+                .iter()
+                .map(|symbol| format!("?{}", symbol.name))
+                .collect();
+            match symbol_type {
+                Some(symbol_type) => format!("{} - {}", names.join(" "), symbol_type),
+                None => names.join(" "),
+            }
+        })
+        .collect()
+}
+
+// consecutive symbols of the same type, and the untyped symbols at the end
+// (otherwise, the type of the next group would apply to them as well)
+fn typed_groups<'b, 'a>(symbols: &'b Vec<Symbol<'a>>) -> Vec<(Vec<&'b Symbol<'a>>, Option<&'a str>)> {
+    let mut groups: Vec<(Vec<&Symbol>, Option<&str>)> = vec![];
+    let mut untyped = vec![];
+    for symbol in symbols.iter() {
+        match symbol.symbol_type {
+            None => untyped.push(symbol),
+            Some(symbol_type) => match groups.last_mut() {
+                Some((group, Some(last_type))) if *last_type == symbol_type => group.push(symbol),
+                _ => groups.push((vec![symbol], Some(symbol_type))),
+            },
+        }
+    }
+    if !untyped.is_empty() {
+        groups.push((untyped, None));
+    }
+    groups
+}
+
+fn symbols_offset(symbols: &Vec<Symbol>) -> Option<usize> {
+    symbols.first().map(|symbol| symbol.name_pos.offset)
+}
+
+fn subtask_offset(subtask: &Subtask) -> usize {
+    match &subtask.id {
+        Some(id) => id.name_pos.offset,
+        None => subtask.task.name_pos.offset,
+    }
+}
+
+// offset of the first symbol of the formula, and the
+// number of parentheses that are opened before it
+fn formula_offset(formula: &Formula) -> Option<(usize, usize)> {
+    let (offset, depth) = match formula {
+        Formula::Atom(predicate) => (predicate.name_pos.offset, 0),
+        Formula::Not(operand) => formula_offset(operand)?,
+        Formula::And(operands) | Formula::Or(operands) | Formula::Xor(operands) => {
+            formula_offset(operands.first()?)?
+        }
+        Formula::Imply(antecedent, _) => formula_offset(antecedent.first()?)?,
+        Formula::Exists(variables, _) | Formula::ForAll(variables, _) => {
+            (symbols_offset(variables)?, 1)
+        }
+        Formula::Comparison(_, lhs, _) => numeric_offset(lhs)?,
+        Formula::Empty | Formula::Equals(..) => return None,
+    };
+    Some((offset, depth + 1))
+}
+
+// offset of the first symbol of the effect, and the
+// number of parentheses that are opened before it
+fn effect_offset(effect: &Effect) -> Option<(usize, usize)> {
+    let (offset, depth) = match effect {
+        Effect::Add(predicate) => (predicate.name_pos.offset, 0),
+        Effect::Delete(predicate) | Effect::Numeric(_, predicate, _) => (predicate.name_pos.offset, 1),
+        Effect::And(operands) | Effect::OneOf(operands) => effect_offset(operands.first()?)?,
+        Effect::ForAll(variables, _) => (symbols_offset(variables)?, 1),
+        Effect::When(condition, _) => formula_offset(condition)?,
+        Effect::Empty => return None,
+    };
+    Some((offset, depth + 1))
+}
+
+fn numeric_offset(expression: &NumericExpression) -> Option<(usize, usize)> {
+    match expression {
+        NumericExpression::Number(_, position) => Some((position.offset, 0)),
+        NumericExpression::Function(function) => Some((function.name_pos.offset, 1)),
+        NumericExpression::Operation(_, operands) => {
+            let (offset, depth) = numeric_offset(operands.first()?)?;
+            Some((offset, depth + 1))
+        }
+    }
+}
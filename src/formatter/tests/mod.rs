@@ -0,0 +1,4 @@
+mod printer_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
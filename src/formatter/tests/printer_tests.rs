@@ -0,0 +1,372 @@
+use super::*;
+
+fn format(program: &str) -> String {
+    format_with(program, FormatOptions::default())
+}
+
+fn format_with(program: &str, options: FormatOptions) -> String {
+    let program = program.as_bytes().to_vec();
+    let parser = Parser::new(LexicalAnalyzer::new(&program));
+    let printer = PrettyPrinter::with_comments(&program, options);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => printer.print_domain(&d),
+        AbstractSyntaxTree::Problem(p) => printer.print_problem(&p),
+    }
+}
+
+#[test]
+pub fn format_domain_test() {
+    let domain = "(define (domain transport) (:requirements :hierarchy :typing)
+        (:types location vehicle - object truck - vehicle)
+        (:predicates (road ?a ?b - location) (at ?v - vehicle ?l - location) (done))
+        (:task get-to :parameters (?v - vehicle ?l - location))
+        (:method m-drive :parameters (?v - vehicle ?from ?to - location)
+            :task (get-to ?v ?to)
+            :precondition (and (at ?v ?from) (not (= ?from ?to)))
+            :tasks (and (t1 (drive ?v ?from ?to)) (t2 (finish)))
+            :order (and (< t1 t2)))
+        (:method m-stay :parameters (?v - vehicle ?l - location)
+            :task (get-to ?v ?l)
+            :ordered-tasks (and))
+        (:action drive :parameters (?v - vehicle ?from ?to - location)
+            :precondition (and (at ?v ?from) (road ?from ?to))
+            :effect (and (not (at ?v ?from)) (at ?v ?to)))
+        (:action finish :parameters () :effect (forall (?v - vehicle) (done))))";
+    let expected = "\
+(define (domain transport)
+  (:requirements :hierarchy :typing)
+
+  (:types
+    location vehicle - object
+    truck - vehicle
+  )
+
+  (:predicates
+    (road ?a ?b - location)
+    (at ?v - vehicle ?l - location)
+    (done)
+  )
+
+  (:task get-to :parameters (?v - vehicle ?l - location))
+
+  (:method m-drive
+    :parameters (?v - vehicle ?from ?to - location)
+    :task (get-to ?v ?to)
+    :precondition (and
+      (at ?v ?from)
+      (not (= ?from ?to))
+    )
+    :subtasks (and
+      (t1 (drive ?v ?from ?to))
+      (t2 (finish))
+    )
+    :ordering (and
+      (< t1 t2)
+    )
+  )
+
+  (:method m-stay
+    :parameters (?v - vehicle ?l - location)
+    :task (get-to ?v ?l)
+    :ordered-subtasks (and)
+  )
+
+  (:action drive
+    :parameters (?v - vehicle ?from ?to - location)
+    :precondition (and
+      (at ?v ?from)
+      (road ?from ?to)
+    )
+    :effect (and
+      (not (at ?v ?from))
+      (at ?v ?to)
+    )
+  )
+
+  (:action finish
+    :parameters ()
+    :effect (forall (?v - vehicle)
+      (done)
+    )
+  )
+)
+";
+    let formatted = format(domain);
+    assert_eq!(formatted, expected);
+    // formatting is idempotent
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn format_problem_test() {
+    let problem = "(define (problem p1) (:domain transport)
+        (:objects l1 l2 - location t1 - truck)
+        (:htn :parameters (?l - location) :ordered-subtasks (and (task0 (get-to t1 ?l)))
+            :constraints (and (not (= ?l l1))))
+        (:init (road l1 l2) (at t1 l1))
+        (:goal (and (at t1 l2))))";
+    let expected = "\
+(define (problem p1)
+  (:domain transport)
+  (:objects
+    l1 l2 - location
+    t1 - truck
+  )
+
+  (:htn
+    :parameters (?l - location)
+    :ordered-subtasks (and
+      (task0 (get-to t1 ?l))
+    )
+    :constraints (and
+      (not (= ?l l1))
+    )
+  )
+
+  (:init
+    (road l1 l2)
+    (at t1 l1)
+  )
+
+  (:goal (and
+    (at t1 l2)
+  ))
+)
+";
+    let formatted = format(problem);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn preserve_comments_test() {
+    let domain = "; transport domain
+(define (domain transport)
+    (:predicates
+        ; where the truck is
+        (at ?l)   (road ?a ?b) ; static
+    )
+    ; moves the truck
+    (:action drive :parameters (?from ?to)
+        :precondition (and
+            ; the truck must be at the origin
+            (at ?from) (road ?from ?to))
+        :effect (at ?to)))
+; end of file
+";
+    let expected = "\
+; transport domain
+(define (domain transport)
+  (:predicates
+    ; where the truck is
+    (at ?l)
+    (road ?a ?b) ; static
+  )
+
+  ; moves the truck
+  (:action drive
+    :parameters (?from ?to)
+    :precondition (and
+      ; the truck must be at the origin
+      (at ?from)
+      (road ?from ?to)
+    )
+    :effect (at ?to)
+  )
+  ; end of file
+)
+";
+    let formatted = format(domain);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn wrap_long_parameters_test() {
+    let domain = "(define (domain d)
+        (:types vehicle location package)
+        (:predicates (in ?package - package ?vehicle - vehicle ?location - location))
+        (:action load :parameters (?package - package ?vehicle - vehicle ?location - location)
+            :effect (in ?package ?vehicle ?location)))";
+    let options = FormatOptions {
+        indent_width: 4,
+        use_tabs: false,
+        max_line_length: 40,
+    };
+    let expected = "\
+(define (domain d)
+    (:types
+        vehicle location package
+    )
+
+    (:predicates
+        (in
+            ?package - package
+            ?vehicle - vehicle
+            ?location - location
+        )
+    )
+
+    (:action load
+        :parameters (
+            ?package - package
+            ?vehicle - vehicle
+            ?location - location
+        )
+        :effect (in ?package ?vehicle ?location)
+    )
+)
+";
+    assert_eq!(format_with(domain, options), expected);
+    assert_eq!(format_with(expected, options), expected);
+}
+
+#[test]
+pub fn tab_indentation_test() {
+    let domain = "(define (domain d) (:predicates (p) (q)))";
+    let options = FormatOptions {
+        use_tabs: true,
+        ..FormatOptions::default()
+    };
+    let expected = "(define (domain d)\n\t(:predicates\n\t\t(p)\n\t\t(q)\n\t)\n)\n";
+    assert_eq!(format_with(domain, options), expected);
+}
+
+#[test]
+pub fn format_range_test() {
+    let program = "(define (domain d)
+  (:predicates (p ?a) (q))
+  (:task t :parameters ())
+  (:action a :parameters (?x)
+      :precondition (and (p ?x) (q))  :effect (not (q))))"
+        .as_bytes()
+        .to_vec();
+    let parser = Parser::new(LexicalAnalyzer::new(&program));
+    let AbstractSyntaxTree::Domain(domain) = parser.parse().unwrap() else {
+        panic!("expected a domain");
+    };
+    // a range inside the action only formats the action
+    let offset = program.windows(6).position(|w| w == b"(and (").unwrap();
+    let printer = PrettyPrinter::with_comments(&program, FormatOptions::default());
+    let edits = printer.print_domain_range(&domain, (offset, offset + 1));
+    assert_eq!(edits.len(), 1);
+    let (start, end, text) = &edits[0];
+    let action = program.windows(9).position(|w| w == b"(:action ").unwrap();
+    assert_eq!((*start, *end), (action, program.len() - 1));
+    let expected = "\
+(:action a
+    :parameters (?x)
+    :precondition (and
+      (p ?x)
+      (q)
+    )
+    :effect (not (q))
+  )";
+    assert_eq!(text, expected);
+}
+
+#[test]
+pub fn format_conditional_effect_test() {
+    let domain = "(define (domain lights) (:requirements :conditional-effects)
+        (:predicates (on ?r) (lit ?r))
+        (:action switch :parameters (?r)
+            :effect (and (when (on ?r) (not (on ?r)))
+                ; every lit room
+                (forall (?s) (when (and (on ?s) (not (lit ?s))) (and (lit ?s) (increase (total-cost) 1)))))))";
+    let expected = "\
+(define (domain lights)
+  (:requirements :conditional-effects)
+
+  (:predicates
+    (on ?r)
+    (lit ?r)
+  )
+
+  (:action switch
+    :parameters (?r)
+    :effect (and
+      (when
+        (on ?r)
+        (not (on ?r))
+      )
+      ; every lit room
+      (forall (?s)
+        (when
+          (and
+            (on ?s)
+            (not (lit ?s))
+          )
+          (and
+            (lit ?s)
+            (increase (total-cost) 1)
+          )
+        )
+      )
+    )
+  )
+)
+";
+    let formatted = format(domain);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
+
+#[test]
+pub fn format_durative_action_test() {
+    let domain = "(define (domain rovers) (:requirements :hierarchy :durative-actions)
+        (:predicates (at ?r ?w) (visited ?w))
+        (:task visit :parameters (?r ?w))
+        (:method m-visit :parameters (?r ?a ?b)
+            :task (visit ?r ?b)
+            :subtasks (and (t1 (navigate ?r ?a ?b)) (t2 (navigate ?r ?b ?a)))
+            :constraints (and (< (end t1) (start t2))))
+        (:durative-action navigate :parameters (?r ?a ?b)
+            :duration (= ?duration (distance ?a ?b))
+            :condition (and (at start (at ?r ?a)) (over all (not (visited ?b))))
+            :effect (and (at start (not (at ?r ?a))) (at end (and (at ?r ?b) (visited ?b))))))";
+    let expected = "\
+(define (domain rovers)
+  (:requirements :hierarchy :durative-actions)
+
+  (:predicates
+    (at ?r ?w)
+    (visited ?w)
+  )
+
+  (:task visit :parameters (?r ?w))
+
+  (:method m-visit
+    :parameters (?r ?a ?b)
+    :task (visit ?r ?b)
+    :subtasks (and
+      (t1 (navigate ?r ?a ?b))
+      (t2 (navigate ?r ?b ?a))
+    )
+    :constraints (and
+      (< (end t1) (start t2))
+    )
+  )
+
+  (:durative-action navigate
+    :parameters (?r ?a ?b)
+    :duration (= ?duration (distance ?a ?b))
+    :condition (and
+      (at start (at ?r ?a))
+      (over all (not (visited ?b)))
+    )
+    :effect (and
+      (at start (not (at ?r ?a)))
+      (at end
+        (and
+          (at ?r ?b)
+          (visited ?b)
+        )
+      )
+    )
+  )
+)
+";
+    let formatted = format(domain);
+    assert_eq!(formatted, expected);
+    assert_eq!(format(&formatted), expected);
+}
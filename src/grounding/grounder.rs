@@ -0,0 +1,744 @@
+use std::collections::{HashMap, HashSet};
+
+use petgraph::algo::has_path_connecting;
+
+use super::*;
+
+// a ground atom, or a ground task, e.g. ("on", ["b1", "b2"])
+type Atom = (String, Vec<String>);
+
+type Bindings<'a> = HashMap<&'a str, &'a str>;
+
+// literals that can be evaluated as soon as their variables are bound
+enum Filter<'a> {
+    Static(bool, &'a Predicate<'a>),
+    Equal(bool, &'a str, &'a str),
+}
+
+// an instance of a method, whose subtasks are not yet known to be decomposable
+struct MethodCandidate<'a> {
+    method: &'a Method<'a>,
+    args: Vec<&'a str>,
+    task: Atom,
+    subtasks: Vec<Atom>,
+    precondition: GroundFormula,
+}
+
+pub struct Grounder<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    symbol_table: &'a SymbolTable<'a>,
+    // constants of the domain and objects of the problem (in the order of declaration)
+    objects: Vec<&'a Symbol<'a>>,
+    // predicates that do not appear in the effect of any action
+    static_predicates: HashSet<&'a str>,
+    init_state: HashSet<Atom>,
+    // functions that are changed by the numeric effects of actions
+    fluent_functions: HashSet<&'a str>,
+    init_values: HashMap<Atom, f64>,
+    // whether a comparison of fluent functions was assumed to hold
+    is_relaxed: bool,
+    facts: Vec<GroundFact>,
+    fact_ids: HashMap<Atom, u32>,
+}
+
+impl<'a> Grounder<'a> {
+    // the domain and the problem are expected to be verified, so names are compared
+    // exactly (every reference has the case of its declaration, as in the semantic
+    // analysis, and only the names of plans are compared case insensitively)
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        symbol_table: &'a SymbolTable<'a>,
+    ) -> Grounder<'a> {
+        let mut objects = vec![];
+        let mut object_names = HashSet::new();
+        for object in domain.constants.iter().flatten().chain(problem.objects.iter()) {
+            if object_names.insert(object.name) {
+                objects.push(object);
+            }
+        }
+        let mut fluent_predicates = HashSet::new();
+        let mut fluent_functions = HashSet::new();
+        for action in domain.actions.iter() {
+            if let Some(effect) = &action.effects {
+                fluent_predicates.extend(effect.get_predicates().iter().map(|predicate| predicate.name));
+                collect_numeric_targets(effect, &mut fluent_functions);
+            }
+        }
+        let static_predicates = domain
+            .predicates
+            .iter()
+            .map(|predicate| predicate.name)
+            .filter(|name| !fluent_predicates.contains(name))
+            .collect();
+        let init_state = problem
+            .init_state
+            .iter()
+            .map(|predicate| ground_atom(predicate, &Bindings::new()))
+            .collect();
+        let init_values = problem
+            .init_values
+            .iter()
+            .filter_map(|init| Some((ground_atom(&init.function, &Bindings::new()), init.value.parse().ok()?)))
+            .collect();
+        Grounder {
+            domain,
+            problem,
+            symbol_table,
+            objects,
+            static_predicates,
+            init_state,
+            fluent_functions,
+            init_values,
+            is_relaxed: false,
+            facts: vec![],
+            fact_ids: HashMap::new(),
+        }
+    }
+
+    pub fn ground(mut self) -> GroundedModel {
+        let (domain, problem) = (self.domain, self.problem);
+        let mut model = GroundedModel::default();
+        // facts of the initial state get the lowest ids
+        for predicate in problem.init_state.iter() {
+            if !self.static_predicates.contains(predicate.name) {
+                let id = self.fact_id(ground_atom(predicate, &Bindings::new()));
+                if !model.initial_state.contains(&id) {
+                    model.initial_state.push(id);
+                }
+            }
+        }
+        let mut action_ids = HashMap::new();
+        for action in domain.actions.iter() {
+            let mut filters = vec![];
+            if let Some(precondition) = &action.preconditions {
+                self.collect_filters(precondition, &mut filters);
+            }
+            for values in self.enumerate(&action.parameters, &filters) {
+                let bindings = bind(&action.parameters, &values);
+                let (precondition, effects, end) = match &action.timing {
+                    Some(timing) => {
+                        let Some(start) = self.ground_snapshot(timing, TimeSpecifier::AtStart, &bindings) else {
+                            continue;
+                        };
+                        let Some(end) = self.ground_snapshot(timing, TimeSpecifier::AtEnd, &bindings) else {
+                            continue;
+                        };
+                        (start.precondition, start.effects, Some(end))
+                    }
+                    None => {
+                        let precondition = match &action.preconditions {
+                            Some(precondition) => self.ground_formula(precondition, &bindings),
+                            None => GroundFormula::True,
+                        };
+                        if precondition == GroundFormula::False {
+                            continue;
+                        }
+                        let mut effects = vec![];
+                        if let Some(effect) = &action.effects {
+                            self.ground_effect(effect, &bindings, &GroundFormula::True, &mut effects);
+                        }
+                        (precondition, effects, None)
+                    }
+                };
+                let id = model.actions.len() as u32;
+                let args: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                action_ids.insert((action.name.to_string(), args.clone()), id);
+                model.actions.push(GroundAction {
+                    id,
+                    name: action.name.to_string(),
+                    args,
+                    precondition,
+                    effects,
+                    end,
+                });
+            }
+        }
+        let candidates = self.method_candidates();
+        // a method is only kept if each of its subtasks is either a ground action,
+        // or a task that is decomposed by another kept method
+        let mut is_decomposable = vec![false; candidates.len()];
+        let mut decomposable_tasks = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, candidate) in candidates.iter().enumerate() {
+                if is_decomposable[index] {
+                    continue;
+                }
+                let has_decomposable_subtasks = candidate.subtasks.iter().all(|subtask| {
+                    action_ids.contains_key(subtask) || decomposable_tasks.contains(subtask)
+                });
+                if has_decomposable_subtasks {
+                    is_decomposable[index] = true;
+                    decomposable_tasks.insert(candidate.task.clone());
+                    changed = true;
+                }
+            }
+        }
+        let mut task_ids = HashMap::new();
+        for (candidate, _) in candidates
+            .into_iter()
+            .zip(is_decomposable.iter())
+            .filter(|(_, is_decomposable)| **is_decomposable)
+        {
+            let task = task_id(&mut model.tasks, &mut task_ids, &candidate.task);
+            let subtasks = candidate
+                .subtasks
+                .iter()
+                .map(|subtask| match action_ids.get(subtask) {
+                    Some(id) => GroundSubtask::Action(*id),
+                    None => GroundSubtask::Task(task_id(&mut model.tasks, &mut task_ids, subtask)),
+                })
+                .collect();
+            model.methods.push(GroundMethod {
+                id: model.methods.len() as u32,
+                name: candidate.method.name.name.to_string(),
+                args: candidate.args.iter().map(|arg| arg.to_string()).collect(),
+                task,
+                precondition: candidate.precondition,
+                network: GroundNetwork {
+                    subtasks,
+                    orderings: orderings(&candidate.method.tn),
+                },
+            });
+        }
+        if let Some(init_tn) = &problem.init_tn {
+            let no_parameters = vec![];
+            let parameters = init_tn.parameters.as_ref().unwrap_or(&no_parameters);
+            let mut filters = vec![];
+            collect_constraint_filters(&init_tn.tn, &mut filters);
+            // the parameters of the initial task network are existentially quantified
+            for values in self.enumerate(parameters, &filters) {
+                let bindings = bind(parameters, &values);
+                let subtasks: Vec<Atom> = init_tn
+                    .tn
+                    .subtasks
+                    .iter()
+                    .map(|subtask| ground_task(subtask, &bindings))
+                    .collect();
+                let is_decomposable = subtasks.iter().all(|subtask| {
+                    action_ids.contains_key(subtask) || decomposable_tasks.contains(subtask)
+                });
+                if is_decomposable {
+                    let subtasks = subtasks
+                        .iter()
+                        .map(|subtask| match action_ids.get(subtask) {
+                            Some(id) => GroundSubtask::Action(*id),
+                            None => GroundSubtask::Task(task_ids[subtask]),
+                        })
+                        .collect();
+                    model.initial_networks.push(GroundNetwork {
+                        subtasks,
+                        orderings: orderings(&init_tn.tn),
+                    });
+                }
+            }
+        }
+        if let Some(goal) = &problem.goal {
+            model.goal = Some(self.ground_formula(goal, &Bindings::new()));
+        }
+        model.facts = self.facts;
+        model.is_relaxed = self.is_relaxed;
+        model
+    }
+
+    fn method_candidates(&mut self) -> Vec<MethodCandidate<'a>> {
+        let domain = self.domain;
+        let mut candidates = vec![];
+        for method in domain.methods.iter() {
+            let mut filters = vec![];
+            if let Some(precondition) = &method.precondition {
+                self.collect_filters(precondition, &mut filters);
+            }
+            collect_constraint_filters(&method.tn, &mut filters);
+            for values in self.enumerate(&method.params, &filters) {
+                let bindings = bind(&method.params, &values);
+                let precondition = match &method.precondition {
+                    Some(precondition) => self.ground_formula(precondition, &bindings),
+                    None => GroundFormula::True,
+                };
+                if precondition == GroundFormula::False {
+                    continue;
+                }
+                let task = (
+                    method.task.name.to_string(),
+                    method
+                        .task_terms
+                        .iter()
+                        .map(|term| resolve(term.name, &bindings).to_string())
+                        .collect(),
+                );
+                let subtasks = method
+                    .tn
+                    .subtasks
+                    .iter()
+                    .map(|subtask| ground_task(subtask, &bindings))
+                    .collect();
+                candidates.push(MethodCandidate {
+                    method,
+                    args: values,
+                    task,
+                    subtasks,
+                    precondition,
+                });
+            }
+        }
+        candidates
+    }
+
+    fn fact_id(&mut self, atom: Atom) -> u32 {
+        if let Some(id) = self.fact_ids.get(&atom) {
+            return *id;
+        }
+        let id = self.facts.len() as u32;
+        self.facts.push(GroundFact {
+            id,
+            predicate: atom.0.clone(),
+            args: atom.1.clone(),
+        });
+        self.fact_ids.insert(atom, id);
+        id
+    }
+
+    fn is_instance(&self, object: &Symbol<'a>, expected: Option<&'a str>) -> bool {
+        let expected = match expected {
+            None | Some("object") => return true,
+            Some(expected) => expected,
+        };
+        match object.symbol_type {
+            None => false,
+            Some(found) if found == expected => true,
+            Some(found) => {
+                let type_hierarchy = &self.symbol_table.type_hierarchy;
+                type_hierarchy.contains_node(found)
+                    && type_hierarchy.contains_node(expected)
+                    && has_path_connecting(type_hierarchy, found, expected, None)
+            }
+        }
+    }
+
+    fn objects_of_type(&self, expected: Option<&'a str>) -> Vec<&'a str> {
+        self.objects
+            .iter()
+            .filter(|object| self.is_instance(object, expected))
+            .map(|object| object.name)
+            .collect()
+    }
+
+    // type correct values of the parameters that satisfy the filters
+    fn enumerate(&self, parameters: &[Symbol<'a>], filters: &Vec<Filter<'a>>) -> Vec<Vec<&'a str>> {
+        let candidates: Vec<Vec<&'a str>> = parameters
+            .iter()
+            .map(|parameter| self.objects_of_type(parameter.symbol_type))
+            .collect();
+        // each filter is evaluated as soon as its last parameter is bound
+        let mut filters_at: Vec<Vec<&Filter>> = (0..=parameters.len()).map(|_| vec![]).collect();
+        for filter in filters.iter() {
+            let terms: Vec<&str> = match filter {
+                Filter::Static(_, predicate) => {
+                    predicate.variables.iter().map(|var| var.name).collect()
+                }
+                Filter::Equal(_, a, b) => vec![a, b],
+            };
+            let depth = terms
+                .iter()
+                .filter_map(|term| parameters.iter().rposition(|parameter| parameter.name == *term))
+                .map(|index| index + 1)
+                .max()
+                .unwrap_or(0);
+            filters_at[depth].push(filter);
+        }
+        let mut result = vec![];
+        let mut values = vec![];
+        self.extend(parameters, &candidates, &filters_at, &mut values, &mut result);
+        result
+    }
+
+    fn extend(
+        &self,
+        parameters: &[Symbol<'a>],
+        candidates: &Vec<Vec<&'a str>>,
+        filters_at: &Vec<Vec<&Filter<'a>>>,
+        values: &mut Vec<&'a str>,
+        result: &mut Vec<Vec<&'a str>>,
+    ) {
+        let depth = values.len();
+        let bindings = bind(&parameters[..depth], values);
+        if !filters_at[depth]
+            .iter()
+            .all(|filter| self.holds(filter, &bindings))
+        {
+            return;
+        }
+        if depth == parameters.len() {
+            result.push(values.clone());
+            return;
+        }
+        for candidate in candidates[depth].iter() {
+            values.push(candidate);
+            self.extend(parameters, candidates, filters_at, values, result);
+            values.pop();
+        }
+    }
+
+    fn holds(&self, filter: &Filter<'a>, bindings: &Bindings<'a>) -> bool {
+        match filter {
+            Filter::Static(is_positive, predicate) => {
+                self.init_state.contains(&ground_atom(predicate, bindings)) == *is_positive
+            }
+            Filter::Equal(is_equal, a, b) => {
+                (resolve(a, bindings) == resolve(b, bindings)) == *is_equal
+            }
+        }
+    }
+
+    // static literals and equalities of the conjunctive part of a formula
+    fn collect_filters(&self, formula: &'a Formula<'a>, filters: &mut Vec<Filter<'a>>) {
+        match formula {
+            Formula::Atom(predicate) if self.static_predicates.contains(predicate.name) => {
+                filters.push(Filter::Static(true, predicate));
+            }
+            Formula::Not(new_formula) => match &**new_formula {
+                Formula::Atom(predicate) if self.static_predicates.contains(predicate.name) => {
+                    filters.push(Filter::Static(false, predicate));
+                }
+                Formula::Equals(a, b) => filters.push(Filter::Equal(false, a.name, b.name)),
+                _ => {}
+            },
+            Formula::Equals(a, b) => filters.push(Filter::Equal(true, a.name, b.name)),
+            Formula::And(new_formula) => {
+                for f in new_formula {
+                    self.collect_filters(f, filters);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // static predicates are evaluated in the initial state
+    fn ground_formula(&mut self, formula: &Formula<'a>, bindings: &Bindings<'a>) -> GroundFormula {
+        match formula {
+            Formula::Empty => GroundFormula::True,
+            Formula::Atom(predicate) => {
+                let atom = ground_atom(predicate, bindings);
+                if !self.static_predicates.contains(predicate.name) {
+                    GroundFormula::Fact(self.fact_id(atom))
+                } else if self.init_state.contains(&atom) {
+                    GroundFormula::True
+                } else {
+                    GroundFormula::False
+                }
+            }
+            Formula::Not(new_formula) => negate(self.ground_formula(new_formula, bindings)),
+            Formula::And(new_formula) => conjunction(
+                new_formula
+                    .iter()
+                    .map(|f| self.ground_formula(f, bindings))
+                    .collect(),
+            ),
+            Formula::Or(new_formula) => disjunction(
+                new_formula
+                    .iter()
+                    .map(|f| self.ground_formula(f, bindings))
+                    .collect(),
+            ),
+            // exactly one of the operands holds
+            Formula::Xor(new_formula) => {
+                let operands: Vec<GroundFormula> = new_formula
+                    .iter()
+                    .map(|f| self.ground_formula(f, bindings))
+                    .collect();
+                let mut disjuncts = vec![];
+                for (i, operand) in operands.iter().enumerate() {
+                    let mut conjuncts = vec![operand.clone()];
+                    for (j, other) in operands.iter().enumerate() {
+                        if i != j {
+                            conjuncts.push(negate(other.clone()));
+                        }
+                    }
+                    disjuncts.push(conjunction(conjuncts));
+                }
+                disjunction(disjuncts)
+            }
+            Formula::Imply(ps, qs) => {
+                let antecedent = conjunction(ps.iter().map(|p| self.ground_formula(p, bindings)).collect());
+                let consequent = conjunction(qs.iter().map(|q| self.ground_formula(q, bindings)).collect());
+                disjunction(vec![negate(antecedent), consequent])
+            }
+            Formula::Exists(vars, new_formula) => {
+                let operands = self
+                    .quantify(vars, bindings)
+                    .iter()
+                    .map(|extended| self.ground_formula(new_formula, extended))
+                    .collect();
+                disjunction(operands)
+            }
+            Formula::ForAll(vars, new_formula) => {
+                let operands = self
+                    .quantify(vars, bindings)
+                    .iter()
+                    .map(|extended| self.ground_formula(new_formula, extended))
+                    .collect();
+                conjunction(operands)
+            }
+            Formula::Equals(a, b) => {
+                if resolve(a.name, bindings) == resolve(b.name, bindings) {
+                    GroundFormula::True
+                } else {
+                    GroundFormula::False
+                }
+            }
+            // static functions are evaluated in the initial state, while comparisons of
+            // fluent ones are assumed to hold (the grounding is propositional)
+            Formula::Comparison(operation, lhs, rhs) => {
+                let mut functions = lhs.get_functions().into_iter().chain(rhs.get_functions());
+                if functions.any(|function| self.fluent_functions.contains(function.name)) {
+                    self.is_relaxed = true;
+                    return GroundFormula::True;
+                }
+                let value_of = |function: &Predicate| self.init_values.get(&ground_atom(function, bindings)).copied();
+                match (lhs.evaluate(&value_of), rhs.evaluate(&value_of)) {
+                    (Some(lhs), Some(rhs)) if compare(operation, lhs, rhs) => GroundFormula::True,
+                    // undefined values do not satisfy any comparison
+                    _ => GroundFormula::False,
+                }
+            }
+        }
+    }
+
+    // the conditions at the time (and the invariant) of a durative action, and its
+    // effects at the time (none if the conditions can not hold)
+    fn ground_snapshot(
+        &mut self,
+        timing: &Timing<'a>,
+        time: TimeSpecifier,
+        bindings: &Bindings<'a>,
+    ) -> Option<GroundSnapshot> {
+        let precondition = conjunction(vec![
+            self.ground_formula(&timing.condition_at(time), bindings),
+            self.ground_formula(&timing.condition_at(TimeSpecifier::OverAll), bindings),
+        ]);
+        if precondition == GroundFormula::False {
+            return None;
+        }
+        let mut effects = vec![];
+        self.ground_effect(&timing.effect_at(time), bindings, &GroundFormula::True, &mut effects);
+        Some(GroundSnapshot { precondition, effects })
+    }
+
+    // effects with the same condition are merged
+    fn ground_effect(
+        &mut self,
+        effect: &Effect<'a>,
+        bindings: &Bindings<'a>,
+        condition: &GroundFormula,
+        effects: &mut Vec<GroundEffect>,
+    ) {
+        match effect {
+            Effect::Add(predicate) => {
+                let id = self.fact_id(ground_atom(predicate, bindings));
+                effect_with_condition(effects, condition).add.push(id);
+            }
+            Effect::Delete(predicate) => {
+                let id = self.fact_id(ground_atom(predicate, bindings));
+                effect_with_condition(effects, condition).delete.push(id);
+            }
+            Effect::And(new_effects) => {
+                for e in new_effects {
+                    self.ground_effect(e, bindings, condition, effects);
+                }
+            }
+            Effect::ForAll(vars, new_effect) => {
+                for extended in self.quantify(vars, bindings) {
+                    self.ground_effect(new_effect, &extended, condition, effects);
+                }
+            }
+            Effect::When(new_condition, new_effect) => {
+                let new_condition = conjunction(vec![
+                    condition.clone(),
+                    self.ground_formula(new_condition, bindings),
+                ]);
+                if new_condition != GroundFormula::False {
+                    self.ground_effect(new_effect, bindings, &new_condition, effects);
+                }
+            }
+            // the grounding is propositional and deterministic (i.e. numeric values
+            // and non-deterministic effects are ignored)
+            Effect::Empty | Effect::OneOf(_) | Effect::Numeric(_, _, _) => {}
+        }
+    }
+
+    // every extension of the bindings with type correct values of the variables
+    fn quantify(&self, vars: &Vec<Symbol<'a>>, bindings: &Bindings<'a>) -> Vec<Bindings<'a>> {
+        let mut result = vec![bindings.clone()];
+        for var in vars.iter() {
+            let objects = self.objects_of_type(var.symbol_type);
+            result = result
+                .into_iter()
+                .flat_map(|partial| {
+                    objects.iter().map(move |object| {
+                        let mut extended = partial.clone();
+                        extended.insert(var.name, object);
+                        extended
+                    })
+                })
+                .collect();
+        }
+        result
+    }
+}
+
+// the functions whose values are changed by the effect
+fn collect_numeric_targets<'a>(effect: &Effect<'a>, functions: &mut HashSet<&'a str>) {
+    match effect {
+        Effect::Numeric(_, function, _) => {
+            functions.insert(function.name);
+        }
+        Effect::And(effects) | Effect::OneOf(effects) => {
+            for e in effects {
+                collect_numeric_targets(e, functions);
+            }
+        }
+        Effect::ForAll(_, new_effect) | Effect::When(_, new_effect) => collect_numeric_targets(new_effect, functions),
+        Effect::Empty | Effect::Add(_) | Effect::Delete(_) => {}
+    }
+}
+
+fn collect_constraint_filters<'a>(tn: &'a HTN<'a>, filters: &mut Vec<Filter<'a>>) {
+    for constraint in tn.constraints.iter().flatten() {
+        match constraint {
+            Constraint::Equal(a, b) => filters.push(Filter::Equal(true, a, b)),
+            Constraint::NotEqual(a, b) => filters.push(Filter::Equal(false, a, b)),
+            // temporal constraints do not restrict the groundings
+            Constraint::Temporal(..) => {}
+        }
+    }
+}
+
+fn effect_with_condition<'b>(
+    effects: &'b mut Vec<GroundEffect>,
+    condition: &GroundFormula,
+) -> &'b mut GroundEffect {
+    match effects.iter().position(|effect| effect.condition == *condition) {
+        Some(index) => &mut effects[index],
+        None => {
+            effects.push(GroundEffect {
+                condition: condition.clone(),
+                add: vec![],
+                delete: vec![],
+            });
+            effects.last_mut().unwrap()
+        }
+    }
+}
+
+fn task_id(tasks: &mut Vec<GroundTask>, task_ids: &mut HashMap<Atom, u32>, task: &Atom) -> u32 {
+    if let Some(id) = task_ids.get(task) {
+        return *id;
+    }
+    let id = tasks.len() as u32;
+    tasks.push(GroundTask {
+        id,
+        name: task.0.clone(),
+        args: task.1.clone(),
+    });
+    task_ids.insert(task.clone(), id);
+    id
+}
+
+fn orderings(tn: &HTN) -> Vec<(usize, usize)> {
+    match &tn.orderings {
+        TaskOrdering::Total => (1..tn.subtasks.len()).map(|i| (i - 1, i)).collect(),
+        TaskOrdering::Partial(orderings) => {
+            let index_of = |name: &str| {
+                tn.subtasks
+                    .iter()
+                    .position(|subtask| subtask.id.as_ref().is_some_and(|id| id.name == name))
+            };
+            orderings
+                .iter()
+                .filter_map(|(a, b)| Some((index_of(a)?, index_of(b)?)))
+                .collect()
+        }
+    }
+}
+
+fn bind<'a>(parameters: &[Symbol<'a>], values: &[&'a str]) -> Bindings<'a> {
+    parameters
+        .iter()
+        .map(|parameter| parameter.name)
+        .zip(values.iter().copied())
+        .collect()
+}
+
+// the value of a term (i.e. either a bound variable or a constant)
+fn resolve<'a>(term: &'a str, bindings: &Bindings<'a>) -> &'a str {
+    bindings.get(term).copied().unwrap_or(term)
+}
+
+fn ground_atom(predicate: &Predicate, bindings: &Bindings) -> Atom {
+    (
+        predicate.name.to_string(),
+        predicate
+            .variables
+            .iter()
+            .map(|var| resolve(var.name, bindings).to_string())
+            .collect(),
+    )
+}
+
+fn ground_task(subtask: &Subtask, bindings: &Bindings) -> Atom {
+    (
+        subtask.task.name.to_string(),
+        subtask
+            .terms
+            .iter()
+            .map(|term| resolve(term.name, bindings).to_string())
+            .collect(),
+    )
+}
+
+fn negate(formula: GroundFormula) -> GroundFormula {
+    match formula {
+        GroundFormula::True => GroundFormula::False,
+        GroundFormula::False => GroundFormula::True,
+        GroundFormula::Not(f) => *f,
+        f => GroundFormula::Not(Box::new(f)),
+    }
+}
+
+fn conjunction(operands: Vec<GroundFormula>) -> GroundFormula {
+    let mut conjuncts = vec![];
+    for operand in operands {
+        match operand {
+            GroundFormula::True => {}
+            GroundFormula::False => return GroundFormula::False,
+            GroundFormula::And(nested) => conjuncts.extend(nested),
+            f => conjuncts.push(f),
+        }
+    }
+    match conjuncts.len() {
+        0 => GroundFormula::True,
+        1 => conjuncts.pop().unwrap(),
+        _ => GroundFormula::And(conjuncts),
+    }
+}
+
+fn disjunction(operands: Vec<GroundFormula>) -> GroundFormula {
+    let mut disjuncts = vec![];
+    for operand in operands {
+        match operand {
+            GroundFormula::False => {}
+            GroundFormula::True => return GroundFormula::True,
+            GroundFormula::Or(nested) => disjuncts.extend(nested),
+            f => disjuncts.push(f),
+        }
+    }
+    match disjuncts.len() {
+        0 => GroundFormula::False,
+        1 => disjuncts.pop().unwrap(),
+        _ => GroundFormula::Or(disjuncts),
+    }
+}
@@ -0,0 +1,11 @@
+mod model;
+mod grounder;
+mod reachability;
+#[cfg(test)]
+mod tests;
+
+use crate::semantic_analyzer::SymbolTable;
+use crate::syntactic_analyzer::*;
+
+pub use model::*;
+pub use grounder::Grounder;
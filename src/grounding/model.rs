@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+// ids are indices into the corresponding vectors of the model, and are
+// assigned in the order of declaration in the domain (and of the objects in the problem)
+#[derive(Debug, Default, Serialize)]
+pub struct GroundedModel {
+    // only the facts of fluent predicates (static ones are compiled away)
+    pub facts: Vec<GroundFact>,
+    pub actions: Vec<GroundAction>,
+    pub tasks: Vec<GroundTask>,
+    pub methods: Vec<GroundMethod>,
+    pub initial_state: Vec<u32>,
+    // one for each instance of the parameters of the initial task network (a single one if it
+    // has none) whose tasks can be decomposed, i.e. a plan has to refine any one of them
+    pub initial_networks: Vec<GroundNetwork>,
+    pub goal: Option<GroundFormula>,
+    // whether comparisons of functions that actions change were replaced by true (the
+    // model is propositional, so it is a relaxation of the problem then)
+    pub is_relaxed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundFact {
+    pub id: u32,
+    pub predicate: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GroundFormula {
+    True,
+    False,
+    Fact(u32),
+    Not(Box<GroundFormula>),
+    And(Vec<GroundFormula>),
+    Or(Vec<GroundFormula>),
+}
+
+// adds and deletes that take place when the condition holds (before the action)
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundEffect {
+    pub condition: GroundFormula,
+    pub add: Vec<u32>,
+    pub delete: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundAction {
+    pub id: u32,
+    pub name: String,
+    pub args: Vec<String>,
+    // (for a durative action, the conditions and effects of its start)
+    pub precondition: GroundFormula,
+    pub effects: Vec<GroundEffect>,
+    // only durative actions have an end, which directly follows their start
+    pub end: Option<GroundSnapshot>,
+}
+
+// the conditions (including the invariant) and the effects at a time point of a durative action
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundSnapshot {
+    pub precondition: GroundFormula,
+    pub effects: Vec<GroundEffect>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundTask {
+    pub id: u32,
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GroundSubtask {
+    Action(u32),
+    Task(u32),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundNetwork {
+    pub subtasks: Vec<GroundSubtask>,
+    // (i, j) means that subtask i precedes subtask j
+    pub orderings: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroundMethod {
+    pub id: u32,
+    pub name: String,
+    // values of the parameters of the method (in the order of declaration)
+    pub args: Vec<String>,
+    pub task: u32,
+    pub precondition: GroundFormula,
+    pub network: GroundNetwork,
+}
@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use super::*;
+
+impl GroundedModel {
+    // the facts that can be reached from the initial state when the deletes of
+    // actions are ignored (negative conditions are assumed to be satisfiable)
+    pub fn relaxed_reachable_facts(&self) -> HashSet<u32> {
+        let mut reached: HashSet<u32> = self.initial_state.iter().copied().collect();
+        let mut is_applied = vec![false; self.actions.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for action in self.actions.iter() {
+                if is_applied[action.id as usize] || !may_hold(&action.precondition, &reached) {
+                    continue;
+                }
+                let mut has_pending_effects = false;
+                changed |= apply(&action.effects, &mut reached, &mut has_pending_effects);
+                // the end of a durative action is applied after its start
+                if let Some(end) = &action.end {
+                    if may_hold(&end.precondition, &reached) {
+                        changed |= apply(&end.effects, &mut reached, &mut has_pending_effects);
+                    } else {
+                        has_pending_effects = true;
+                    }
+                }
+                // actions with unsatisfied conditional effects (or ends) are revisited
+                is_applied[action.id as usize] = !has_pending_effects;
+            }
+        }
+        reached
+    }
+}
+
+// adds the facts of the effects whose conditions may hold (and returns whether a fact is new)
+fn apply(effects: &[GroundEffect], reached: &mut HashSet<u32>, has_pending_effects: &mut bool) -> bool {
+    let mut changed = false;
+    for effect in effects.iter() {
+        if !may_hold(&effect.condition, reached) {
+            *has_pending_effects = true;
+            continue;
+        }
+        for fact in effect.add.iter() {
+            changed |= reached.insert(*fact);
+        }
+    }
+    changed
+}
+
+// whether the formula can hold in a state that only contains the reached facts
+fn may_hold(formula: &GroundFormula, reached: &HashSet<u32>) -> bool {
+    match formula {
+        GroundFormula::True | GroundFormula::Not(_) => true,
+        GroundFormula::False => false,
+        GroundFormula::Fact(fact) => reached.contains(fact),
+        GroundFormula::And(operands) => operands.iter().all(|f| may_hold(f, reached)),
+        GroundFormula::Or(operands) => operands.iter().any(|f| may_hold(f, reached)),
+    }
+}
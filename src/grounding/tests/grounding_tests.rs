@@ -0,0 +1,292 @@
+use super::*;
+
+fn get_domain() -> Vec<u8> {
+    "
+    (define (domain transport)
+        (:requirements :hierarchy :typing :negative-preconditions)
+        (:types location vehicle - object truck - vehicle)
+        (:predicates (road ?a ?b - location) (at ?v - vehicle ?l - location))
+        (:task get-to :parameters (?v - vehicle ?l - location))
+        (:action drive
+            :parameters (?v - vehicle ?from ?to - location)
+            :precondition (and (at ?v ?from) (road ?from ?to))
+            :effect (and (not (at ?v ?from)) (at ?v ?to)))
+        (:method m-drive
+            :parameters (?v - vehicle ?from ?to - location)
+            :task (get-to ?v ?to)
+            :precondition (at ?v ?from)
+            :ordered-subtasks (and (t1 (drive ?v ?from ?to)))
+            :constraints (and (not (= ?from ?to))))
+        (:method m-stay
+            :parameters (?v - vehicle ?l - location)
+            :task (get-to ?v ?l)
+            :precondition (at ?v ?l)
+            :ordered-subtasks (and))
+        (:method m-unreachable
+            :parameters (?v - vehicle ?l - location)
+            :task (get-to ?v ?l)
+            :ordered-subtasks (and (t1 (drive ?v ?l ?l))))
+    )"
+    .as_bytes()
+    .to_vec()
+}
+
+fn get_problem() -> Vec<u8> {
+    "
+    (define (problem p1)
+        (:domain transport)
+        (:objects l1 l2 l3 - location t1 - truck)
+        (:htn :parameters () :ordered-subtasks (and (task0 (get-to t1 l3))))
+        (:init (road l1 l2) (road l2 l3) (at t1 l1))
+        (:goal (at t1 l3))
+    )"
+    .as_bytes()
+    .to_vec()
+}
+
+fn ground(domain: &Vec<u8>, problem: &Vec<u8>) -> GroundedModel {
+    let domain_parser = Parser::new(LexicalAnalyzer::new(domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let symbol_table = DomainSemanticAnalyzer::new(&d).verify_domain().unwrap();
+            Grounder::new(&d, &p, &symbol_table).ground()
+        }
+        _ => panic!("expected a domain and a problem"),
+    }
+}
+
+#[test]
+pub fn ground_action_test() {
+    let model = ground(&get_domain(), &get_problem());
+    // road is static, so drive is only instantiated along the roads
+    assert_eq!(model.actions.len(), 2);
+    assert_eq!(model.actions[0].name, "drive");
+    assert_eq!(model.actions[0].args, vec!["t1", "l1", "l2"]);
+    assert_eq!(model.actions[1].args, vec!["t1", "l2", "l3"]);
+    // static facts are compiled away
+    assert!(model.facts.iter().all(|fact| fact.predicate == "at"));
+    // facts of the initial state come first
+    assert_eq!(model.initial_state, vec![0]);
+    assert_eq!(model.facts[0].args, vec!["t1", "l1"]);
+    assert_eq!(model.actions[0].precondition, GroundFormula::Fact(0));
+    let effect = &model.actions[0].effects[0];
+    assert_eq!(effect.condition, GroundFormula::True);
+    assert_eq!(effect.delete, vec![0]);
+    assert_eq!(model.facts[effect.add[0] as usize].args, vec!["t1", "l2"]);
+    let goal_fact = model
+        .facts
+        .iter()
+        .find(|fact| fact.args == vec!["t1", "l3"])
+        .unwrap();
+    assert_eq!(model.goal, Some(GroundFormula::Fact(goal_fact.id)));
+    assert!(!model.is_relaxed);
+}
+
+#[test]
+pub fn ground_method_test() {
+    let model = ground(&get_domain(), &get_problem());
+    let drives: Vec<&GroundMethod> = model
+        .methods
+        .iter()
+        .filter(|method| method.name == "m-drive")
+        .collect();
+    // one for each drive action
+    assert_eq!(drives.len(), 2);
+    assert_eq!(drives[0].args, vec!["t1", "l1", "l2"]);
+    assert_eq!(drives[0].network.subtasks, vec![GroundSubtask::Action(0)]);
+    let task = &model.tasks[drives[0].task as usize];
+    assert_eq!(task.name, "get-to");
+    assert_eq!(task.args, vec!["t1", "l2"]);
+    // one for each location
+    let stays = model.methods.iter().filter(|method| method.name == "m-stay");
+    assert_eq!(stays.count(), 3);
+    // drive ?v ?l ?l has no ground action
+    assert!(model.methods.iter().all(|method| method.name != "m-unreachable"));
+    assert_eq!(model.tasks.len(), 3);
+    for (index, method) in model.methods.iter().enumerate() {
+        assert_eq!(method.id, index as u32);
+    }
+    assert_eq!(model.initial_networks.len(), 1);
+    match model.initial_networks[0].subtasks[..] {
+        [GroundSubtask::Task(id)] => {
+            assert_eq!(model.tasks[id as usize].args, vec!["t1", "l3"]);
+        }
+        _ => panic!("expected a single task"),
+    }
+}
+
+#[test]
+pub fn parameterized_initial_network_test() {
+    let problem = "
+    (define (problem p2)
+        (:domain transport)
+        (:objects l1 l2 l3 - location t1 t2 - truck)
+        (:htn :parameters (?v - vehicle) :ordered-subtasks (and (task0 (get-to ?v l3))))
+        (:init (road l1 l2) (road l2 l3) (at t1 l1) (at t2 l2))
+    )"
+    .as_bytes()
+    .to_vec();
+    let model = ground(&get_domain(), &problem);
+    // one network for each truck
+    let mut trucks: Vec<&String> = model
+        .initial_networks
+        .iter()
+        .map(|network| match network.subtasks[..] {
+            [GroundSubtask::Task(id)] => &model.tasks[id as usize].args[0],
+            _ => panic!("expected a single task"),
+        })
+        .collect();
+    trucks.sort();
+    assert_eq!(trucks, vec!["t1", "t2"]);
+}
+
+#[test]
+pub fn swapped_files_test() {
+    let result = crate::HDDLAnalyzer::ground(&get_problem(), &get_domain());
+    assert!(matches!(result, Err(ParsingError::Syntactic(_))));
+}
+
+#[test]
+pub fn ground_conditional_effect_test() {
+    let domain = "
+    (define (domain lights)
+        (:requirements :typing :conditional-effects)
+        (:types room)
+        (:predicates (on ?r - room) (lit ?r - room))
+        (:action switch
+            :parameters (?r - room)
+            :effect (and
+                (when (on ?r) (not (on ?r)))
+                (when (not (on ?r)) (on ?r))
+                (forall (?s - room) (when (on ?s) (lit ?s)))))
+    )"
+    .as_bytes()
+    .to_vec();
+    let problem = "
+    (define (problem p1)
+        (:domain lights)
+        (:objects r1 r2 - room)
+        (:init (on r1))
+    )"
+    .as_bytes()
+    .to_vec();
+    let model = ground(&domain, &problem);
+    assert_eq!(model.actions.len(), 2);
+    let fact = |predicate: &str, room: &str| {
+        model
+            .facts
+            .iter()
+            .find(|fact| fact.predicate == predicate && fact.args == vec![room])
+            .unwrap()
+            .id
+    };
+    // the effects with the same condition are merged
+    let effects = &model.actions[0].effects;
+    assert_eq!(effects.len(), 3);
+    assert_eq!(effects[0].condition, GroundFormula::Fact(fact("on", "r1")));
+    assert_eq!(effects[0].delete, vec![fact("on", "r1")]);
+    assert_eq!(effects[0].add, vec![fact("lit", "r1")]);
+    assert_eq!(
+        effects[1].condition,
+        GroundFormula::Not(Box::new(GroundFormula::Fact(fact("on", "r1"))))
+    );
+    assert_eq!(effects[1].add, vec![fact("on", "r1")]);
+    assert_eq!(effects[2].condition, GroundFormula::Fact(fact("on", "r2")));
+    assert_eq!(effects[2].add, vec![fact("lit", "r2")]);
+}
+
+#[test]
+pub fn relaxed_reachability_test() {
+    let problem = "
+    (define (problem p2)
+        (:domain transport)
+        (:objects l1 l2 l3 - location t1 - truck)
+        (:init (road l1 l2) (road l2 l3) (at t1 l2))
+        (:goal (at t1 l3))
+    )"
+    .as_bytes()
+    .to_vec();
+    let model = ground(&get_domain(), &problem);
+    let fact = |location: &str| {
+        model
+            .facts
+            .iter()
+            .find(|fact| fact.predicate == "at" && fact.args == vec!["t1", location])
+            .unwrap()
+            .id
+    };
+    let reachable = model.relaxed_reachable_facts();
+    assert!(reachable.contains(&fact("l2")));
+    assert!(reachable.contains(&fact("l3")));
+    // there is no road back to l1
+    assert!(!reachable.contains(&fact("l1")));
+}
+
+#[test]
+pub fn numeric_condition_test() {
+    let domain = "
+    (define (domain fuel)
+        (:requirements :typing :numeric-fluents)
+        (:types location truck)
+        (:predicates (at ?t - truck ?l - location))
+        (:functions (road-length ?l1 ?l2 - location) - number (fuel ?t - truck) - number)
+        (:action drive
+            :parameters (?t - truck ?from ?to - location)
+            :precondition (and (at ?t ?from) (<= (road-length ?from ?to) 5) (>= (fuel ?t) 1))
+            :effect (and (not (at ?t ?from)) (at ?t ?to) (decrease (fuel ?t) 1)))
+    )"
+    .as_bytes()
+    .to_vec();
+    let problem = "
+    (define (problem p3)
+        (:domain fuel)
+        (:objects l1 l2 l3 - location t1 - truck)
+        (:init (at t1 l1) (= (road-length l1 l2) 3) (= (road-length l2 l3) 8) (= (fuel t1) 2))
+    )"
+    .as_bytes()
+    .to_vec();
+    let model = ground(&domain, &problem);
+    // road-length is static (the other roads are undefined, or too long)
+    let args: Vec<&Vec<String>> = model.actions.iter().map(|action| &action.args).collect();
+    assert_eq!(args, vec![&vec!["t1".to_string(), "l1".to_string(), "l2".to_string()]]);
+    // while the fuel is assumed to suffice
+    assert!(model.is_relaxed);
+}
+
+#[test]
+pub fn durative_action_test() {
+    let domain = "
+    (define (domain doors)
+        (:requirements :typing :negative-preconditions :durative-actions)
+        (:types room)
+        (:predicates (at ?r - room) (open ?r - room) (door ?a ?b - room))
+        (:durative-action walk
+            :parameters (?a ?b - room)
+            :duration (= ?duration 1)
+            :condition (and (at start (at ?a)) (over all (door ?a ?b)) (at end (open ?b)) (at end (not (at ?a))))
+            :effect (and (at start (not (at ?a))) (at end (at ?b))))
+    )"
+    .as_bytes()
+    .to_vec();
+    let problem = |init: &str| {
+        format!(
+            "(define (problem p4) (:domain doors) (:objects r1 r2 - room) (:init (at r1) (door r1 r2) {}))",
+            init
+        )
+        .into_bytes()
+    };
+    let model = ground(&domain, &problem("(open r2)"));
+    assert_eq!(model.actions.len(), 1);
+    let fact = |room: &str| model.facts.iter().find(|fact| fact.args == vec![room]).unwrap().id;
+    let walk = &model.actions[0];
+    assert_eq!(walk.precondition, GroundFormula::Fact(fact("r1")));
+    assert_eq!(walk.effects[0].delete, vec![fact("r1")]);
+    // the end follows the start
+    let end = walk.end.as_ref().unwrap();
+    assert_eq!(end.precondition, GroundFormula::Not(Box::new(GroundFormula::Fact(fact("r1")))));
+    assert_eq!(end.effects[0].add, vec![fact("r2")]);
+    assert!(model.relaxed_reachable_facts().contains(&fact("r2")));
+    // the door to r2 is never open, so the walk can not end
+    assert!(ground(&domain, &problem("")).actions.is_empty());
+}
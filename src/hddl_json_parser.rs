@@ -2,7 +2,7 @@ use serde_json::{json, Value};
 use crate::lexical_analyzer::LexicalAnalyzer;
 pub use crate::output::{ ParsingError, SemanticErrorType, SyntacticError, WarningType};
 use crate::syntactic_analyzer;
-use crate::syntactic_analyzer::{Subtask, HTN, Method, Task, AbstractSyntaxTree, Formula, Predicate, Action, Symbol};
+use crate::syntactic_analyzer::{Subtask, HTN, Method, Task, AbstractSyntaxTree, Formula, Effect, Predicate, Action, Symbol, Constraint};
 
 pub struct HDDLJsonParser;
 
@@ -84,7 +84,7 @@ impl HDDLJsonParser {
                     .iter()
                     .filter(|m| m.task.name == task.name)
                     .map(|m| {
-                        json!({
+                        let mut method_json = json!({
                             "name": m.name.name,
                             "precondition": match &m.precondition {
                                 Some(formula) => self.tasks_call_to_json(formula),
@@ -92,7 +92,21 @@ impl HDDLJsonParser {
                             },
                             "tasks":  self.subtasks_to_json(&m.tn.subtasks)
                         }
-                    )
+                    );
+                        let temporal: Vec<_> = m.tn.constraints.iter().flatten()
+                            .filter_map(|constraint| match constraint {
+                                Constraint::Temporal(operation, lhs, rhs) => Some(json!({
+                                    "type": operation.to_string(),
+                                    "left": lhs.to_string(),
+                                    "right": rhs.to_string()
+                                })),
+                                _ => None,
+                            })
+                            .collect();
+                        if !temporal.is_empty() {
+                            method_json["temporal_constraints"] = json!(temporal);
+                        }
+                        method_json
                     })
                     .collect();
 
@@ -136,16 +150,38 @@ impl HDDLJsonParser {
                     None => vec![],
                 };
                 let effect_json = match &action.effects {
-                    Some(formula) => self.tasks_call_to_json(formula),
+                    Some(effect) => self.effect_to_json(effect),
                     None => vec![],
                 };
 
-                json!({
+                let mut action_json = json!({
                 "name": action.name,
                 "parameters": parameters_json,
                 "precondition": precondition_json,
                 "effect": effect_json
-            })
+            });
+                // durative actions also keep when their conditions and effects apply
+                if let Some(timing) = &action.timing {
+                    action_json["duration"] = json!(timing.duration.iter()
+                        .map(|constraint| json!({
+                            "type": constraint.operation.to_string(),
+                            "value": constraint.value.to_string()
+                        }))
+                        .collect::<Vec<_>>());
+                    action_json["timed_precondition"] = json!(timing.conditions.iter()
+                        .map(|(time, condition)| json!({
+                            "time": time.to_string(),
+                            "expression": self.tasks_call_to_json(condition)
+                        }))
+                        .collect::<Vec<_>>());
+                    action_json["timed_effect"] = json!(timing.effects.iter()
+                        .map(|(time, effect)| json!({
+                            "time": time.to_string(),
+                            "expression": self.effect_to_json(effect)
+                        }))
+                        .collect::<Vec<_>>());
+                }
+                action_json
             })
             .collect()
     }
@@ -208,10 +244,51 @@ impl HDDLJsonParser {
             Formula::Equals(left, right) => {
                 vec!{json!({
                 "type": "equals",
-                "left": left,
-                "right": right
+                "left": left.name,
+                "right": right.name
             })}
             }
+            Formula::Comparison(operation, left, right) => {
+                vec!{json!({
+                "type": operation.to_string(),
+                "left": left.to_string(),
+                "right": right.to_string()
+            })}
+            }
+        }
+    }
+
+    fn effect_to_json<'a>(&self, effect: &Effect<'a>) -> Vec<Value> {
+        match effect {
+            Effect::Empty => vec![],
+            Effect::Add(pred) => self.predicate_to_json(pred),
+            Effect::Delete(pred) => vec!{json!({
+                "type": "not",
+                "expression": self.predicate_to_json(pred)
+            })},
+            Effect::And(effects) => vec!{json!({
+                "type": "and",
+                "expression": effects.iter().flat_map(|e| self.effect_to_json(e)).collect::<Vec<_>>()
+            })},
+            Effect::OneOf(effects) => vec!{json!({
+                "type": "oneof",
+                "expression": effects.iter().flat_map(|e| self.effect_to_json(e)).collect::<Vec<_>>()
+            })},
+            Effect::ForAll(vars, new_effect) => vec!{json!({
+                "type": "forall",
+                "parameters": self.parameters_to_json(vars),
+                "effect": self.effect_to_json(new_effect)
+            })},
+            Effect::When(condition, new_effect) => vec!{json!({
+                "type": "when",
+                "condition": self.tasks_call_to_json(condition),
+                "effect": self.effect_to_json(new_effect)
+            })},
+            Effect::Numeric(operation, function, value) => vec!{json!({
+                "type": operation.to_string(),
+                "function": self.predicate_to_json(function),
+                "value": value.to_string()
+            })},
         }
     }
 
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use crate::formatter::block_spans;
+use crate::language_server::document_store::AnalyzedDocument;
+use crate::language_server::formatting_utils::{to_offset, to_position};
+use crate::language_server::hover_utils::term_name;
+use crate::language_server::outline_utils::find_block;
+use crate::syntactic_analyzer::*;
+
+use tower_lsp::lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, NumberOrString, Range, TextEdit, Url, WorkspaceEdit,
+};
+
+// the quick fixes for the diagnostics of a document (declarations are added to
+// the domain, which is the document itself for domains)
+pub fn code_actions(
+    url: &Url,
+    document: &AnalyzedDocument,
+    domain: Option<(&Url, &AnalyzedDocument)>,
+    diagnostics: &[Diagnostic],
+) -> Vec<CodeActionOrCommand> {
+    let domain = match document.ast() {
+        Some(AbstractSyntaxTree::Domain(_)) => Some((url, document)),
+        _ => domain,
+    };
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| {
+            let (title, edits) = quick_fix(url, document, domain, diagnostic)?;
+            Some(CodeActionOrCommand::CodeAction(CodeAction {
+                title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(WorkspaceEdit::new(edits)),
+                is_preferred: Some(true),
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+fn quick_fix(
+    url: &Url,
+    document: &AnalyzedDocument,
+    domain: Option<(&Url, &AnalyzedDocument)>,
+    diagnostic: &Diagnostic,
+) -> Option<(String, HashMap<Url, Vec<TextEdit>>)> {
+    let Some(NumberOrString::String(code)) = &diagnostic.code else {
+        return None;
+    };
+    let content = document.content();
+    let start = to_offset(content, &diagnostic.range.start);
+    let end = to_offset(content, &diagnostic.range.end);
+    let symbol = String::from_utf8_lossy(&content[start..end]).to_string();
+    let in_domain = |edit: TextEdit| {
+        let (domain_url, _) = domain?;
+        Some(HashMap::from([(domain_url.clone(), vec![edit])]))
+    };
+    let in_document = |edit: TextEdit| HashMap::from([(url.clone(), vec![edit])]);
+    match code.as_str() {
+        "UndefinedPredicate" => {
+            let (_, domain_document) = domain?;
+            let (atom, scope) = find_atom(document.ast()?, domain_document.ast(), start)?;
+            let declaration = predicate_declaration(atom, &scope);
+            let edit = add_to_block(
+                domain_document.content(),
+                ":predicates",
+                &[":constants", ":types", ":requirements", "domain"],
+                &declaration,
+            )?;
+            Some((format!("Declare predicate {}", declaration), in_domain(edit)?))
+        }
+        "UndefinedType" => {
+            let (_, domain_document) = domain?;
+            let edit = add_to_block(domain_document.content(), ":types", &[":requirements", "domain"], &symbol)?;
+            Some((format!("Add type {} to :types", symbol), in_domain(edit)?))
+        }
+        "UndefinedObject" => {
+            let Some(AbstractSyntaxTree::Problem(problem)) = document.ast() else {
+                return None;
+            };
+            let object = match domain.and_then(|(_, domain)| domain.ast()) {
+                Some(AbstractSyntaxTree::Domain(domain)) => match object_type(problem, domain, start) {
+                    Some(object_type) => format!("{} - {}", symbol, object_type),
+                    None => symbol.clone(),
+                },
+                _ => symbol.clone(),
+            };
+            let edit = add_to_block(content, ":objects", &[":requirements", ":domain", "problem"], &object)?;
+            Some((format!("Add {} to :objects", object), in_document(edit)))
+        }
+        "InconsistentPredicateArity" => {
+            let expected = diagnostic.data.as_ref()?.get("expected")?.as_u64()? as usize;
+            let domain_ast = domain.and_then(|(_, domain)| domain.ast());
+            let (atom, _) = find_atom(document.ast()?, domain_ast, start)?;
+            let declared = match domain_ast {
+                Some(AbstractSyntaxTree::Domain(domain)) => {
+                    domain.predicates.iter().find(|predicate| predicate.name == atom.name)
+                }
+                _ => None,
+            };
+            let terms: Vec<String> = std::iter::once(atom.name.to_string())
+                .chain((0..expected).map(|index| match atom.variables.get(index) {
+                    Some(term) => term_name(term),
+                    None => match declared.and_then(|declared| declared.variables.get(index)) {
+                        Some(variable) => format!("?{}", variable.name),
+                        None => format!("?arg{}", index + 1),
+                    },
+                }))
+                .collect();
+            let call_end = atom
+                .variables
+                .last()
+                .map_or(atom.name_pos.end_offset, |term| term.name_pos.end_offset);
+            let edit = TextEdit::new(span(content, atom.name_pos.offset, call_end), terms.join(" "));
+            Some((format!("Change to ({})", terms.join(" ")), in_document(edit)))
+        }
+        "DuplicateRequirementDeclaration" | "UnusedRequirement" => {
+            // the whitespace before the requirement is removed with it
+            let removed_start = content[..start]
+                .iter()
+                .rposition(|c| !c.is_ascii_whitespace())
+                .map_or(0, |last| last + 1);
+            let edit = TextEdit::new(span(content, removed_start, end), String::new());
+            let kind = match code.as_str() {
+                "DuplicateRequirementDeclaration" => "duplicate",
+                _ => "unused",
+            };
+            Some((format!("Remove {} requirement {}", kind, symbol), in_document(edit)))
+        }
+        "UndeclaredRequirement" | "MissingRequirement" => {
+            let requirement = diagnostic.data.as_ref()?.get("requirement")?.as_str()?;
+            let edit = add_to_block(content, ":requirements", &["domain"], requirement)?;
+            Some((format!("Add requirement {}", requirement), in_document(edit)))
+        }
+        _ => None,
+    }
+}
+
+fn span(content: &[u8], start: usize, end: usize) -> Range {
+    Range {
+        start: to_position(content, start),
+        end: to_position(content, end),
+    }
+}
+
+// adds the element to the end of the block, or adds the block after the first
+// of its predecessors that exists (e.g. ":types" after ":requirements")
+fn add_to_block(content: &[u8], keyword: &str, predecessors: &[&str], element: &str) -> Option<TextEdit> {
+    let blocks = block_spans(content);
+    if let Some((start, end)) = find_block(content, &blocks, keyword) {
+        let close = end - 1;
+        let last = content[..close].iter().rposition(|c| !c.is_ascii_whitespace())? + 1;
+        let text = match content[start..close].contains(&b'\n') {
+            // one element per line
+            true => format!("\n{}{}", indentation(content, last - 1), element),
+            false => format!(" {}", element),
+        };
+        let position = to_position(content, last);
+        return Some(TextEdit::new(Range::new(position, position), text));
+    }
+    let (start, end) = predecessors
+        .iter()
+        .find_map(|predecessor| find_block(content, &blocks, predecessor))?;
+    // the new block is aligned with its predecessor, unless the predecessor is
+    // the header (e.g. "(define (domain name)")
+    let line_start = content[..start].iter().rposition(|c| *c == b'\n').map_or(0, |newline| newline + 1);
+    let indentation = match content[line_start..start].iter().all(|c| c.is_ascii_whitespace()) {
+        true => indentation(content, start),
+        false => "  ".to_string(),
+    };
+    let position = to_position(content, end);
+    Some(TextEdit::new(
+        Range::new(position, position),
+        format!("\n{}({} {})", indentation, keyword, element),
+    ))
+}
+
+// the leading whitespace of the line that contains the offset
+fn indentation(content: &[u8], offset: usize) -> String {
+    let line_start = content[..offset].iter().rposition(|c| *c == b'\n').map_or(0, |newline| newline + 1);
+    content[line_start..]
+        .iter()
+        .take_while(|c| **c == b' ' || **c == b'\t')
+        .map(|c| *c as char)
+        .collect()
+}
+
+// "(name ?a - type_a ?b - type_b)", with the variables and types of the terms of its use
+fn predicate_declaration(atom: &Predicate, scope: &Vec<&Symbol>) -> String {
+    let mut parameters: Vec<String> = vec![];
+    let mut declaration = vec![atom.name.to_string()];
+    for (index, term) in atom.variables.iter().enumerate() {
+        let term_type = scope
+            .iter()
+            .find(|symbol| symbol.name == term.name)
+            .and_then(|symbol| symbol.symbol_type);
+        let mut parameter = match (is_variable(term), term_type) {
+            (true, _) => term.name.to_string(),
+            (false, Some(term_type)) => term_type.chars().take(1).collect(),
+            (false, None) => format!("arg{}", index + 1),
+        };
+        if parameters.contains(&parameter) {
+            parameter = format!("{}{}", parameter, index + 1);
+        }
+        declaration.push(match term_type {
+            Some(term_type) => format!("?{} - {}", parameter, term_type),
+            None => format!("?{}", parameter),
+        });
+        parameters.push(parameter);
+    }
+    format!("({})", declaration.join(" "))
+}
+
+fn is_variable(term: &Symbol) -> bool {
+    term.name_pos.end_offset - term.name_pos.offset == term.name.len() + 1
+}
+
+// the parameters, formulas, atoms and quantified variables of a block
+type Scope<'b, 'a> = (Vec<&'b Symbol<'a>>, Vec<&'b Formula<'a>>, Vec<&'b Predicate<'a>>, Vec<&'b Symbol<'a>>);
+
+// the predicate whose name starts at the offset, with the symbols in its scope
+// (i.e. parameters, quantified variables, constants and objects)
+fn find_atom<'a, 'b>(
+    ast: &'b AbstractSyntaxTree<'a>,
+    domain: Option<&'b AbstractSyntaxTree<'a>>,
+    offset: usize,
+) -> Option<(&'b Predicate<'a>, Vec<&'b Symbol<'a>>)> {
+    let mut scopes: Vec<Scope> = vec![];
+    let constants: Vec<&Symbol> = match domain {
+        Some(AbstractSyntaxTree::Domain(domain)) => domain.constants.iter().flatten().collect(),
+        _ => vec![],
+    };
+    match ast {
+        AbstractSyntaxTree::Domain(domain) => {
+            for action in domain.actions.iter() {
+                let mut formulas: Vec<&Formula> = action.preconditions.iter().collect();
+                let (mut atoms, mut variables) = (vec![], vec![]);
+                if let Some(effect) = &action.effects {
+                    effect_atoms(effect, &mut formulas, &mut atoms, &mut variables);
+                }
+                scopes.push((action.parameters.iter().collect(), formulas, atoms, variables));
+            }
+            for method in domain.methods.iter() {
+                scopes.push((method.params.iter().collect(), method.precondition.iter().collect(), vec![], vec![]));
+            }
+        }
+        AbstractSyntaxTree::Problem(problem) => {
+            let facts = problem.init_state.iter().collect();
+            scopes.push((problem.objects.iter().collect(), problem.goal.iter().collect(), facts, vec![]));
+        }
+    }
+    for (parameters, formulas, mut atoms, mut variables) in scopes {
+        for formula in formulas {
+            formula_atoms(formula, &mut atoms, &mut variables);
+        }
+        if let Some(atom) = atoms.into_iter().find(|atom| atom.name_pos.offset == offset) {
+            let scope = parameters.into_iter().chain(variables).chain(constants).collect();
+            return Some((atom, scope));
+        }
+    }
+    None
+}
+
+// the predicates and the quantified variables of the formula
+fn formula_atoms<'a, 'b>(formula: &'b Formula<'a>, atoms: &mut Vec<&'b Predicate<'a>>, variables: &mut Vec<&'b Symbol<'a>>) {
+    match formula {
+        Formula::Empty | Formula::Equals(_, _) | Formula::Comparison(_, _, _) => {}
+        Formula::Atom(predicate) => atoms.push(predicate),
+        Formula::Not(new_formula) => formula_atoms(new_formula, atoms, variables),
+        Formula::ForAll(quantified, new_formula) | Formula::Exists(quantified, new_formula) => {
+            variables.extend(quantified.iter());
+            formula_atoms(new_formula, atoms, variables);
+        }
+        Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            for f in new_formula {
+                formula_atoms(f, atoms, variables);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                formula_atoms(f, atoms, variables);
+            }
+        }
+    }
+}
+
+// the added and deleted predicates, the variables of the universal effects, and
+// the conditions of the effect (whose atoms are collected with the formulas)
+fn effect_atoms<'a, 'b>(
+    effect: &'b Effect<'a>,
+    conditions: &mut Vec<&'b Formula<'a>>,
+    atoms: &mut Vec<&'b Predicate<'a>>,
+    variables: &mut Vec<&'b Symbol<'a>>,
+) {
+    match effect {
+        Effect::Empty | Effect::Numeric(_, _, _) => {}
+        Effect::Add(predicate) | Effect::Delete(predicate) => atoms.push(predicate),
+        Effect::And(effects) | Effect::OneOf(effects) => {
+            for e in effects {
+                effect_atoms(e, conditions, atoms, variables);
+            }
+        }
+        Effect::ForAll(quantified, new_effect) => {
+            variables.extend(quantified.iter());
+            effect_atoms(new_effect, conditions, atoms, variables);
+        }
+        Effect::When(condition, new_effect) => {
+            conditions.push(condition);
+            effect_atoms(new_effect, conditions, atoms, variables);
+        }
+    }
+}
+
+// the declared type of the parameter that an (undefined) object of the problem is passed to
+fn object_type<'a>(problem: &ProblemAST, domain: &DomainAST<'a>, offset: usize) -> Option<&'a str> {
+    let mut atoms: Vec<&Predicate> = problem.init_state.iter().collect();
+    for goal in problem.goal.iter() {
+        formula_atoms(goal, &mut atoms, &mut vec![]);
+    }
+    for atom in atoms {
+        if let Some(index) = atom.variables.iter().position(|term| term.name_pos.offset == offset) {
+            let declared = domain.predicates.iter().find(|predicate| predicate.name == atom.name)?;
+            return declared.variables.get(index)?.symbol_type;
+        }
+    }
+    for subtask in problem.init_tn.iter().flat_map(|init_tn| init_tn.tn.subtasks.iter()) {
+        if let Some(index) = subtask.terms.iter().position(|term| term.name_pos.offset == offset) {
+            let parameters = domain
+                .compound_tasks
+                .iter()
+                .find(|task| task.name == subtask.task.name)
+                .map(|task| &task.parameters)
+                .or_else(|| {
+                    domain
+                        .actions
+                        .iter()
+                        .find(|action| action.name == subtask.task.name)
+                        .map(|action| &action.parameters)
+                })?;
+            return parameters.get(index)?.symbol_type;
+        }
+    }
+    None
+}
@@ -0,0 +1,256 @@
+use petgraph::algo::has_path_connecting;
+use petgraph::prelude::GraphMap;
+use petgraph::Directed;
+
+use crate::formatter::parameter_list;
+use crate::lexical_analyzer::{LexicalAnalyzer, RequirementType};
+use crate::semantic_analyzer::DomainTypeChecker;
+use crate::syntactic_analyzer::*;
+
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionItemLabelDetails};
+
+// what is expected at the cursor
+#[derive(Debug, PartialEq)]
+pub enum CompletionContext {
+    Requirement,
+    Type,
+    Predicate,
+    Task,
+    // an argument (by its index) of an atom of the initial state or the goal
+    Argument(String, usize),
+    Unknown,
+}
+
+// an open parenthesis before the cursor
+#[derive(Default)]
+struct Frame<'a> {
+    head: Option<&'a str>,
+    // the last keyword of the frame (e.g. ":precondition" in an action)
+    keyword: Option<&'a str>,
+    arguments: usize,
+}
+
+const FORMULA_KEYWORDS: [&str; 4] = [":precondition", ":effect", ":goal", ":init"];
+const SUBTASK_KEYWORDS: [&str; 5] = [":subtasks", ":tasks", ":ordered-subtasks", ":ordered-tasks", ":task"];
+const CONNECTIVES: [&str; 7] = ["and", "or", "not", "imply", "forall", "exists", "="];
+
+// the context is found lexically, since the block around the cursor is
+// usually incomplete (and is dropped by the parser)
+pub fn completion_context(content: &[u8], offset: usize) -> CompletionContext {
+    let text = String::from_utf8_lossy(&content[..offset.min(content.len())]);
+    let mut tokens = tokenize(&text);
+    // the word that is being typed
+    if text.ends_with(|c: char| !c.is_whitespace() && c != '(' && c != ')') {
+        tokens.pop();
+    }
+    let mut frames: Vec<Frame> = vec![];
+    for token in tokens.iter() {
+        match *token {
+            "(" => frames.push(Frame::default()),
+            ")" => {
+                frames.pop();
+            }
+            word => {
+                if let Some(frame) = frames.last_mut() {
+                    if frame.head.is_none() {
+                        frame.head = Some(word);
+                        if word.starts_with(':') {
+                            frame.keyword = Some(word);
+                        }
+                    } else if word.starts_with(':') {
+                        frame.keyword = Some(word);
+                        frame.arguments = 0;
+                    } else {
+                        frame.arguments += 1;
+                    }
+                }
+            }
+        }
+    }
+    let Some(current) = frames.last() else {
+        return CompletionContext::Unknown;
+    };
+    if tokens.last() == Some(&"-") && current.head != Some("-") {
+        return CompletionContext::Type;
+    }
+    if current.head == Some(":requirements") {
+        return CompletionContext::Requirement;
+    }
+    let keyword = frames[..frames.len() - 1]
+        .iter()
+        .rev()
+        .find_map(|frame| frame.keyword);
+    match (current.head, keyword) {
+        (None, Some(keyword)) if FORMULA_KEYWORDS.contains(&keyword) => CompletionContext::Predicate,
+        (None, Some(keyword)) if SUBTASK_KEYWORDS.contains(&keyword) => CompletionContext::Task,
+        (Some(head), Some(":init" | ":goal")) if !CONNECTIVES.contains(&head) => {
+            CompletionContext::Argument(head.to_string(), current.arguments)
+        }
+        _ => CompletionContext::Unknown,
+    }
+}
+
+// the domain is the document itself if the document is a domain
+pub fn complete(content: &Vec<u8>, offset: usize, domain: Option<&Vec<u8>>) -> Vec<CompletionItem> {
+    let context = completion_context(content, offset);
+    if context == CompletionContext::Requirement {
+        return RequirementType::ALL
+            .iter()
+            .map(|requirement| item(requirement.to_string(), CompletionItemKind::KEYWORD, None))
+            .collect();
+    }
+    let domain_parser = domain.map(|domain| Parser::new(LexicalAnalyzer::new(domain)));
+    let domain = match domain_parser.as_ref().map(|parser| parser.parse_partial()) {
+        Some((Some(AbstractSyntaxTree::Domain(domain)), _)) => Some(domain),
+        _ => None,
+    };
+    let type_hierarchy = match &domain {
+        Some(domain) => DomainTypeChecker::new(&domain.types).get_type_hierarchy(),
+        None => GraphMap::new(),
+    };
+    match context {
+        CompletionContext::Type => {
+            let mut types: Vec<&str> = type_hierarchy.nodes().collect();
+            types.sort();
+            types
+                .into_iter()
+                .map(|name| item(name.to_string(), CompletionItemKind::CLASS, None))
+                .collect()
+        }
+        CompletionContext::Predicate => domain
+            .iter()
+            .flat_map(|domain| domain.predicates.iter())
+            .map(|predicate| {
+                let mut item = item(
+                    predicate.name.to_string(),
+                    CompletionItemKind::PROPERTY,
+                    Some(signature(predicate.name, &predicate.variables)),
+                );
+                item.label_details = Some(CompletionItemLabelDetails {
+                    detail: None,
+                    description: Some(format!("arity {}", predicate.variables.len())),
+                });
+                item
+            })
+            .collect(),
+        CompletionContext::Task => {
+            let tasks = domain
+                .iter()
+                .flat_map(|domain| domain.compound_tasks.iter())
+                .map(|task| ("compound task", task.name, &task.parameters));
+            let actions = domain
+                .iter()
+                .flat_map(|domain| domain.actions.iter())
+                .map(|action| ("primitive task", action.name, &action.parameters));
+            tasks
+                .chain(actions)
+                .map(|(description, name, parameters)| {
+                    let mut item = item(
+                        name.to_string(),
+                        CompletionItemKind::FUNCTION,
+                        Some(signature(name, parameters)),
+                    );
+                    item.label_details = Some(CompletionItemLabelDetails {
+                        detail: None,
+                        description: Some(description.to_string()),
+                    });
+                    item
+                })
+                .collect()
+        }
+        CompletionContext::Argument(predicate, index) => {
+            let problem_lexer = LexicalAnalyzer::new(content);
+            let problem_parser = Parser::new(problem_lexer);
+            let objects = match problem_parser.parse_partial() {
+                (Some(AbstractSyntaxTree::Problem(problem)), _) => problem.objects,
+                _ => vec![],
+            };
+            let declaration = domain
+                .iter()
+                .flat_map(|domain| domain.predicates.iter())
+                .find(|declaration| declaration.name == predicate);
+            let expected_type = match declaration {
+                Some(declaration) => match declaration.variables.get(index) {
+                    Some(parameter) => parameter.symbol_type,
+                    // too many arguments
+                    None => return vec![],
+                },
+                None => None,
+            };
+            let constants = domain.iter().flat_map(|domain| domain.constants.iter().flatten());
+            objects
+                .iter()
+                .chain(constants)
+                .filter(|object| fits(&type_hierarchy, object.symbol_type, expected_type))
+                .map(|object| {
+                    item(
+                        object.name.to_string(),
+                        CompletionItemKind::CONSTANT,
+                        object.symbol_type.map(|t| t.to_string()),
+                    )
+                })
+                .collect()
+        }
+        CompletionContext::Requirement | CompletionContext::Unknown => vec![],
+    }
+}
+
+fn fits(type_hierarchy: &GraphMap<&str, (), Directed>, found: Option<&str>, expected: Option<&str>) -> bool {
+    match (found, expected) {
+        (_, None) => true,
+        (None, Some(expected)) => expected == "object",
+        (Some(found), Some(expected)) => {
+            found == expected
+                || (type_hierarchy.contains_node(found)
+                    && type_hierarchy.contains_node(expected)
+                    && has_path_connecting(type_hierarchy, found, expected, None))
+        }
+    }
+}
+
+fn signature(name: &str, parameters: &Vec<Symbol>) -> String {
+    let parameters = parameter_list(parameters);
+    if parameters.is_empty() {
+        format!("({})", name)
+    } else {
+        format!("({} {})", name, parameters)
+    }
+}
+
+fn item(label: String, kind: CompletionItemKind, detail: Option<String>) -> CompletionItem {
+    CompletionItem {
+        label,
+        kind: Some(kind),
+        detail,
+        ..CompletionItem::default()
+    }
+}
+
+// parentheses and words, without the comments
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut start = None;
+    let mut in_comment = false;
+    for (index, c) in text.char_indices() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if c.is_whitespace() || c == '(' || c == ')' || c == ';' {
+            if let Some(word_start) = start.take() {
+                tokens.push(&text[word_start..index]);
+            }
+            match c {
+                '(' | ')' => tokens.push(&text[index..index + 1]),
+                ';' => in_comment = true,
+                _ => {}
+            }
+        } else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(word_start) = start {
+        tokens.push(&text[word_start..]);
+    }
+    tokens
+}
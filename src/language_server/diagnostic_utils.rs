@@ -1,17 +1,21 @@
+use crate::language_server::document_store::AnalyzedDocument;
 use crate::lexical_analyzer::LexicalAnalyzer;
-use crate::semantic_analyzer::DomainSemanticAnalyzer;
 use crate::semantic_analyzer::ProblemSemanticAnalyzer;
 use crate::syntactic_analyzer::AbstractSyntaxTree;
 use crate::syntactic_analyzer::FileVariant;
 use crate::syntactic_analyzer::Parser;
 use crate::ParsingError;
 use crate::SemanticErrorType;
+use crate::WarningType;
+use crate::TokenPosition;
 
 use tower_lsp::lsp_types::DocumentDiagnosticReportResult;
 use tower_lsp::lsp_types::{FullDocumentDiagnosticReport, DocumentDiagnosticReport};
 use tower_lsp::lsp_types::RelatedFullDocumentDiagnosticReport;
 use tower_lsp::lsp_types::{DiagnosticRelatedInformation, Location, Url};
-use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+use serde_json::json;
 
 
 pub fn classify_file(content: &Vec<u8>) -> FileVariant {
@@ -20,114 +24,59 @@ pub fn classify_file(content: &Vec<u8>) -> FileVariant {
     parser.classify()
 }
 
-pub fn diagnose_domain(content: &Vec<u8>) -> DocumentDiagnosticReportResult {
+// the name of the domain that the document defines (none for problems)
+pub fn definition_domain_name(content: &Vec<u8>) -> Option<String> {
     let lexer = LexicalAnalyzer::new(content);
     let parser = Parser::new(lexer);
-    let mut items = vec![];
-    match parser.parse() {
-        Ok(ast) => {
-            if let AbstractSyntaxTree::Domain(d_ast) = ast {
-                match DomainSemanticAnalyzer::new(&d_ast).verify_domain() {
-                    Ok(_) => {
-                        // TODO: add warnings
-                    }
-                    Err(semantic_error) => {
-                        items.push(ParsingError::Semantic(semantic_error).into());
-                    }
-                }
-            }
-        },
-        Err(paring_error) => {
-            items.push(paring_error.into());
-        }
+    match parser.classify() {
+        FileVariant::Domain => parser.domain_name().map(|name| name.to_string()),
+        _ => None,
     }
-    DocumentDiagnosticReportResult::Report(
-        DocumentDiagnosticReport::Full(
-            RelatedFullDocumentDiagnosticReport {
-                related_documents: None,
-                full_document_diagnostic_report:
-                FullDocumentDiagnosticReport {
-                    items: items,
-                    result_id: None,
-                },
-            },
-        ),
-    )
 }
 
-pub fn generate_empty_report() -> DocumentDiagnosticReportResult {
-    DocumentDiagnosticReportResult::Report(
-        DocumentDiagnosticReport::Full(
-            RelatedFullDocumentDiagnosticReport {
-                related_documents: None,
-                full_document_diagnostic_report:
-                FullDocumentDiagnosticReport {
-                    items: vec![],
-                    result_id: None,
-                },
-            },
-        ),
-    )
+pub fn diagnose_domain(domain: &AnalyzedDocument) -> Vec<Diagnostic> {
+    domain.analysis().diagnostics.clone()
 }
 
-pub fn diagnose_problem(domain_content: Option<&Vec<u8>>, problem_content: &Vec<u8>) -> DocumentDiagnosticReportResult {
+// the cached analysis of the domain is reused for all of its problems
+pub fn diagnose_problem(
+    domain: Option<&AnalyzedDocument>,
+    url: &Url,
+    problem: &AnalyzedDocument,
+) -> Vec<Diagnostic> {
     let mut items = vec![];
-    let mut symbol_table = None;
-    
-    let domain_lexer;
-    let domain_parser;
-    let domain_verifier;
-    let domain_ast;
-    match domain_content {
-        Some(content) => {
-            domain_lexer = LexicalAnalyzer::new(content);
-            domain_parser = Parser::new(domain_lexer);
-            match domain_parser.parse() {
-                Ok(d_ast) => {
-                    if let AbstractSyntaxTree::Domain(d) = d_ast {
-                        domain_ast = d;
-                        domain_verifier = DomainSemanticAnalyzer::new(&domain_ast);
-                        match domain_verifier.verify_domain() {
-                            Ok(symbols) => {
-                                symbol_table = Some(symbols);
-                                // TODO: add warnings
-                            }
-                            Err(semantic_error) => {
-                                items.push(ParsingError::Semantic(semantic_error).into());
-                            }
-                        }
-                    }
-                }
-                Err(parsing_error) => {
-                    items.push(parsing_error.into());
-                }
+    let symbol_table = match domain {
+        Some(domain) => match &domain.analysis().symbol_table {
+            Some(symbol_table) => Some(symbol_table),
+            None => {
+                items.extend(domain.analysis().diagnostics.iter().cloned());
+                None
             }
-        }
-        None => {}
-    }
-    let problem_lexer = LexicalAnalyzer::new(problem_content);
-    match Parser::new(problem_lexer).parse() {
-        Ok(ast) if symbol_table.is_some() => {
-            if let AbstractSyntaxTree::Problem(p_ast) = ast {
-                let semantic_verifier = ProblemSemanticAnalyzer::new(
-                    &p_ast, 
-                    symbol_table.unwrap()
-                );
-                match semantic_verifier.verify_problem() {
-                    Ok(_warnings) => {
-                        // TODO: add warnings
-                    },
-                    Err(semantic_error) => {
-                        items.push(ParsingError::Semantic(semantic_error).into());
-                    }
-                }
+        },
+        None => None,
+    };
+    match (problem.ast(), symbol_table) {
+        (Some(AbstractSyntaxTree::Problem(p_ast)), Some(symbol_table))
+            if problem.analysis().diagnostics.is_empty() =>
+        {
+            let semantic_verifier = ProblemSemanticAnalyzer::new(
+                p_ast,
+                symbol_table.clone()
+            );
+            let (warnings, errors) = semantic_verifier.verify_problem_all();
+            for semantic_error in errors {
+                items.push(to_diagnostic(ParsingError::Semantic(semantic_error), url));
+            }
+            for warning in warnings {
+                items.push(warning.into());
             }
         },
-        Ok(_) => {}
-        Err(parsing_error) => {
-            items.push(parsing_error.into());
-        }
+        _ => items.extend(problem.analysis().diagnostics.iter().cloned()),
     }
+    items
+}
+
+pub fn report(items: Vec<Diagnostic>) -> DocumentDiagnosticReportResult {
     DocumentDiagnosticReportResult::Report(
         DocumentDiagnosticReport::Full(
             RelatedFullDocumentDiagnosticReport {
@@ -143,177 +92,205 @@ pub fn diagnose_problem(domain_content: Option<&Vec<u8>>, problem_content: &Vec<
     )
 }
 
+// converts a (1-based) token position to an LSP range
+pub fn to_range(position: &TokenPosition) -> Range {
+    Range {
+        start: Position {
+            line: position.line.saturating_sub(1),
+            character: position.column.saturating_sub(1),
+        },
+        end: Position {
+            line: position.line.saturating_sub(1),
+            character: position.end_column.saturating_sub(1),
+        },
+    }
+}
 
-impl From<ParsingError> for Diagnostic {
-    fn from(error: ParsingError) -> Self {
+impl From<WarningType> for Diagnostic {
+    fn from(warning: WarningType) -> Self {
         let source = Some("HDDL Analyzer".to_string());
-        match error {
-            ParsingError::Lexiacal(lexical_error) => {
-                let line_start = lexical_error.position.line;
-                Diagnostic::new(
-                    Range {
-                        start: Position::new(line_start - 1, 0),
-                        end: Position::new(line_start, 0),
-                    },
-                    Some(DiagnosticSeverity::ERROR),
-                    None,
-                    source,
-                    lexical_error.to_string(),
-                    None,
-                    None,
-                )
-            }
-            ParsingError::Syntactic(syntactic_error) => {
-                let line_start = syntactic_error.position.line;
-                Diagnostic::new(
-                    Range {
-                        start: Position::new(line_start - 1, 0),
-                        end: Position::new(line_start, 0),
-                    },
-                    Some(DiagnosticSeverity::ERROR),
-                    None,
-                    source,
-                    syntactic_error.to_string(),
-                    None,
-                    None,
-                )
+        let range = to_range(&warning.position());
+        let code = Some(NumberOrString::String(warning.code().to_string()));
+        match warning {
+            WarningType::MissingRequirement(info) => {
+                missing_requirement_diagnostic(&info.symbol, &info.position)
             }
-            ParsingError::Semantic(semantic_error) => {
-                match semantic_error {
-                    // Duplicate Errors
-                    SemanticErrorType::DuplicateObjectDeclaration(ref duplicate)
-                    | SemanticErrorType::DuplicatePredicateDeclaration(ref duplicate)
-                    | SemanticErrorType::DuplicateActionDeclaration(ref duplicate)
-                    | SemanticErrorType::DuplicateCompoundTaskDeclaration(ref duplicate)
-                    | SemanticErrorType::DuplicateMethodDeclaration(ref duplicate)
-                    | SemanticErrorType::DuplicateParameterDeclaration(ref duplicate) => {
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: duplicate.second_pos.line - 1, character: 0 },
-                                end: Position { line: duplicate.second_pos.line, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            Some(vec![
-                                DiagnosticRelatedInformation {
-                                    location: Location {
-                                        // TODO: fix the dummy URI
-                                        uri: Url::parse("//").unwrap(),
-                                        range: Range {
-                                            start: Position { line: duplicate.first_pos.line - 1, character: 0 },
-                                            end: Position { line: duplicate.first_pos.line, character: 0 }
-                                        }
-                                    },
-                                    message: semantic_error.to_string()
-                                }
-                            ]), 
-                            None
-                        )
-                    }
-                    SemanticErrorType::DuplicateRequirementDeclaration(_) => {
-                        // TODO: fix this dummy range
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: 0, character: 0 },
-                                end: Position { line: 1, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::INFORMATION), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
-                    // Undefined Entities
-                    SemanticErrorType::UndefinedPredicate(ref undefined)
-                    | SemanticErrorType::UndefinedType(ref undefined)
-                    | SemanticErrorType::UndefinedSubtask(ref undefined)
-                    | SemanticErrorType::UndefinedTask(ref undefined)
-                    | SemanticErrorType::UndefinedParameter(ref undefined)
-                    | SemanticErrorType::UndefinedObject(ref undefined) => {
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: undefined.position.line - 1, character: 0 },
-                                end: Position { line: undefined.position.line, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
-                    // Inconsistency Error
-                    SemanticErrorType::InconsistentPredicateArity(ref arity_error)
-                    | SemanticErrorType::InconsistentTaskArity(ref arity_error) => {
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: arity_error.position.line - 1, character: 0 },
-                                end: Position { line: arity_error.position.line, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
-                    SemanticErrorType::InconsistentPredicateArgType(ref type_error)
-                    | SemanticErrorType::InconsistentTaskArgType(ref type_error) => {
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: type_error.position.line - 1, character: 0 },
-                                end: Position { line: type_error.position.line, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
-                    // Ordering Errors
-                    SemanticErrorType::CyclicTypeDeclaration => {
-                        // TODO: fix the dummy range
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: 0, character: 0 },
-                                end: Position { line: 10, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
-                    SemanticErrorType::CyclicOrderingDeclaration(pos) |
-                    // Complementary Error
-                    SemanticErrorType::ComplementaryActionEffect(pos) |
-                    SemanticErrorType::ComplementaryActionPrecondition(pos) |
-                    SemanticErrorType::ComplementaryMethodPrecondition(pos) => {
-                        Diagnostic::new(
-                            Range {
-                                start: Position { line: pos.line - 1, character: 0 },
-                                end: Position { line: pos.line, character: 0 }
-                            },
-                            Some(DiagnosticSeverity::ERROR), 
-                            None, 
-                            source, 
-                            semantic_error.to_string(), 
-                            None, 
-                            None
-                        )
-                    }
+            _ => Diagnostic::new(
+                range,
+                Some(DiagnosticSeverity::WARNING),
+                code,
+                source,
+                warning.to_string(),
+                None,
+                None
+            ),
+        }
+    }
+}
+
+// the related information of an error refers to the document of the error
+pub fn to_diagnostic(error: ParsingError, uri: &Url) -> Diagnostic {
+    let source = Some("HDDL Analyzer".to_string());
+    let code = Some(NumberOrString::String(error.code().to_string()));
+    match error {
+        ParsingError::Lexiacal(lexical_error) => {
+            Diagnostic::new(
+                to_range(&lexical_error.position),
+                Some(DiagnosticSeverity::ERROR),
+                code,
+                source,
+                lexical_error.to_string(),
+                None,
+                None,
+            )
+        }
+        ParsingError::Syntactic(syntactic_error) => {
+            Diagnostic::new(
+                to_range(&syntactic_error.position),
+                Some(DiagnosticSeverity::ERROR),
+                code,
+                source,
+                syntactic_error.to_string(),
+                None,
+                None,
+            )
+        }
+        ParsingError::Semantic(semantic_error) => {
+            let data = match &semantic_error {
+                SemanticErrorType::InconsistentPredicateArity(arity_error)
+                | SemanticErrorType::InconsistentTaskArity(arity_error)
+                | SemanticErrorType::InconsistentFunctionArity(arity_error) => Some(json!({
+                    "expected": arity_error.expected_arity,
+                    "found": arity_error.found_arity,
+                })),
+                SemanticErrorType::UndeclaredRequirement(undefined) => Some(json!({
+                    "requirement": undefined.symbol,
+                })),
+                _ => None,
+            };
+            let mut diagnostic = match semantic_error {
+                // Duplicate Errors
+                SemanticErrorType::DuplicateObjectDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicatePredicateDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicateActionDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicateCompoundTaskDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicateMethodDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicateParameterDeclaration(ref duplicate)
+                | SemanticErrorType::DuplicateFunctionDeclaration(ref duplicate) => {
+                    Diagnostic::new(
+                        to_range(&duplicate.second_pos),
+                        Some(DiagnosticSeverity::ERROR), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        Some(vec![
+                            DiagnosticRelatedInformation {
+                                location: Location {
+                                    uri: uri.clone(),
+                                    range: to_range(&duplicate.first_pos)
+                                },
+                                message: semantic_error.to_string()
+                            }
+                        ]), 
+                        None
+                    )
                 }
-            }
+                SemanticErrorType::DuplicateRequirementDeclaration(ref duplicate) => {
+                    Diagnostic::new(
+                        to_range(&duplicate.second_pos),
+                        Some(DiagnosticSeverity::INFORMATION), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        None, 
+                        None
+                    )
+                }
+                // Undefined Entities
+                SemanticErrorType::UndefinedPredicate(ref undefined)
+                | SemanticErrorType::UndefinedType(ref undefined)
+                | SemanticErrorType::UndefinedSubtask(ref undefined)
+                | SemanticErrorType::UndefinedTask(ref undefined)
+                | SemanticErrorType::UndefinedParameter(ref undefined)
+                | SemanticErrorType::UndefinedObject(ref undefined)
+                | SemanticErrorType::UndefinedFunction(ref undefined)
+                | SemanticErrorType::UndeclaredRequirement(ref undefined)
+                | SemanticErrorType::NonClassicalConstruct(ref undefined) => {
+                    Diagnostic::new(
+                        to_range(&undefined.position),
+                        Some(DiagnosticSeverity::ERROR), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        None, 
+                        None
+                    )
+                }
+                // Inconsistency Error
+                SemanticErrorType::InconsistentPredicateArity(ref arity_error)
+                | SemanticErrorType::InconsistentTaskArity(ref arity_error)
+                | SemanticErrorType::InconsistentFunctionArity(ref arity_error) => {
+                    Diagnostic::new(
+                        to_range(&arity_error.position),
+                        Some(DiagnosticSeverity::ERROR), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        None, 
+                        None
+                    )
+                }
+                SemanticErrorType::InconsistentPredicateArgType(ref type_error)
+                | SemanticErrorType::InconsistentTaskArgType(ref type_error)
+                | SemanticErrorType::InconsistentFunctionArgType(ref type_error) => {
+                    Diagnostic::new(
+                        to_range(&type_error.position),
+                        Some(DiagnosticSeverity::ERROR), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        None, 
+                        None
+                    )
+                }
+                // Ordering Errors
+                SemanticErrorType::CyclicTypeDeclaration(pos) |
+                SemanticErrorType::CyclicOrderingDeclaration(pos) |
+                // Complementary Error
+                SemanticErrorType::ComplementaryActionEffect(pos) |
+                SemanticErrorType::ComplementaryActionPrecondition(pos) |
+                SemanticErrorType::ComplementaryMethodPrecondition(pos) |
+                SemanticErrorType::ComplementaryGoal(pos) |
+                SemanticErrorType::MissingGoal(pos) => {
+                    Diagnostic::new(
+                        to_range(&pos),
+                        Some(DiagnosticSeverity::ERROR), 
+                        None, 
+                        source, 
+                        semantic_error.to_string(), 
+                        None, 
+                        None
+                    )
+                }
+            };
+            diagnostic.code = code;
+            diagnostic.data = data;
+            diagnostic
         }
     }
 }
+
+// a requirement that the domain uses without declaring it
+pub fn missing_requirement_diagnostic(requirement: &str, position: &TokenPosition) -> Diagnostic {
+    let mut diagnostic = Diagnostic::new(
+        to_range(position),
+        Some(DiagnosticSeverity::WARNING),
+        Some(NumberOrString::String("MissingRequirement".to_string())),
+        Some("HDDL Analyzer".to_string()),
+        format!("requirement {} is used but not declared", requirement),
+        None,
+        None,
+    );
+    diagnostic.data = Some(json!({ "requirement": requirement }));
+    diagnostic
+}
@@ -0,0 +1,152 @@
+use std::sync::{Arc, OnceLock};
+
+use self_cell::self_cell;
+
+use crate::language_server::diagnostic_utils::to_diagnostic;
+use crate::language_server::formatting_utils::to_offset;
+use crate::language_server::position_encoding::PositionEncoding;
+use crate::language_server::symbol_index::SymbolIndex;
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::semantic_analyzer::{DomainSemanticAnalyzer, SymbolTable};
+use crate::syntactic_analyzer::{AbstractSyntaxTree, Parser};
+use crate::ParsingError;
+
+use tower_lsp::lsp_types::{Diagnostic, TextDocumentContentChangeEvent, Url};
+
+// the (possibly partial) syntax tree and the syntax errors of a document
+type Parse<'a> = (Option<AbstractSyntaxTree<'a>>, Vec<Diagnostic>);
+
+self_cell!(
+    struct ParsedDocument {
+        owner: Vec<u8>,
+
+        #[covariant]
+        dependent: Parse,
+    }
+);
+
+pub struct Analysis<'a> {
+    // the symbols of a domain without syntax or semantic errors
+    pub symbol_table: Option<SymbolTable<'a>>,
+    // the errors and warnings that do not depend on other documents
+    pub diagnostics: Vec<Diagnostic>,
+    pub index: Option<SymbolIndex>,
+}
+
+self_cell!(
+    pub struct AnalyzedDocument {
+        owner: ParsedDocument,
+
+        #[covariant]
+        dependent: Analysis,
+    }
+);
+
+impl AnalyzedDocument {
+    pub fn analyze(url: &Url, content: Vec<u8>) -> AnalyzedDocument {
+        let parsed = ParsedDocument::new(content, |content| {
+            let lexer = LexicalAnalyzer::new(content);
+            let parser = Parser::new(lexer);
+            let (ast, syntax_errors) = parser.parse_partial();
+            (ast, syntax_errors.into_iter().map(|error| to_diagnostic(error, url)).collect())
+        });
+        AnalyzedDocument::new(parsed, |parsed| {
+            let (ast, syntax_errors) = parsed.borrow_dependent();
+            let index = ast.as_ref().map(SymbolIndex::from_ast);
+            match ast {
+                Some(AbstractSyntaxTree::Domain(domain)) if syntax_errors.is_empty() => {
+                    let (symbols, errors) = DomainSemanticAnalyzer::new(domain).verify_domain_all();
+                    let is_valid = errors.is_empty();
+                    let mut diagnostics: Vec<Diagnostic> = errors
+                        .into_iter()
+                        .map(|error| to_diagnostic(ParsingError::Semantic(error), url))
+                        .collect();
+                    diagnostics.extend(symbols.warnings.iter().cloned().map(Diagnostic::from));
+                    let symbol_table = match is_valid {
+                        true => Some(symbols),
+                        false => None,
+                    };
+                    Analysis {
+                        symbol_table,
+                        diagnostics,
+                        index,
+                    }
+                }
+                _ => Analysis {
+                    symbol_table: None,
+                    diagnostics: syntax_errors.clone(),
+                    index,
+                },
+            }
+        })
+    }
+
+    pub fn content(&self) -> &Vec<u8> {
+        self.borrow_owner().borrow_owner()
+    }
+
+    pub fn ast(&self) -> Option<&AbstractSyntaxTree<'_>> {
+        self.borrow_owner().borrow_dependent().0.as_ref()
+    }
+
+    pub fn analysis(&self) -> &Analysis<'_> {
+        self.borrow_dependent()
+    }
+}
+
+// a document that is synced with the client, and is only analyzed again
+// after it is changed
+pub struct Document {
+    pub version: i32,
+    content: Vec<u8>,
+    analysis: OnceLock<Arc<AnalyzedDocument>>,
+}
+
+impl Document {
+    pub fn new(version: i32, content: Vec<u8>) -> Document {
+        Document {
+            version,
+            content,
+            analysis: OnceLock::new(),
+        }
+    }
+
+    pub fn content(&self) -> &Vec<u8> {
+        &self.content
+    }
+
+    pub fn analysis(&self, url: &Url) -> Arc<AnalyzedDocument> {
+        self.analysis
+            .get_or_init(|| Arc::new(AnalyzedDocument::analyze(url, self.content.clone())))
+            .clone()
+    }
+
+    // the changes are applied in order (changes without a range replace the whole content)
+    pub fn apply_changes(
+        &mut self,
+        version: i32,
+        changes: Vec<TextDocumentContentChangeEvent>,
+        encoding: PositionEncoding,
+    ) {
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let range = encoding.decode_range(&self.content, range);
+                    let start = to_offset(&self.content, &range.start);
+                    let end = to_offset(&self.content, &range.end).max(start);
+                    self.content.splice(start..end, change.text.into_bytes());
+                }
+                None => self.content = change.text.into_bytes(),
+            }
+        }
+        self.version = version;
+        self.analysis = OnceLock::new();
+    }
+
+    pub fn replace(&mut self, content: Vec<u8>) {
+        if content != self.content {
+            self.content = content;
+            self.analysis = OnceLock::new();
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::language_server::diagnostic_utils::to_range;
+use crate::TokenPosition;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range, Url};
+
+// the project file (in the root of a workspace folder) with the explicit mappings
+pub const PROJECT_FILE: &str = "hddl.json";
+
+// explicit mappings from problem files (or folders of problems) to their domain file
+#[derive(Debug, Default, Clone)]
+pub struct DomainMappings {
+    mappings: Vec<(PathBuf, PathBuf)>,
+}
+
+impl DomainMappings {
+    // reads {"domainMappings": {"<problem file or folder>": "<domain file>"}}, in
+    // which the relative paths are relative to the base folder
+    pub fn from_json(value: &Value, base: &Path) -> DomainMappings {
+        let mappings = value
+            .get("domainMappings")
+            .and_then(|mappings| mappings.as_object())
+            .map(|mappings| {
+                mappings
+                    .iter()
+                    .filter_map(|(problem, domain)| {
+                        let domain = domain.as_str()?;
+                        Some((base.join(problem), base.join(domain)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        DomainMappings { mappings }
+    }
+
+    pub fn extend(&mut self, other: DomainMappings) {
+        self.mappings.extend(other.mappings);
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    // the most specific mapping (i.e. the longest problem path) is used
+    pub fn domain_of(&self, problem: &Path) -> Option<&PathBuf> {
+        self.mappings
+            .iter()
+            .filter(|(problems, _)| problem.starts_with(problems))
+            .max_by_key(|(problems, _)| problems.components().count())
+            .map(|(_, domain)| domain)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Association {
+    Unique(PathBuf),
+    // the closest candidate, and all of the candidates
+    Ambiguous(PathBuf, Vec<PathBuf>),
+    Missing,
+}
+
+// the domains with the declared name that are closest to the problem (i.e. share
+// the longest folder path with it) are preferred
+pub fn choose_domain(problem: &Path, candidates: &[PathBuf]) -> Association {
+    let folder = problem.parent().unwrap_or(problem);
+    let closeness = |candidate: &PathBuf| {
+        candidate
+            .parent()
+            .unwrap_or(candidate)
+            .components()
+            .zip(folder.components())
+            .take_while(|(a, b)| a == b)
+            .count()
+    };
+    let mut sorted = candidates.to_vec();
+    sorted.sort();
+    let closest = sorted
+        .iter()
+        .rev()
+        .max_by_key(|candidate| closeness(candidate))
+        .cloned();
+    match (closest, sorted.len()) {
+        (None, _) => Association::Missing,
+        (Some(closest), 1) => Association::Unique(closest),
+        (Some(closest), _) => Association::Ambiguous(closest, sorted),
+    }
+}
+
+// a warning on the "(:domain ...)" of a problem, with the related domain files
+pub fn association_warning(position: &TokenPosition, message: String, related: &[PathBuf]) -> Diagnostic {
+    let related_information = related
+        .iter()
+        .filter_map(|domain| Url::from_file_path(domain).ok())
+        .map(|domain| DiagnosticRelatedInformation {
+            location: Location::new(domain, Range::default()),
+            message: "candidate domain".to_string(),
+        })
+        .collect::<Vec<_>>();
+    Diagnostic::new(
+        to_range(position),
+        Some(DiagnosticSeverity::WARNING),
+        None,
+        Some("HDDL Analyzer".to_string()),
+        message,
+        if related_information.is_empty() { None } else { Some(related_information) },
+        None,
+    )
+}
+
+pub fn association_diagnostic(domain_name: &str, position: &TokenPosition, association: &Association) -> Option<Diagnostic> {
+    match association {
+        Association::Unique(_) => None,
+        Association::Ambiguous(chosen, candidates) => Some(association_warning(
+            position,
+            format!(
+                "{} domains are named {}, and {} is used (add a mapping to {} to choose another one)",
+                candidates.len(),
+                domain_name,
+                chosen.display(),
+                PROJECT_FILE
+            ),
+            candidates,
+        )),
+        Association::Missing => Some(association_warning(
+            position,
+            format!("no domain named {} was found in the workspace", domain_name),
+            &[],
+        )),
+    }
+}
@@ -0,0 +1,95 @@
+use crate::formatter::{FormatOptions, PrettyPrinter};
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::syntactic_analyzer::AbstractSyntaxTree;
+use crate::syntactic_analyzer::Parser;
+
+use tower_lsp::lsp_types::{FormattingOptions, Position, Range, TextEdit};
+
+pub fn to_format_options(options: &FormattingOptions, max_line_length: usize) -> FormatOptions {
+    FormatOptions {
+        indent_width: options.tab_size as usize,
+        use_tabs: !options.insert_spaces,
+        max_line_length,
+    }
+}
+
+// columns are counted in bytes (consistent with the diagnostics), the positions
+// of the client are converted by its position encoding
+pub fn to_position(content: &[u8], offset: usize) -> Position {
+    let offset = offset.min(content.len());
+    let line_start = content[..offset]
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |newline| newline + 1);
+    let line = content[..line_start].iter().filter(|byte| **byte == b'\n').count();
+    Position {
+        line: line as u32,
+        character: (offset - line_start) as u32,
+    }
+}
+
+pub fn to_offset(content: &[u8], position: &Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match content[line_start..].iter().position(|byte| *byte == b'\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return content.len(),
+        }
+    }
+    let line_end = content[line_start..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map_or(content.len(), |newline| line_start + newline);
+    (line_start + position.character as usize).min(line_end)
+}
+
+// returns none if the document can not be parsed
+pub fn format_document(content: &Vec<u8>, options: FormatOptions) -> Option<Vec<TextEdit>> {
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let printer = PrettyPrinter::with_comments(content, options);
+    let formatted = match parser.parse().ok()? {
+        AbstractSyntaxTree::Domain(domain) => printer.print_domain(&domain),
+        AbstractSyntaxTree::Problem(problem) => printer.print_problem(&problem),
+    };
+    if formatted.as_bytes() == content.as_slice() {
+        return Some(vec![]);
+    }
+    Some(vec![TextEdit {
+        range: Range {
+            start: Position::new(0, 0),
+            end: to_position(content, content.len()),
+        },
+        new_text: formatted,
+    }])
+}
+
+// only the top-level blocks that overlap with the range are formatted
+pub fn format_range(
+    content: &Vec<u8>,
+    range: &Range,
+    options: FormatOptions,
+) -> Option<Vec<TextEdit>> {
+    let start = to_offset(content, &range.start);
+    let end = to_offset(content, &range.end);
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let printer = PrettyPrinter::with_comments(content, options);
+    let replacements = match parser.parse().ok()? {
+        AbstractSyntaxTree::Domain(domain) => printer.print_domain_range(&domain, (start, end)),
+        AbstractSyntaxTree::Problem(problem) => printer.print_problem_range(&problem, (start, end)),
+    };
+    Some(
+        replacements
+            .into_iter()
+            .filter(|(start, end, text)| text.as_bytes() != &content[*start..*end])
+            .map(|(start, end, text)| TextEdit {
+                range: Range {
+                    start: to_position(content, start),
+                    end: to_position(content, end),
+                },
+                new_text: text,
+            })
+            .collect(),
+    )
+}
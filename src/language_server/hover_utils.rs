@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+
+use petgraph::Direction;
+
+use crate::formatter::parameter_list;
+use crate::language_server::symbol_index::ElementKind;
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::semantic_analyzer::{DomainTypeChecker, TaskType, TDG};
+use crate::syntactic_analyzer::*;
+
+// markdown description of a symbol declared in the domain
+pub fn describe_domain_symbol(content: &Vec<u8>, kind: ElementKind, name: &str) -> Option<String> {
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let (Some(AbstractSyntaxTree::Domain(domain)), _) = parser.parse_partial() else {
+        return None;
+    };
+    match kind {
+        ElementKind::Task => describe_task(&domain, name),
+        ElementKind::Method => {
+            let method = domain.methods.iter().find(|method| method.name.name == name)?;
+            let task: Vec<String> = std::iter::once(method.task.name.to_string())
+                .chain(method.task_terms.iter().map(|term| term_name(term)))
+                .collect();
+            Some(format!(
+                "{}\n\nmethod of `({})`",
+                signature(&format!(
+                    "(:method {} :parameters ({}))",
+                    name,
+                    parameter_list(&method.params)
+                )),
+                task.join(" ")
+            ))
+        }
+        ElementKind::Predicate => {
+            let predicate = domain.predicates.iter().find(|predicate| predicate.name == name)?;
+            let parameters = parameter_list(&predicate.variables);
+            if parameters.is_empty() {
+                Some(signature(&format!("({})", name)))
+            } else {
+                Some(signature(&format!("({} {})", name, parameters)))
+            }
+        }
+        ElementKind::Type => describe_type(&domain, name),
+        ElementKind::Object => {
+            let constant = domain
+                .constants
+                .as_ref()?
+                .iter()
+                .find(|constant| constant.name == name)?;
+            Some(format!("{}\n\nconstant", typed_name(constant)))
+        }
+        ElementKind::Variable => None,
+    }
+}
+
+// markdown description of an object declared in the problem
+pub fn describe_problem_object(content: &Vec<u8>, name: &str) -> Option<String> {
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let (Some(AbstractSyntaxTree::Problem(problem)), _) = parser.parse_partial() else {
+        return None;
+    };
+    let object = problem.objects.iter().find(|object| object.name == name)?;
+    Some(format!("{}\n\nobject", typed_name(object)))
+}
+
+fn describe_task(domain: &DomainAST, name: &str) -> Option<String> {
+    let (header, parameters) = match domain.compound_tasks.iter().find(|task| task.name == name) {
+        Some(task) => ("task", &task.parameters),
+        None => {
+            let action = domain.actions.iter().find(|action| action.name == name)?;
+            match action.timing {
+                Some(_) => ("durative-action", &action.parameters),
+                None => ("action", &action.parameters),
+            }
+        }
+    };
+    let mut description = signature(&format!(
+        "(:{} {} :parameters ({}))",
+        header,
+        name,
+        parameter_list(parameters)
+    ));
+    // the decomposition graph is only built for domains whose task references are resolved
+    let tdg = match is_resolved(domain) {
+        true => Some(TDG::new(domain)),
+        false => None,
+    };
+    let task_type = match tdg.as_ref().and_then(|tdg| tdg.task_type(name)) {
+        Some(task_type) => task_type,
+        None if header != "task" => TaskType::Primitive,
+        None => TaskType::Compound,
+    };
+    match task_type {
+        TaskType::Primitive => description.push_str("\n\nprimitive task"),
+        TaskType::Compound => {
+            description.push_str("\n\ncompound task");
+            if let Some(tdg) = &tdg {
+                if tdg.compute_nullables().contains(name) {
+                    description.push_str(" (nullable)");
+                } else {
+                    description.push_str(" (not nullable)");
+                }
+            }
+            let methods: Vec<String> = domain
+                .methods
+                .iter()
+                .filter(|method| method.task.name == name)
+                .map(|method| format!("`{}`", method.name.name))
+                .collect();
+            if methods.is_empty() {
+                description.push_str("\n\nno methods");
+            } else {
+                description.push_str(&format!("\n\nmethods: {}", methods.join(", ")));
+            }
+        }
+    }
+    Some(description)
+}
+
+fn describe_type(domain: &DomainAST, name: &str) -> Option<String> {
+    let type_hierarchy = DomainTypeChecker::new(&domain.types).get_type_hierarchy();
+    if !type_hierarchy.contains_node(name) {
+        return None;
+    }
+    let mut description = signature(&format!("(:types {})", name));
+    // edges point from a type to its supertypes
+    for (label, direction) in [("supertypes", Direction::Outgoing), ("subtypes", Direction::Incoming)] {
+        let mut neighbors: Vec<&str> = type_hierarchy.neighbors_directed(name, direction).collect();
+        if !neighbors.is_empty() {
+            neighbors.sort();
+            let neighbors: Vec<String> = neighbors.iter().map(|t| format!("`{}`", t)).collect();
+            description.push_str(&format!("\n\n{}: {}", label, neighbors.join(", ")));
+        }
+    }
+    Some(description)
+}
+
+// every task that is refined or used as a subtask is declared
+fn is_resolved(domain: &DomainAST) -> bool {
+    let declared: HashSet<&str> = domain
+        .compound_tasks
+        .iter()
+        .map(|task| task.name)
+        .chain(domain.actions.iter().map(|action| action.name))
+        .collect();
+    domain.methods.iter().all(|method| {
+        declared.contains(method.task.name)
+            && method
+                .tn
+                .subtasks
+                .iter()
+                .all(|subtask| declared.contains(subtask.task.name))
+    })
+}
+
+fn signature(code: &str) -> String {
+    format!("```hddl\n{}\n```", code)
+}
+
+fn typed_name(symbol: &Symbol) -> String {
+    match symbol.symbol_type {
+        Some(symbol_type) => signature(&format!("{} - {}", symbol.name, symbol_type)),
+        None => signature(symbol.name),
+    }
+}
+
+// variables keep their '?'
+pub fn term_name(term: &Symbol) -> String {
+    let lexeme_length = term.name_pos.end_offset - term.name_pos.offset;
+    if lexeme_length == term.name.len() + 1 {
+        format!("?{}", term.name)
+    } else {
+        term.name.to_string()
+    }
+}
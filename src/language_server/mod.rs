@@ -1,8 +1,32 @@
 pub mod request_handler;
+mod code_action_utils;
+mod completion_utils;
 mod diagnostic_utils;
+mod document_store;
+mod domain_association;
+mod formatting_utils;
+mod hover_utils;
+mod outline_utils;
+mod position_encoding;
+mod rename_utils;
+mod semantic_tokens_utils;
+mod symbol_index;
+#[cfg(test)]
+mod tests;
 
 pub use request_handler::RequestHandler;
 
 use super::*;
 
-use crate::language_server::diagnostic_utils::*;
\ No newline at end of file
+use crate::language_server::code_action_utils::*;
+use crate::language_server::completion_utils::*;
+use crate::language_server::diagnostic_utils::*;
+use crate::language_server::document_store::*;
+use crate::language_server::domain_association::*;
+use crate::language_server::formatting_utils::*;
+use crate::language_server::hover_utils::*;
+use crate::language_server::outline_utils::*;
+use crate::language_server::position_encoding::*;
+use crate::language_server::rename_utils::*;
+use crate::language_server::semantic_tokens_utils::*;
+use crate::language_server::symbol_index::*;
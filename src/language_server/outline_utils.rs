@@ -0,0 +1,264 @@
+use crate::formatter::{block_spans, parameter_list};
+use crate::language_server::diagnostic_utils::to_range;
+use crate::language_server::formatting_utils::to_position;
+use crate::language_server::hover_utils::term_name;
+use crate::language_server::symbol_index::{index_document, ElementKind};
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::syntactic_analyzer::*;
+use crate::TokenPosition;
+
+use tower_lsp::lsp_types::{DocumentSymbol, Location, Range, SymbolInformation, SymbolKind, Url};
+
+// the outline of a domain (types, constants, predicates, tasks with their
+// methods, and actions) or a problem (objects, initial task network, initial
+// state and goal)
+pub fn document_symbols(content: &Vec<u8>) -> Option<Vec<DocumentSymbol>> {
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let blocks = block_spans(content);
+    match parser.parse_partial() {
+        (Some(AbstractSyntaxTree::Domain(domain)), _) => Some(domain_symbols(content, &blocks, &domain)),
+        (Some(AbstractSyntaxTree::Problem(problem)), _) => {
+            Some(problem_symbols(content, &blocks, &problem))
+        }
+        (None, _) => None,
+    }
+}
+
+// the declarations of the document whose name matches the query
+#[allow(deprecated)]
+pub fn workspace_symbols(url: &Url, content: &Vec<u8>, query: &str) -> Vec<SymbolInformation> {
+    let Some(index) = index_document(content) else {
+        return vec![];
+    };
+    let container_name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .map(|file_name| file_name.to_string());
+    index
+        .occurrences
+        .iter()
+        .filter(|occurrence| occurrence.is_declaration && occurrence.kind != ElementKind::Variable)
+        .filter(|occurrence| fuzzy_score(query, &occurrence.name).is_some())
+        .map(|occurrence| SymbolInformation {
+            name: occurrence.name.clone(),
+            kind: symbol_kind(occurrence.kind),
+            tags: None,
+            deprecated: None,
+            location: Location::new(url.clone(), to_range(&occurrence.position)),
+            container_name: container_name.clone(),
+        })
+        .collect()
+}
+
+// the characters of the query appear in the name in the same order (ignoring
+// case); lower scores are better matches
+pub fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    let mut score = 0;
+    let mut remaining = name.char_indices();
+    let mut last_match = None;
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let (index, _) = remaining.find(|(_, c)| *c == query_char)?;
+        // gaps between the matched characters
+        score += match last_match {
+            Some(last_match) => index - last_match - 1,
+            None => index,
+        };
+        last_match = Some(index);
+    }
+    Some(score)
+}
+
+fn symbol_kind(kind: ElementKind) -> SymbolKind {
+    match kind {
+        ElementKind::Task => SymbolKind::FUNCTION,
+        ElementKind::Method => SymbolKind::METHOD,
+        ElementKind::Predicate => SymbolKind::PROPERTY,
+        ElementKind::Type => SymbolKind::CLASS,
+        ElementKind::Object => SymbolKind::CONSTANT,
+        ElementKind::Variable => SymbolKind::VARIABLE,
+    }
+}
+
+fn domain_symbols(content: &[u8], blocks: &[(usize, usize)], domain: &DomainAST) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    for declared_type in domain.types.iter().flatten() {
+        symbols.push(leaf(ElementKind::Type, declared_type.name, declared_type.symbol_type, &declared_type.name_pos));
+    }
+    for constant in domain.constants.iter().flatten() {
+        symbols.push(leaf(ElementKind::Object, constant.name, constant.symbol_type, &constant.name_pos));
+    }
+    for predicate in domain.predicates.iter() {
+        let parameters = parameter_list(&predicate.variables);
+        symbols.push(leaf(ElementKind::Predicate, predicate.name, Some(&parameters), &predicate.name_pos));
+    }
+    let method_symbol = |method: &Method| {
+        let parameters = parameter_list(&method.params);
+        block(content, blocks, ElementKind::Method, method.name.name, &parameters, &method.name.name_pos)
+    };
+    for task in domain.compound_tasks.iter() {
+        let mut symbol = block(
+            content,
+            blocks,
+            ElementKind::Task,
+            task.name,
+            &parameter_list(&task.parameters),
+            &task.name_pos,
+        );
+        let methods: Vec<DocumentSymbol> = domain
+            .methods
+            .iter()
+            .filter(|method| method.task.name == task.name)
+            .map(method_symbol)
+            .collect();
+        if !methods.is_empty() {
+            symbol.children = Some(methods);
+        }
+        symbols.push(symbol);
+    }
+    // methods of undeclared tasks are not lost
+    for method in domain.methods.iter() {
+        if !domain.compound_tasks.iter().any(|task| task.name == method.task.name) {
+            symbols.push(method_symbol(method));
+        }
+    }
+    for action in domain.actions.iter() {
+        let parameters = parameter_list(&action.parameters);
+        symbols.push(block(content, blocks, ElementKind::Task, action.name, &parameters, &action.name_pos));
+    }
+    symbols
+}
+
+fn problem_symbols(content: &[u8], blocks: &[(usize, usize)], problem: &ProblemAST) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+    let objects = problem
+        .objects
+        .iter()
+        .map(|object| leaf(ElementKind::Object, object.name, object.symbol_type, &object.name_pos))
+        .collect();
+    symbols.extend(section(content, blocks, ":objects", objects));
+    if let Some(init_tn) = &problem.init_tn {
+        let subtasks = init_tn
+            .tn
+            .subtasks
+            .iter()
+            .map(|subtask| {
+                let detail = subtask.id.as_ref().map(|id| id.name);
+                leaf(ElementKind::Task, &atom(subtask.task.name, &subtask.terms), detail, &subtask.task.name_pos)
+            })
+            .collect();
+        symbols.extend(section(content, blocks, ":htn", subtasks));
+    }
+    let facts = problem
+        .init_state
+        .iter()
+        .map(|fact| leaf(ElementKind::Predicate, &atom(fact.name, &fact.variables), None, &fact.name_pos))
+        .collect();
+    symbols.extend(section(content, blocks, ":init", facts));
+    if let Some(goal) = &problem.goal {
+        let atoms = goal
+            .get_propositional_predicates()
+            .iter()
+            .map(|goal_atom| {
+                leaf(ElementKind::Predicate, &atom(goal_atom.name, &goal_atom.variables), None, &goal_atom.name_pos)
+            })
+            .collect();
+        symbols.extend(section(content, blocks, ":goal", atoms));
+    }
+    symbols
+}
+
+fn atom(name: &str, terms: &Vec<Symbol>) -> String {
+    let terms: Vec<String> = std::iter::once(name.to_string())
+        .chain(terms.iter().map(term_name))
+        .collect();
+    format!("({})", terms.join(" "))
+}
+
+#[allow(deprecated)]
+fn symbol(name: String, detail: Option<String>, kind: SymbolKind, range: Range, selection_range: Range) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: None,
+    }
+}
+
+// a symbol that only spans its name
+fn leaf(kind: ElementKind, name: &str, detail: Option<&str>, position: &TokenPosition) -> DocumentSymbol {
+    let range = to_range(position);
+    let detail = detail.filter(|detail| !detail.is_empty()).map(|detail| detail.to_string());
+    symbol(name.to_string(), detail, symbol_kind(kind), range, range)
+}
+
+// a symbol that spans its (top-level) block
+fn block(
+    content: &[u8],
+    blocks: &[(usize, usize)],
+    kind: ElementKind,
+    name: &str,
+    detail: &str,
+    position: &TokenPosition,
+) -> DocumentSymbol {
+    let mut symbol = leaf(kind, name, Some(detail), position);
+    if let Some((start, end)) = blocks
+        .iter()
+        .find(|(start, end)| *start <= position.offset && position.end_offset <= *end)
+    {
+        symbol.range = Range {
+            start: to_position(content, *start),
+            end: to_position(content, *end),
+        };
+    }
+    symbol
+}
+
+// a top-level block of a problem (e.g. ":init") that groups its elements
+fn section(
+    content: &[u8],
+    blocks: &[(usize, usize)],
+    keyword: &str,
+    children: Vec<DocumentSymbol>,
+) -> Option<DocumentSymbol> {
+    let (start, end) = find_block(content, blocks, keyword)?;
+    let keyword_start = start + 1 + content[start + 1..end]
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(0);
+    let range = Range {
+        start: to_position(content, start),
+        end: to_position(content, end),
+    };
+    let selection_range = Range {
+        start: to_position(content, keyword_start),
+        end: to_position(content, keyword_start + keyword.len()),
+    };
+    let mut symbol = symbol(
+        keyword.trim_start_matches(':').to_string(),
+        Some(format!("{} elements", children.len())),
+        SymbolKind::NAMESPACE,
+        range,
+        selection_range,
+    );
+    symbol.children = Some(children);
+    Some(symbol)
+}
+
+// the top-level block that starts with the keyword (e.g. ":predicates")
+pub fn find_block(content: &[u8], blocks: &[(usize, usize)], keyword: &str) -> Option<(usize, usize)> {
+    blocks
+        .iter()
+        .find(|(start, end)| {
+            let text = String::from_utf8_lossy(&content[start + 1..*end]).to_lowercase();
+            let text = text.trim_start();
+            text.starts_with(keyword)
+                && !text[keyword.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+        .copied()
+}
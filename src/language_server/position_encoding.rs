@@ -0,0 +1,90 @@
+use tower_lsp::lsp_types::{Position, PositionEncodingKind, Range};
+
+// the server counts the columns of a line in bytes, while clients count them
+// in UTF-16 code units (unless they agree to UTF-8 during the initialization)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PositionEncoding {
+    UTF8,
+    UTF16,
+}
+
+impl PositionEncoding {
+    // the preferred encoding among the ones offered by the client
+    pub fn negotiate(offered: Option<&[PositionEncodingKind]>) -> PositionEncoding {
+        match offered {
+            Some(offered) if offered.contains(&PositionEncodingKind::UTF8) => PositionEncoding::UTF8,
+            _ => PositionEncoding::UTF16,
+        }
+    }
+
+    pub fn kind(self) -> PositionEncodingKind {
+        match self {
+            PositionEncoding::UTF8 => PositionEncodingKind::UTF8,
+            PositionEncoding::UTF16 => PositionEncodingKind::UTF16,
+        }
+    }
+
+    // converts a position of the client to the (byte) column of the server
+    pub fn decode(self, content: &[u8], position: Position) -> Position {
+        match self {
+            PositionEncoding::UTF8 => position,
+            PositionEncoding::UTF16 => {
+                let line = line_of(content, position.line);
+                let mut units = 0;
+                let mut column = 0;
+                while column < line.len() && units < position.character as usize {
+                    let (width, char_units) = char_width(line[column]);
+                    units += char_units;
+                    column += width;
+                }
+                Position::new(position.line, column.min(line.len()) as u32)
+            }
+        }
+    }
+
+    // converts a (byte) column of the server to a position of the client
+    pub fn encode(self, content: &[u8], position: Position) -> Position {
+        let line = line_of(content, position.line);
+        let end = (position.character as usize).min(line.len());
+        Position::new(position.line, self.columns(&line[..end]))
+    }
+
+    // the number of columns the client counts for the bytes of a line
+    pub fn columns(self, bytes: &[u8]) -> u32 {
+        match self {
+            PositionEncoding::UTF8 => bytes.len() as u32,
+            PositionEncoding::UTF16 => bytes
+                .iter()
+                // continuation bytes belong to the previous character
+                .filter(|byte| *byte & 0xC0 != 0x80)
+                .map(|byte| char_width(*byte).1 as u32)
+                .sum(),
+        }
+    }
+
+    pub fn decode_range(self, content: &[u8], range: Range) -> Range {
+        Range::new(self.decode(content, range.start), self.decode(content, range.end))
+    }
+
+    pub fn encode_range(self, content: &[u8], range: Range) -> Range {
+        Range::new(self.encode(content, range.start), self.encode(content, range.end))
+    }
+}
+
+// the bytes of the line (without the line break)
+fn line_of(content: &[u8], line: u32) -> &[u8] {
+    content
+        .split(|byte| *byte == b'\n')
+        .nth(line as usize)
+        .unwrap_or_default()
+}
+
+// the length of a character in bytes and in UTF-16 code units (by its first byte)
+fn char_width(first_byte: u8) -> (usize, usize) {
+    match first_byte {
+        0xF0..=0xFF => (4, 2),
+        0xE0..=0xEF => (3, 1),
+        0xC0..=0xDF => (2, 1),
+        _ => (1, 1),
+    }
+}
@@ -0,0 +1,46 @@
+use crate::language_server::diagnostic_utils::to_range;
+use crate::language_server::symbol_index::{ElementKind, SymbolIndex, SymbolOccurrence};
+
+use tower_lsp::lsp_types::TextEdit;
+
+// the name without the '?' of variables, if it is a valid identifier
+pub fn validate_name(new_name: &str) -> Result<String, String> {
+    let name = new_name.trim().trim_start_matches('?');
+    let mut characters = name.chars();
+    let is_valid = match characters.next() {
+        Some(first) => {
+            first.is_alphabetic()
+                && characters.all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        }
+        None => false,
+    };
+    if is_valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!("{} is not a valid name", new_name))
+    }
+}
+
+// whether the new name is taken by another symbol of the same kind (and scope)
+pub fn is_declared(index: &SymbolIndex, symbol: &SymbolOccurrence, new_name: &str) -> bool {
+    index.occurrences.iter().any(|occurrence| {
+        occurrence.is_declaration
+            && occurrence.kind == symbol.kind
+            && occurrence.scope == symbol.scope
+            && occurrence.name == new_name
+    })
+}
+
+pub fn rename_edits(index: &SymbolIndex, symbol: &SymbolOccurrence, new_name: &str) -> Vec<TextEdit> {
+    let new_text = match symbol.kind {
+        ElementKind::Variable => format!("?{}", new_name),
+        _ => new_name.to_string(),
+    };
+    index
+        .occurrences_of(symbol)
+        .map(|occurrence| TextEdit {
+            range: to_range(&occurrence.position),
+            new_text: new_text.clone(),
+        })
+        .collect()
+}
@@ -1,5 +1,8 @@
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use tokio::sync::RwLock;
 use tower_lsp::lsp_types::*;
@@ -9,7 +12,25 @@ use super::*;
 
 pub struct RequestHandler {
     client: Client,
-    documents: Arc<RwLock<HashMap<Url, Vec<u8>>>>,
+    documents: Arc<RwLock<HashMap<Url, Document>>>,
+    // analyses of the files that are not synced (e.g. the domain of a problem)
+    analyses: RwLock<HashMap<Url, Arc<AnalyzedDocument>>>,
+    // set by the client through the "maxLineLength" initialization option
+    max_line_length: AtomicUsize,
+    // whether the client pulls the diagnostics (otherwise they are published),
+    // and can be asked to pull them again
+    pull_diagnostics: AtomicBool,
+    diagnostic_refresh: AtomicBool,
+    // whether the client agreed to count the columns in bytes (otherwise they
+    // are counted in UTF-16 code units)
+    utf8_positions: AtomicBool,
+    // searched for workspace symbols and domains
+    workspace_folders: RwLock<Vec<PathBuf>>,
+    // the "domainMappings" of the initialization options (or the latest settings)
+    settings: RwLock<Option<serde_json::Value>>,
+    domain_mappings: RwLock<DomainMappings>,
+    // the domain names of the files on the disk (by their modification time)
+    domain_names: RwLock<HashMap<PathBuf, (SystemTime, Option<String>)>>,
 }
 
 impl RequestHandler {
@@ -17,11 +38,583 @@ impl RequestHandler {
         RequestHandler {
             client,
             documents: Arc::new(RwLock::new(HashMap::new())),
+            analyses: RwLock::new(HashMap::new()),
+            max_line_length: AtomicUsize::new(FormatOptions::default().max_line_length),
+            pull_diagnostics: AtomicBool::new(false),
+            diagnostic_refresh: AtomicBool::new(false),
+            utf8_positions: AtomicBool::new(false),
+            workspace_folders: RwLock::new(vec![]),
+            settings: RwLock::new(None),
+            domain_mappings: RwLock::new(DomainMappings::default()),
+            domain_names: RwLock::new(HashMap::new()),
+        }
+    }
+
+    // the encoding of the positions agreed on with the client
+    fn encoding(&self) -> PositionEncoding {
+        match self.utf8_positions.load(Ordering::Relaxed) {
+            true => PositionEncoding::UTF8,
+            false => PositionEncoding::UTF16,
+        }
+    }
+
+    // the offset of a position of the client in the document
+    fn offset(&self, document: &[u8], position: Position) -> usize {
+        to_offset(document, &self.encoding().decode(document, position))
+    }
+
+    // the ranges of the server are converted to the positions of the client
+    fn encode_edits(&self, content: &[u8], edits: Vec<TextEdit>) -> Vec<TextEdit> {
+        let encoding = self.encoding();
+        edits
+            .into_iter()
+            .map(|edit| TextEdit::new(encoding.encode_range(content, edit.range), edit.new_text))
+            .collect()
+    }
+
+    fn encode_locations(&self, content: &[u8], locations: Vec<Location>) -> Vec<Location> {
+        let encoding = self.encoding();
+        locations
+            .into_iter()
+            .map(|location| Location::new(location.uri, encoding.encode_range(content, location.range)))
+            .collect()
+    }
+
+    fn encode_symbols(&self, content: &[u8], symbols: &mut [DocumentSymbol]) {
+        let encoding = self.encoding();
+        for symbol in symbols {
+            symbol.range = encoding.encode_range(content, symbol.range);
+            symbol.selection_range = encoding.encode_range(content, symbol.selection_range);
+            if let Some(children) = &mut symbol.children {
+                self.encode_symbols(content, children);
+            }
         }
     }
 
     async fn sync(&self, url: Url, content: Vec<u8>) {
-        self.documents.write().await.insert(url, content);
+        let mut documents = self.documents.write().await;
+        match documents.get_mut(&url) {
+            Some(document) => document.replace(content),
+            None => {
+                documents.insert(url, Document::new(0, content));
+            }
+        }
+    }
+
+    async fn document(&self, url: &Url) -> tower_lsp::jsonrpc::Result<Vec<u8>> {
+        match self.documents.read().await.get(url) {
+            Some(document) => Ok(document.content().clone()),
+            None => Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "{} is not synced",
+                url
+            ))),
+        }
+    }
+
+    // the domains or problems next to the document (the synced content is
+    // preferred over the content on the disk)
+    async fn neighbours(&self, url: &Url, variant: FileVariant) -> Vec<(Url, Vec<u8>)> {
+        let mut neighbours = vec![];
+        let Ok(file_path) = url.to_file_path() else {
+            return neighbours;
+        };
+        let Some(root_folder) = file_path.parent() else {
+            return neighbours;
+        };
+        let Ok(mut files) = tokio::fs::read_dir(root_folder).await else {
+            return neighbours;
+        };
+        while let Ok(Some(entry)) = files.next_entry().await {
+            match entry.path().extension() {
+                Some(extension) if (extension == "hddl" || extension == "pddl") => {
+                    let Ok(entry_url) = Url::from_file_path(entry.path()) else {
+                        continue;
+                    };
+                    if &entry_url == url {
+                        continue;
+                    }
+                    let synced = self
+                        .documents
+                        .read()
+                        .await
+                        .get(&entry_url)
+                        .map(|document| document.content().clone());
+                    let content = match synced {
+                        Some(content) => content,
+                        None => match tokio::fs::read(entry.path()).await {
+                            Ok(content) => content,
+                            Err(_) => continue,
+                        },
+                    };
+                    match (classify_file(&content), &variant) {
+                        (FileVariant::Domain, FileVariant::Domain)
+                        | (FileVariant::Problem, FileVariant::Problem) => {
+                            neighbours.push((entry_url, content));
+                        }
+                        _ => {}
+                    }
+                }
+                // File is not .PDDL or .HDDL
+                _ => {}
+            }
+        }
+        neighbours.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        neighbours
+    }
+
+    // the domains and problems in the workspace folders
+    async fn workspace_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![];
+        let mut folders = self.workspace_folders.read().await.clone();
+        while let Some(folder) = folders.pop() {
+            let Ok(mut entries) = tokio::fs::read_dir(&folder).await else {
+                continue;
+            };
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+                match entry.file_type().await {
+                    Ok(file_type) if file_type.is_dir() && !is_hidden => folders.push(path),
+                    Ok(file_type) if file_type.is_file() => match path.extension() {
+                        Some(extension) if (extension == "hddl" || extension == "pddl") => {
+                            files.push(path);
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    // the synced documents and the domains and problems in the workspace folders
+    async fn workspace_documents(&self) -> Vec<(Url, Vec<u8>)> {
+        let mut documents: HashMap<Url, Vec<u8>> = self
+            .documents
+            .read()
+            .await
+            .iter()
+            .map(|(url, document)| (url.clone(), document.content().clone()))
+            .collect();
+        for path in self.workspace_files().await {
+            let Ok(url) = Url::from_file_path(&path) else {
+                continue;
+            };
+            if documents.contains_key(&url) {
+                continue;
+            }
+            if let Ok(content) = tokio::fs::read(&path).await {
+                documents.insert(url, content);
+            }
+        }
+        let mut documents: Vec<(Url, Vec<u8>)> = documents.into_iter().collect();
+        documents.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        documents
+    }
+
+    // the synced content of the file, or its content on the disk
+    async fn read(&self, url: &Url) -> Option<Vec<u8>> {
+        if let Some(document) = self.documents.read().await.get(url) {
+            return Some(document.content().clone());
+        }
+        tokio::fs::read(url.to_file_path().ok()?).await.ok()
+    }
+
+    // the domains in the workspace folders and next to the problem by their
+    // name (files on the disk are only read again after they are modified)
+    async fn domains(&self, problem: &Path) -> Vec<(PathBuf, String)> {
+        let mut files = self.workspace_files().await;
+        if let Some(folder) = problem.parent() {
+            if let Ok(mut entries) = tokio::fs::read_dir(folder).await {
+                while let Ok(Some(entry)) = entries.next_entry().await {
+                    let path = entry.path();
+                    match path.extension() {
+                        Some(extension) if (extension == "hddl" || extension == "pddl") => {
+                            files.push(path)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        files.sort();
+        files.dedup();
+        let mut domains = vec![];
+        for path in files {
+            if path == problem {
+                continue;
+            }
+            let Ok(url) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let synced = self
+                .documents
+                .read()
+                .await
+                .get(&url)
+                .map(|document| document.content().clone());
+            let domain_name = match synced {
+                Some(content) => definition_domain_name(&content),
+                None => {
+                    let Ok(modified) = tokio::fs::metadata(&path).await.and_then(|m| m.modified()) else {
+                        continue;
+                    };
+                    let cached = self.domain_names.read().await.get(&path).cloned();
+                    match cached {
+                        Some((time, domain_name)) if time == modified => domain_name,
+                        _ => {
+                            let Ok(content) = tokio::fs::read(&path).await else {
+                                continue;
+                            };
+                            let domain_name = definition_domain_name(&content);
+                            self.domain_names
+                                .write()
+                                .await
+                                .insert(path.clone(), (modified, domain_name.clone()));
+                            domain_name
+                        }
+                    }
+                }
+            };
+            if let Some(domain_name) = domain_name {
+                domains.push((path, domain_name));
+            }
+        }
+        domains
+    }
+
+    // the domain of a problem (an explicit mapping is preferred over the domains
+    // with the declared name), and a warning if the domain is not clear
+    async fn associate(&self, url: &Url) -> (Option<(Url, Vec<u8>)>, Option<Diagnostic>) {
+        let Some(content) = self.read(url).await else {
+            return (None, None);
+        };
+        let analysis = self.analysis(url, &content).await;
+        let Some(AbstractSyntaxTree::Problem(problem)) = analysis.ast() else {
+            return (None, None);
+        };
+        let Ok(problem_path) = url.to_file_path() else {
+            return (None, None);
+        };
+        let mapped = self.domain_mappings.read().await.domain_of(&problem_path).cloned();
+        let mut mapping_warning = None;
+        if let Some(domain_path) = mapped {
+            let domain = match Url::from_file_path(&domain_path) {
+                Ok(domain_url) => self.read(&domain_url).await.map(|domain| (domain_url, domain)),
+                Err(_) => None,
+            };
+            match domain {
+                Some((domain_url, domain)) => {
+                    let warning = match definition_domain_name(&domain) {
+                        Some(name) if name.eq_ignore_ascii_case(&problem.domain_name) => None,
+                        name => Some(association_warning(
+                            &problem.domain_name_pos,
+                            format!(
+                                "the problem refers to {}, but it is mapped to {} ({})",
+                                problem.domain_name,
+                                domain_path.display(),
+                                name.unwrap_or("not a domain".to_string())
+                            ),
+                            std::slice::from_ref(&domain_path),
+                        )),
+                    };
+                    return (Some((domain_url, domain)), warning);
+                }
+                None => {
+                    mapping_warning = Some(association_warning(
+                        &problem.domain_name_pos,
+                        format!("the mapped domain {} can not be read", domain_path.display()),
+                        &[],
+                    ));
+                }
+            }
+        }
+        let candidates: Vec<PathBuf> = self
+            .domains(&problem_path)
+            .await
+            .into_iter()
+            .filter(|(_, name)| name.eq_ignore_ascii_case(&problem.domain_name))
+            .map(|(path, _)| path)
+            .collect();
+        let association = choose_domain(&problem_path, &candidates);
+        let warning = mapping_warning.or(association_diagnostic(
+            &problem.domain_name,
+            &problem.domain_name_pos,
+            &association,
+        ));
+        let domain = match association {
+            Association::Unique(path) | Association::Ambiguous(path, _) => match Url::from_file_path(&path) {
+                Ok(domain_url) => self.read(&domain_url).await.map(|domain| (domain_url, domain)),
+                Err(_) => None,
+            },
+            Association::Missing => None,
+        };
+        (domain, warning)
+    }
+
+    // the explicit mappings of the settings and of the project files
+    async fn reload_mappings(&self) {
+        let folders = self.workspace_folders.read().await.clone();
+        let mut mappings = DomainMappings::default();
+        for folder in folders.iter() {
+            let Ok(project) = tokio::fs::read(folder.join(PROJECT_FILE)).await else {
+                continue;
+            };
+            match serde_json::from_slice(&project) {
+                Ok(project) => mappings.extend(DomainMappings::from_json(&project, folder)),
+                Err(error) => {
+                    self.client
+                        .log_message(
+                            MessageType::WARNING,
+                            format!("{} is not valid JSON: {}", folder.join(PROJECT_FILE).display(), error),
+                        )
+                        .await
+                }
+            }
+        }
+        if let Some(settings) = self.settings.read().await.as_ref() {
+            let base = folders.first().cloned().unwrap_or_default();
+            mappings.extend(DomainMappings::from_json(settings, &base));
+        }
+        *self.domain_mappings.write().await = mappings;
+    }
+
+    // the cached analysis of the content (the content of a synced document is
+    // analyzed once per version, and the rest once per change on the disk)
+    async fn analysis(&self, url: &Url, content: &Vec<u8>) -> Arc<AnalyzedDocument> {
+        if let Some(document) = self.documents.read().await.get(url) {
+            if document.content() == content {
+                return document.analysis(url);
+            }
+        }
+        if let Some(analysis) = self.analyses.read().await.get(url) {
+            if analysis.content() == content {
+                return analysis.clone();
+            }
+        }
+        let analysis = Arc::new(AnalyzedDocument::analyze(url, content.clone()));
+        self.analyses.write().await.insert(url.clone(), analysis.clone());
+        analysis
+    }
+
+    async fn diagnose(&self, url: &Url, document: &Vec<u8>) -> Vec<Diagnostic> {
+        let encoding = self.encoding();
+        let mut diagnostics = self.diagnose_document(url, document).await;
+        for diagnostic in diagnostics.iter_mut() {
+            diagnostic.range = encoding.encode_range(document, diagnostic.range);
+            for related in diagnostic.related_information.iter_mut().flatten() {
+                if &related.location.uri == url {
+                    related.location.range = encoding.encode_range(document, related.location.range);
+                }
+            }
+        }
+        diagnostics
+    }
+
+    async fn diagnose_document(&self, url: &Url, document: &Vec<u8>) -> Vec<Diagnostic> {
+        match classify_file(document) {
+            FileVariant::Domain => {
+                self.client
+                    .log_message(
+                        MessageType::LOG,
+                        format!("{} is a domain. Attempting to diagnose.", url),
+                    )
+                    .await;
+                let analysis = self.analysis(url, document).await;
+                diagnose_domain(&analysis)
+            }
+            FileVariant::Problem => {
+                let problem = self.analysis(url, document).await;
+                let (domain, warning) = self.associate(url).await;
+                let mut diagnostics = match domain {
+                    Some((domain_url, domain)) => {
+                        self.client
+                            .log_message(
+                                MessageType::LOG,
+                                format!(
+                                    "{} is the domain for {}. Attempting to diagnose.",
+                                    domain_url,
+                                    url
+                                ),
+                            )
+                            .await;
+                        let domain = self.analysis(&domain_url, &domain).await;
+                        diagnose_problem(Some(&domain), url, &problem)
+                    }
+                    None => {
+                        // could not find the domain
+                        self.client
+                            .log_message(MessageType::LOG, format!("Could not find the domain of {}", url))
+                            .await;
+                        diagnose_problem(None, url, &problem)
+                    }
+                };
+                diagnostics.extend(warning);
+                diagnostics
+            }
+            FileVariant::MaybeNotHDDL => {
+                // TODO: attempt to fix this
+                self.client
+                    .log_message(
+                        MessageType::LOG,
+                        format!(
+                            "{} does not have proper HDDL header. Ignoring diagnostic request.",
+                            url
+                        ),
+                    )
+                    .await;
+                vec![]
+            }
+        }
+    }
+
+    // clients that do not pull the diagnostics get them after every change of
+    // the document, and of its domain (for problems)
+    async fn publish_diagnostics(&self, url: &Url) {
+        let Some((version, content)) = self
+            .documents
+            .read()
+            .await
+            .get(url)
+            .map(|document| (document.version, document.content().clone()))
+        else {
+            return;
+        };
+        let is_domain = matches!(classify_file(&content), FileVariant::Domain);
+        if self.pull_diagnostics.load(Ordering::Relaxed) {
+            // clients that pull are asked to pull the problems again
+            if is_domain && self.diagnostic_refresh.load(Ordering::Relaxed) {
+                let _ = self.client.workspace_diagnostic_refresh().await;
+            }
+            return;
+        }
+        let diagnostics = self.diagnose(url, &content).await;
+        self.client
+            .publish_diagnostics(url.clone(), diagnostics, Some(version))
+            .await;
+        if !is_domain {
+            return;
+        }
+        let open_problems: Vec<(Url, i32, Vec<u8>)> = self
+            .documents
+            .read()
+            .await
+            .iter()
+            .filter(|(problem_url, _)| *problem_url != url)
+            .map(|(problem_url, document)| {
+                (problem_url.clone(), document.version, document.content().clone())
+            })
+            .collect();
+        for (problem_url, version, content) in open_problems {
+            if !matches!(classify_file(&content), FileVariant::Problem) {
+                continue;
+            }
+            match self.find_domain(&problem_url).await {
+                Some((domain_url, _)) if &domain_url == url => {
+                    let diagnostics = self.diagnose(&problem_url, &content).await;
+                    self.client
+                        .publish_diagnostics(problem_url, diagnostics, Some(version))
+                        .await;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // the semantic tokens of a synced document (in the range)
+    async fn semantic_tokens(&self, url: &Url, range: Option<Range>) -> tower_lsp::jsonrpc::Result<Vec<SemanticToken>> {
+        let document = self.document(url).await?;
+        let analysis = self.analysis(url, &document).await;
+        let domain = match classify_file(&document) {
+            FileVariant::Problem => match self.find_domain(url).await {
+                Some((domain_url, domain)) => Some(self.analysis(&domain_url, &domain).await),
+                None => None,
+            },
+            _ => None,
+        };
+        let range = range.map(|range| (self.offset(&document, range.start), self.offset(&document, range.end)));
+        Ok(semantic_tokens(
+            &document,
+            analysis.ast(),
+            analysis.analysis().index.as_ref(),
+            domain.as_ref().and_then(|domain| domain.ast()),
+            range,
+            self.encoding(),
+        ))
+    }
+
+    async fn find_domain(&self, url: &Url) -> Option<(Url, Vec<u8>)> {
+        self.associate(url).await.0
+    }
+
+    // problems refer to the declarations of their domain
+    async fn declarations(
+        &self,
+        url: &Url,
+        document: &Vec<u8>,
+        index: &SymbolIndex,
+        symbol: &SymbolOccurrence,
+    ) -> Vec<Location> {
+        let locations = self.encode_locations(document, to_locations(url, index.declarations_of(symbol)));
+        if !locations.is_empty() || symbol.kind == ElementKind::Variable {
+            return locations;
+        }
+        if let FileVariant::Problem = classify_file(document) {
+            if let Some((domain_url, domain)) = self.find_domain(url).await {
+                let domain_analysis = self.analysis(&domain_url, &domain).await;
+                if let Some(domain_index) = &domain_analysis.analysis().index {
+                    let locations = to_locations(&domain_url, domain_index.declarations_of(symbol));
+                    return self.encode_locations(&domain, locations);
+                }
+            }
+        }
+        locations
+    }
+
+    // the problems next to the domain that are associated with it (i.e. not the
+    // problems of other domains in the same folder)
+    async fn problems_of(&self, domain_url: &Url) -> Vec<(Url, Vec<u8>)> {
+        let mut problems = vec![];
+        for (problem_url, problem) in self.neighbours(domain_url, FileVariant::Problem).await {
+            if self.find_domain(&problem_url).await.is_some_and(|(url, _)| &url == domain_url) {
+                problems.push((problem_url, problem));
+            }
+        }
+        problems
+    }
+
+    // the documents that can refer to the symbol: variables and the objects of a
+    // problem are local to their document, and the rest is shared by the domain
+    // and all of its problems
+    async fn related_documents(
+        &self,
+        url: &Url,
+        document: &Vec<u8>,
+        index: &SymbolIndex,
+        symbol: &SymbolOccurrence,
+    ) -> Vec<(Url, Vec<u8>)> {
+        let local = vec![(url.clone(), document.clone())];
+        if symbol.kind == ElementKind::Variable {
+            return local;
+        }
+        match classify_file(document) {
+            FileVariant::Problem if index.declarations_of(symbol).next().is_some() => local,
+            FileVariant::Problem => match self.find_domain(url).await {
+                Some((domain_url, domain)) => {
+                    let mut documents = vec![(domain_url.clone(), domain)];
+                    documents.extend(self.problems_of(&domain_url).await);
+                    documents
+                }
+                None => local,
+            },
+            _ => {
+                let mut documents = local;
+                documents.extend(self.problems_of(url).await);
+                documents
+            }
+        }
     }
 }
 
@@ -29,21 +622,98 @@ impl RequestHandler {
 impl LanguageServer for RequestHandler {
     async fn initialize(
         &self,
-        _: InitializeParams,
+        params: InitializeParams,
     ) -> tower_lsp::jsonrpc::Result<InitializeResult> {
+        if let Some(max_line_length) = params
+            .initialization_options
+            .as_ref()
+            .and_then(|options| options.get("maxLineLength"))
+            .and_then(|value| value.as_u64())
+        {
+            self.max_line_length.store(max_line_length as usize, Ordering::Relaxed);
+        }
+        let pull_diagnostics = params
+            .capabilities
+            .text_document
+            .as_ref()
+            .is_some_and(|text_document| text_document.diagnostic.is_some());
+        let diagnostic_refresh = params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.diagnostic.as_ref())
+            .and_then(|diagnostic| diagnostic.refresh_support)
+            .unwrap_or(false);
+        let encoding = PositionEncoding::negotiate(
+            params
+                .capabilities
+                .general
+                .as_ref()
+                .and_then(|general| general.position_encodings.as_deref()),
+        );
+        self.pull_diagnostics.store(pull_diagnostics, Ordering::Relaxed);
+        self.diagnostic_refresh.store(diagnostic_refresh, Ordering::Relaxed);
+        self.utf8_positions.store(encoding == PositionEncoding::UTF8, Ordering::Relaxed);
+        let mut workspace_folders: Vec<PathBuf> = params
+            .workspace_folders
+            .iter()
+            .flatten()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect();
+        if workspace_folders.is_empty() {
+            workspace_folders.extend(params.root_uri.and_then(|root| root.to_file_path().ok()));
+        }
+        *self.workspace_folders.write().await = workspace_folders;
+        *self.settings.write().await = params.initialization_options;
+        self.reload_mappings().await;
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
-                text_document_sync: Some(TextDocumentSyncCapability::Kind(
-                    TextDocumentSyncKind::FULL,
+                position_encoding: Some(encoding.kind()),
+                // the diagnostics are published again when a document is saved
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..TextDocumentSyncOptions::default()
+                    },
                 )),
                 diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
                     DiagnosticOptions {
                         identifier: Some("HDDL Server".to_string()),
                         inter_file_dependencies: true,
-                        workspace_diagnostics: false,
+                        workspace_diagnostics: true,
+                        work_done_progress_options: WorkDoneProgressOptions::default(),
+                    },
+                )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec!["(".to_string(), "-".to_string(), ":".to_string()]),
+                    ..CompletionOptions::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                })),
+                semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+                    SemanticTokensOptions {
                         work_done_progress_options: WorkDoneProgressOptions::default(),
+                        legend: legend(),
+                        range: Some(true),
+                        full: Some(SemanticTokensFullOptions::Bool(true)),
                     },
                 )),
+                code_action_provider: Some(CodeActionProviderCapability::Options(CodeActionOptions {
+                    code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                    resolve_provider: None,
+                })),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                document_range_formatting_provider: Some(OneOf::Left(true)),
                 // Add other capabilities as needed
                 ..ServerCapabilities::default()
             },
@@ -62,118 +732,398 @@ impl LanguageServer for RequestHandler {
         Ok(())
     }
 
+    async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
+        // the settings may be in the section of the server
+        let settings = match params.settings.get("hddl") {
+            Some(settings) => settings.clone(),
+            None => params.settings,
+        };
+        *self.settings.write().await = Some(settings);
+        self.reload_mappings().await;
+    }
+
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        self.sync(
-            params.text_document.uri,
+        let document = Document::new(
+            params.text_document.version,
             params.text_document.text.into_bytes(),
-        ).await;
+        );
+        let url = params.text_document.uri;
+        self.documents.write().await.insert(url.clone(), document);
+        self.publish_diagnostics(&url).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        // the file is read from the disk again
+        let url = params.text_document.uri;
+        self.documents.write().await.remove(&url);
+        if !self.pull_diagnostics.load(Ordering::Relaxed) {
+            self.client.publish_diagnostics(url, vec![], None).await;
+        }
     }
 
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
-        // get the saved file content
+        let url = params.text_document.uri;
+        // the content of an open document is already synced by its changes,
+        // so the disk is only read for documents that are not open
         let text = match params.text {
-            Some(content) => content.into_bytes(),
-            None => tokio::fs::read(params.text_document.uri.path())
-                .await
-                .unwrap(),
+            Some(content) => Some(content.into_bytes()),
+            None if self.documents.read().await.contains_key(&url) => None,
+            None => match url.to_file_path() {
+                Ok(path) => tokio::fs::read(path).await.ok(),
+                Err(_) => None,
+            },
         };
-        // sync the file
-        self.sync(params.text_document.uri, text).await;
+        if let Some(text) = text {
+            self.sync(url.clone(), text).await;
+        }
+        if url.path().ends_with(PROJECT_FILE) {
+            self.reload_mappings().await;
+        }
+        self.publish_diagnostics(&url).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
-        let uri = params.text_document.uri;
-        // TODO: add support for incremental change
-        if let Some(new_text) = params.content_changes.into_iter().next() {
-            self.sync(uri, new_text.text.into_bytes()).await
+        if let Some(document) = self.documents.write().await.get_mut(&params.text_document.uri) {
+            document.apply_changes(params.text_document.version, params.content_changes, self.encoding());
         }
+        self.publish_diagnostics(&params.text_document.uri).await;
     }
 
     async fn diagnostic(
         &self,
         params: DocumentDiagnosticParams,
     ) -> tower_lsp::jsonrpc::Result<DocumentDiagnosticReportResult> {
-        match self.documents.read().await.get(&params.text_document.uri) {
-            Some(document) => {
-                self.client
-                    .log_message(MessageType::LOG, "Diagnostic Request Recieved.")
-                    .await;
-                let file_path = params.text_document.uri.to_file_path().unwrap();
-                match classify_file(document) {
-                    FileVariant::Domain => {
-                        self.client
-                            .log_message(
-                                MessageType::LOG,
-                                format!(
-                                    "{} is a domain. Attempting to diagnose.",
-                                    params.text_document.uri.to_string()
-                                ),
-                            )
-                            .await;
-                        let diagnosis = diagnose_domain(document);
-                        return Ok(diagnosis);
-                    }
-                    FileVariant::Problem => {
-                        let root_folder = file_path.parent().unwrap();
-                        let mut files = tokio::fs::read_dir(root_folder).await.unwrap();
-                        while let Ok(Some(entry)) = files.next_entry().await {
-                            match entry.path().extension() {
-                                Some(extension) if (extension == "hddl" || extension == "pddl") => {
-                                    let content = tokio::fs::read(entry.path()).await.unwrap();
-                                    match classify_file(&content) {
-                                        FileVariant::Domain => {
-                                            self.client
-                                                .log_message(
-                                                    MessageType::LOG,
-                                                    format!(
-                                                        "{} is the domain for {}. Attempting to diagnose.",
-                                                        root_folder.to_str().unwrap(),
-                                                        params.text_document.uri.to_string()
-                                                    ),
-                                                ).await;
-                                            return Ok(diagnose_problem(Some(&content), document));
-                                        }
-                                        // File is not the domain
-                                        _ => {}
-                                    }
-                                }
-                                // File is not .PDDL or .HDDL
-                                _ => {}
-                            }
+        self.client
+            .log_message(MessageType::LOG, "Diagnostic Request Recieved.")
+            .await;
+        let document = self.document(&params.text_document.uri).await?;
+        let items = self.diagnose(&params.text_document.uri, &document).await;
+        Ok(report(items))
+    }
+
+    async fn workspace_diagnostic(
+        &self,
+        _: WorkspaceDiagnosticParams,
+    ) -> tower_lsp::jsonrpc::Result<WorkspaceDiagnosticReportResult> {
+        let mut items = vec![];
+        for (url, content) in self.workspace_documents().await {
+            let version = self.documents.read().await.get(&url).map(|document| document.version);
+            let diagnostics = self.diagnose(&url, &content).await;
+            items.push(WorkspaceDocumentDiagnosticReport::Full(
+                WorkspaceFullDocumentDiagnosticReport {
+                    uri: url,
+                    version: version.map(|version| version as i64),
+                    full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                        result_id: None,
+                        items: diagnostics,
+                    },
+                },
+            ));
+        }
+        Ok(WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items }))
+    }
+
+    async fn formatting(
+        &self,
+        params: DocumentFormattingParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<Vec<TextEdit>>> {
+        let documents = self.documents.read().await;
+        let document = documents.get(&params.text_document.uri).ok_or_else(|| {
+            tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "{} is not synced",
+                params.text_document.uri
+            ))
+        })?;
+        let max_line_length = self.max_line_length.load(Ordering::Relaxed);
+        let options = to_format_options(&params.options, max_line_length);
+        let content = document.content();
+        Ok(format_document(content, options).map(|edits| self.encode_edits(content, edits)))
+    }
+
+    async fn range_formatting(
+        &self,
+        params: DocumentRangeFormattingParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<Vec<TextEdit>>> {
+        let documents = self.documents.read().await;
+        let document = documents.get(&params.text_document.uri).ok_or_else(|| {
+            tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "{} is not synced",
+                params.text_document.uri
+            ))
+        })?;
+        let max_line_length = self.max_line_length.load(Ordering::Relaxed);
+        let options = to_format_options(&params.options, max_line_length);
+        let content = document.content();
+        let range = self.encoding().decode_range(content, params.range);
+        Ok(format_range(content, &range, options).map(|edits| self.encode_edits(content, edits)))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<GotoDefinitionResponse>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        let Some(index) = &analysis.analysis().index else {
+            return Ok(None);
+        };
+        let Some(occurrence) = index.find(self.offset(&document, position)) else {
+            return Ok(None);
+        };
+        let locations = self.declarations(&url, &document, index, occurrence).await;
+        if locations.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(GotoDefinitionResponse::Array(locations)))
+    }
+
+    async fn references(
+        &self,
+        params: ReferenceParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<Vec<Location>>> {
+        let url = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        let Some(index) = &analysis.analysis().index else {
+            return Ok(None);
+        };
+        let Some(occurrence) = index.find(self.offset(&document, position)) else {
+            return Ok(None);
+        };
+        let documents = self.related_documents(&url, &document, index, occurrence).await;
+        let mut locations = vec![];
+        for (document_url, content) in documents.iter() {
+            let document_analysis = self.analysis(document_url, content).await;
+            let Some(document_index) = &document_analysis.analysis().index else {
+                continue;
+            };
+            let occurrences = document_index
+                .occurrences_of(occurrence)
+                .filter(|other| include_declaration || !other.is_declaration);
+            locations.extend(self.encode_locations(content, to_locations(document_url, occurrences)));
+        }
+        Ok(Some(locations))
+    }
+
+    async fn hover(&self, params: HoverParams) -> tower_lsp::jsonrpc::Result<Option<Hover>> {
+        let url = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        let Some(index) = &analysis.analysis().index else {
+            return Ok(None);
+        };
+        let Some(occurrence) = index.find(self.offset(&document, position)) else {
+            return Ok(None);
+        };
+        let description = match classify_file(&document) {
+            FileVariant::Problem => {
+                let object = match occurrence.kind {
+                    ElementKind::Object => describe_problem_object(&document, &occurrence.name),
+                    _ => None,
+                };
+                match object {
+                    Some(description) => Some(description),
+                    // the rest is declared in the domain
+                    None => match self.find_domain(&url).await {
+                        Some((_, domain)) => {
+                            describe_domain_symbol(&domain, occurrence.kind, &occurrence.name)
                         }
-                        // could not find the domain
-                        self.client
-                            .log_message(
-                                MessageType::LOG,
-                                format!(
-                                    "Could not find the domain in {}",
-                                    root_folder.to_str().unwrap()
-                                ),
-                            )
-                            .await;
-                        return Ok(diagnose_problem(None, document));
-                    }
-                    FileVariant::MaybeNotHDDL => {
-                        // TODO: attempt to fix this
-                        self.client
-                            .log_message(
-                                MessageType::LOG,
-                                format!(
-                                    "{} does not have proper HDDL header. Ignoring diagnostic request.",
-                                    params.text_document.uri.to_string()
-                                ),
-                            )
-                            .await;
-                        Ok(generate_empty_report())
-                    }
+                        None => None,
+                    },
                 }
             }
-            None => {
+            _ => describe_domain_symbol(&document, occurrence.kind, &occurrence.name),
+        };
+        Ok(description.map(|value| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(self.encoding().encode_range(&document, to_range(&occurrence.position))),
+        }))
+    }
+
+    async fn completion(
+        &self,
+        params: CompletionParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<CompletionResponse>> {
+        let url = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let document = self.document(&url).await?;
+        let offset = self.offset(&document, position);
+        let domain = match classify_file(&document) {
+            FileVariant::Problem => self.find_domain(&url).await.map(|(_, domain)| domain),
+            _ => Some(document.clone()),
+        };
+        let items = complete(&document, offset, domain.as_ref());
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<PrepareRenameResponse>> {
+        let url = params.text_document.uri;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        let Some(index) = &analysis.analysis().index else {
+            return Ok(None);
+        };
+        let Some(occurrence) = index.find(self.offset(&document, params.position)) else {
+            return Ok(None);
+        };
+        // built-in (e.g. the "object" type) and undefined symbols are not renamed
+        if self.declarations(&url, &document, index, occurrence).await.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(PrepareRenameResponse::RangeWithPlaceholder {
+            range: self.encoding().encode_range(&document, to_range(&occurrence.position)),
+            placeholder: occurrence.name.clone(),
+        }))
+    }
+
+    async fn rename(&self, params: RenameParams) -> tower_lsp::jsonrpc::Result<Option<WorkspaceEdit>> {
+        let url = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        let Some(index) = &analysis.analysis().index else {
+            return Ok(None);
+        };
+        let Some(occurrence) = index.find(self.offset(&document, position)) else {
+            return Ok(None);
+        };
+        if self.declarations(&url, &document, index, occurrence).await.is_empty() {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                "{} is not declared",
+                occurrence.name
+            )));
+        }
+        let new_name = validate_name(&params.new_name)
+            .map_err(tower_lsp::jsonrpc::Error::invalid_params)?;
+        let mut changes = HashMap::new();
+        for (document_url, content) in self.related_documents(&url, &document, index, occurrence).await {
+            let document_analysis = self.analysis(&document_url, &content).await;
+            let Some(document_index) = &document_analysis.analysis().index else {
+                continue;
+            };
+            if is_declared(document_index, occurrence, &new_name) {
                 return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
-                    "{} is not synced",
-                    params.text_document.uri
+                    "{} is already declared",
+                    new_name
                 )));
             }
+            let edits = self.encode_edits(&content, rename_edits(document_index, occurrence, &new_name));
+            if !edits.is_empty() {
+                changes.insert(document_url, edits);
+            }
+        }
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    async fn semantic_tokens_full(
+        &self,
+        params: SemanticTokensParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<SemanticTokensResult>> {
+        let data = self.semantic_tokens(&params.text_document.uri, None).await?;
+        Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+
+    async fn semantic_tokens_range(
+        &self,
+        params: SemanticTokensRangeParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<SemanticTokensRangeResult>> {
+        let data = self.semantic_tokens(&params.text_document.uri, Some(params.range)).await?;
+        Ok(Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+            result_id: None,
+            data,
+        })))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> tower_lsp::jsonrpc::Result<Option<CodeActionResponse>> {
+        if let Some(only) = &params.context.only {
+            if !only.iter().any(|kind| CodeActionKind::QUICKFIX.as_str().starts_with(kind.as_str())) {
+                return Ok(None);
+            }
+        }
+        let url = params.text_document.uri;
+        let document = self.document(&url).await?;
+        let analysis = self.analysis(&url, &document).await;
+        // declarations are added to the domain of a problem
+        let domain = match classify_file(&document) {
+            FileVariant::Problem => match self.find_domain(&url).await {
+                Some((domain_url, domain)) => {
+                    let domain_analysis = self.analysis(&domain_url, &domain).await;
+                    Some((domain_url, domain_analysis))
+                }
+                None => None,
+            },
+            _ => None,
+        };
+        let domain = domain.as_ref().map(|(domain_url, domain)| (domain_url, domain.as_ref()));
+        let encoding = self.encoding();
+        let diagnostics: Vec<Diagnostic> = params
+            .context
+            .diagnostics
+            .into_iter()
+            .map(|mut diagnostic| {
+                diagnostic.range = encoding.decode_range(&document, diagnostic.range);
+                diagnostic
+            })
+            .collect();
+        let mut actions = code_actions(&url, &analysis, domain, &diagnostics);
+        // the edits are in the document or in its domain
+        for action in actions.iter_mut() {
+            let CodeActionOrCommand::CodeAction(action) = action else {
+                continue;
+            };
+            for diagnostic in action.diagnostics.iter_mut().flatten() {
+                diagnostic.range = encoding.encode_range(&document, diagnostic.range);
+            }
+            for (edit_url, edits) in action.edit.iter_mut().flat_map(|edit| edit.changes.iter_mut().flatten()) {
+                let content = match domain {
+                    Some((domain_url, domain)) if domain_url == edit_url => domain.content(),
+                    _ => &document,
+                };
+                *edits = self.encode_edits(content, std::mem::take(edits));
+            }
+        }
+        Ok(Some(actions))
+    }
+
+    async fn document_symbol(
+        &self,
+        params: DocumentSymbolParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<DocumentSymbolResponse>> {
+        let document = self.document(&params.text_document.uri).await?;
+        let symbols = document_symbols(&document).map(|mut symbols| {
+            self.encode_symbols(&document, &mut symbols);
+            symbols
+        });
+        Ok(symbols.map(DocumentSymbolResponse::Nested))
+    }
+
+    async fn symbol(
+        &self,
+        params: WorkspaceSymbolParams,
+    ) -> tower_lsp::jsonrpc::Result<Option<Vec<SymbolInformation>>> {
+        let mut symbols = vec![];
+        for (url, content) in self.workspace_documents().await {
+            let encoding = self.encoding();
+            symbols.extend(workspace_symbols(&url, &content, &params.query).into_iter().map(|mut symbol| {
+                symbol.location.range = encoding.encode_range(&content, symbol.location.range);
+                symbol
+            }));
         }
+        symbols.sort_by_key(|symbol| fuzzy_score(&params.query, &symbol.name));
+        Ok(Some(symbols))
     }
 }
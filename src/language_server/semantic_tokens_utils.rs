@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::language_server::position_encoding::PositionEncoding;
+use crate::language_server::symbol_index::{ElementKind, SymbolIndex, SymbolOccurrence};
+use crate::lexical_analyzer::{LexicalAnalyzer, Token};
+use crate::syntactic_analyzer::*;
+
+use tower_lsp::lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+
+// the order of the legend (i.e. a token type is encoded as its index)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightKind {
+    Keyword,
+    Requirement,
+    Type,
+    Predicate,
+    PrimitiveTask,
+    CompoundTask,
+    Method,
+    Variable,
+    Constant,
+    Object,
+}
+
+const TOKEN_TYPES: [SemanticTokenType; 10] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::new("requirement"),
+    SemanticTokenType::TYPE,
+    SemanticTokenType::new("predicate"),
+    SemanticTokenType::new("primitiveTask"),
+    SemanticTokenType::new("compoundTask"),
+    SemanticTokenType::METHOD,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::new("constant"),
+    SemanticTokenType::new("object"),
+];
+
+const DECLARATION: u32 = 1;
+const UNDEFINED: u32 = 1 << 1;
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: vec![SemanticTokenModifier::DECLARATION, SemanticTokenModifier::new("undefined")],
+    }
+}
+
+// the symbols that a document can refer to
+struct Definitions {
+    types: HashSet<String>,
+    predicates: HashSet<String>,
+    primitive_tasks: HashSet<String>,
+    compound_tasks: HashSet<String>,
+    constants: HashSet<String>,
+    objects: HashSet<String>,
+}
+
+impl Definitions {
+    fn new(domain: &DomainAST, problem: Option<&ProblemAST>) -> Definitions {
+        let names = |symbols: Option<&Vec<Symbol>>| {
+            symbols
+                .into_iter()
+                .flatten()
+                .map(|symbol| symbol.name.to_string())
+                .collect::<HashSet<String>>()
+        };
+        let mut types = names(domain.types.as_ref());
+        types.insert("object".to_string());
+        Definitions {
+            types,
+            predicates: domain.predicates.iter().map(|predicate| predicate.name.to_string()).collect(),
+            primitive_tasks: domain.actions.iter().map(|action| action.name.to_string()).collect(),
+            compound_tasks: domain.compound_tasks.iter().map(|task| task.name.to_string()).collect(),
+            constants: names(domain.constants.as_ref()),
+            objects: names(problem.map(|problem| &problem.objects)),
+        }
+    }
+}
+
+// the tokens (in the order of the document) that start in the byte range; the
+// keywords and requirements come from the lexer, and the symbols are classified
+// with the index and the declarations of the domain (symbols of problems
+// without a domain are never undefined)
+pub fn semantic_tokens(
+    content: &Vec<u8>,
+    ast: Option<&AbstractSyntaxTree>,
+    index: Option<&SymbolIndex>,
+    domain: Option<&AbstractSyntaxTree>,
+    range: Option<(usize, usize)>,
+    encoding: PositionEncoding,
+) -> Vec<SemanticToken> {
+    let definitions = match (ast, domain) {
+        (Some(AbstractSyntaxTree::Domain(domain)), _) => Some(Definitions::new(domain, None)),
+        (Some(AbstractSyntaxTree::Problem(problem)), Some(AbstractSyntaxTree::Domain(domain))) => {
+            Some(Definitions::new(domain, Some(problem)))
+        }
+        _ => None,
+    };
+    let is_problem = matches!(ast, Some(AbstractSyntaxTree::Problem(_)));
+    let occurrences: HashMap<usize, &SymbolOccurrence> = index
+        .map(|index| {
+            index
+                .occurrences
+                .iter()
+                .map(|occurrence| (occurrence.position.offset, occurrence))
+                .collect()
+        })
+        .unwrap_or_default();
+    let lexer = LexicalAnalyzer::new(content);
+    let mut tokens = vec![];
+    let mut last_error = None;
+    loop {
+        let token = lexer.get_token();
+        let position = lexer.get_last_token_position();
+        let classified = match token {
+            Ok(Token::EOF) => break,
+            Ok(Token::Keyword(_)) | Ok(Token::Operator(_)) => Some((HighlightKind::Keyword, 0)),
+            Ok(Token::Requirement(_)) => Some((HighlightKind::Requirement, 0)),
+            Ok(Token::Identifier(_)) => index.zip(occurrences.get(&position.offset)).map(|(index, occurrence)| {
+                classify(occurrence, index, definitions.as_ref(), is_problem)
+            }),
+            Ok(Token::Punctuator(_)) | Ok(Token::Number(_)) => None,
+            // the lexer skips invalid lexemes (unless it is stuck on one)
+            Err(_) if last_error == Some(position.offset) => break,
+            Err(_) => {
+                last_error = Some(position.offset);
+                None
+            }
+        };
+        let in_range = range.is_none_or(|(start, end)| start <= position.offset && position.offset < end);
+        if let (Some(classified), true) = (classified, in_range) {
+            tokens.push((position, classified));
+        }
+    }
+    // the positions are encoded relative to the previous token
+    let mut previous = (0, 0);
+    tokens
+        .into_iter()
+        .map(|(position, (kind, modifiers))| {
+            let line = position.line.saturating_sub(1);
+            let line_start = position.offset - position.column.saturating_sub(1) as usize;
+            let start = encoding.columns(&content[line_start..position.offset]);
+            let token = SemanticToken {
+                delta_line: line - previous.0,
+                delta_start: if line == previous.0 { start - previous.1 } else { start },
+                length: encoding.columns(&content[position.offset..position.end_offset]),
+                token_type: kind as u32,
+                token_modifiers_bitset: modifiers,
+            };
+            previous = (line, start);
+            token
+        })
+        .collect()
+}
+
+fn classify(
+    occurrence: &SymbolOccurrence,
+    index: &SymbolIndex,
+    definitions: Option<&Definitions>,
+    is_problem: bool,
+) -> (HighlightKind, u32) {
+    let declaration = if occurrence.is_declaration { DECLARATION } else { 0 };
+    let defined = |names: fn(&Definitions) -> &HashSet<String>| {
+        definitions.is_none_or(|definitions| names(definitions).contains(&occurrence.name))
+    };
+    let (kind, is_defined) = match occurrence.kind {
+        ElementKind::Type => (HighlightKind::Type, defined(|definitions| &definitions.types)),
+        ElementKind::Predicate => (HighlightKind::Predicate, defined(|definitions| &definitions.predicates)),
+        ElementKind::Method => (HighlightKind::Method, true),
+        ElementKind::Variable => (
+            HighlightKind::Variable,
+            index.declarations_of(occurrence).next().is_some(),
+        ),
+        ElementKind::Task => match definitions {
+            Some(definitions) if definitions.primitive_tasks.contains(&occurrence.name) => {
+                (HighlightKind::PrimitiveTask, true)
+            }
+            Some(definitions) if definitions.compound_tasks.contains(&occurrence.name) => {
+                (HighlightKind::CompoundTask, true)
+            }
+            Some(_) => (HighlightKind::CompoundTask, false),
+            // the tasks of initial task networks are usually compound
+            None => (HighlightKind::CompoundTask, true),
+        },
+        ElementKind::Object => match definitions {
+            Some(definitions) if definitions.constants.contains(&occurrence.name) => (HighlightKind::Constant, true),
+            Some(definitions) if definitions.objects.contains(&occurrence.name) => (HighlightKind::Object, true),
+            Some(_) if is_problem => (HighlightKind::Object, false),
+            Some(_) => (HighlightKind::Constant, false),
+            None => (HighlightKind::Object, true),
+        },
+    };
+    let undefined = if is_defined { 0 } else { UNDEFINED };
+    (kind, declaration | undefined)
+}
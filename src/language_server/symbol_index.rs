@@ -0,0 +1,312 @@
+use crate::language_server::diagnostic_utils::to_range;
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::syntactic_analyzer::*;
+use crate::TokenPosition;
+
+use tower_lsp::lsp_types::{Location, Url};
+
+// actions and compound tasks share a namespace (both can be subtasks),
+// and so do constants and objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    Task,
+    Method,
+    Predicate,
+    Type,
+    Object,
+    Variable,
+}
+
+#[derive(Debug, Clone)]
+pub struct SymbolOccurrence {
+    pub kind: ElementKind,
+    pub name: String,
+    // the position of variables includes their '?'
+    pub position: TokenPosition,
+    pub is_declaration: bool,
+    // variables are local to their block (or quantifier), and the
+    // rest of the symbols are global (i.e. in scope 0)
+    pub scope: usize,
+}
+
+impl SymbolOccurrence {
+    pub fn is_same_symbol(&self, other: &SymbolOccurrence) -> bool {
+        self.kind == other.kind && self.name == other.name && self.scope == other.scope
+    }
+}
+
+// every named element of a document that refers to (or declares) a domain or problem symbol
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    pub occurrences: Vec<SymbolOccurrence>,
+}
+
+// documents with syntax errors are indexed as far as they could be parsed
+pub fn index_document(content: &Vec<u8>) -> Option<SymbolIndex> {
+    let lexer = LexicalAnalyzer::new(content);
+    let parser = Parser::new(lexer);
+    let (ast, _) = parser.parse_partial();
+    ast.as_ref().map(SymbolIndex::from_ast)
+}
+
+pub fn to_locations<'b>(
+    url: &Url,
+    occurrences: impl Iterator<Item = &'b SymbolOccurrence>,
+) -> Vec<Location> {
+    occurrences
+        .map(|occurrence| Location::new(url.clone(), to_range(&occurrence.position)))
+        .collect()
+}
+
+impl SymbolIndex {
+    pub fn from_ast(ast: &AbstractSyntaxTree) -> SymbolIndex {
+        match ast {
+            AbstractSyntaxTree::Domain(domain) => SymbolIndex::from_domain(domain),
+            AbstractSyntaxTree::Problem(problem) => SymbolIndex::from_problem(problem),
+        }
+    }
+
+    pub fn from_domain(domain: &DomainAST) -> SymbolIndex {
+        let mut indexer = Indexer::default();
+        if let Some(types) = &domain.types {
+            indexer.declarations(ElementKind::Type, types);
+        }
+        if let Some(constants) = &domain.constants {
+            indexer.declarations(ElementKind::Object, constants);
+        }
+        for predicate in domain.predicates.iter() {
+            indexer.add(ElementKind::Predicate, predicate.name, &predicate.name_pos, true);
+            indexer.enter_scope(&predicate.variables);
+            indexer.exit_scope();
+        }
+        for task in domain.compound_tasks.iter() {
+            indexer.add(ElementKind::Task, task.name, &task.name_pos, true);
+            indexer.enter_scope(&task.parameters);
+            indexer.exit_scope();
+        }
+        for method in domain.methods.iter() {
+            indexer.add(ElementKind::Method, method.name.name, &method.name.name_pos, true);
+            indexer.enter_scope(&method.params);
+            indexer.add(ElementKind::Task, method.task.name, &method.task.name_pos, false);
+            indexer.terms(&method.task_terms);
+            if let Some(precondition) = &method.precondition {
+                indexer.formula(precondition);
+            }
+            indexer.network(&method.tn);
+            indexer.exit_scope();
+        }
+        for action in domain.actions.iter() {
+            indexer.add(ElementKind::Task, action.name, &action.name_pos, true);
+            indexer.enter_scope(&action.parameters);
+            if let Some(precondition) = &action.preconditions {
+                indexer.formula(precondition);
+            }
+            if let Some(effect) = &action.effects {
+                indexer.effect(effect);
+            }
+            indexer.exit_scope();
+        }
+        indexer.index
+    }
+
+    pub fn from_problem(problem: &ProblemAST) -> SymbolIndex {
+        let mut indexer = Indexer::default();
+        indexer.declarations(ElementKind::Object, &problem.objects);
+        if let Some(init_tn) = &problem.init_tn {
+            indexer.enter_scope(init_tn.parameters.as_ref().unwrap_or(&vec![]));
+            indexer.network(&init_tn.tn);
+            indexer.exit_scope();
+        }
+        for fact in problem.init_state.iter() {
+            indexer.atom(fact);
+        }
+        for value in problem.init_values.iter() {
+            indexer.terms(&value.function.variables);
+        }
+        if let Some(goal) = &problem.goal {
+            indexer.formula(goal);
+        }
+        indexer.index
+    }
+
+    // the occurrence whose name contains the byte offset
+    pub fn find(&self, offset: usize) -> Option<&SymbolOccurrence> {
+        self.occurrences.iter().find(|occurrence| {
+            occurrence.position.offset <= offset && offset <= occurrence.position.end_offset
+        })
+    }
+
+    pub fn occurrences_of<'b>(
+        &'b self,
+        symbol: &'b SymbolOccurrence,
+    ) -> impl Iterator<Item = &'b SymbolOccurrence> + 'b {
+        self.occurrences
+            .iter()
+            .filter(move |occurrence| occurrence.is_same_symbol(symbol))
+    }
+
+    pub fn declarations_of<'b>(
+        &'b self,
+        symbol: &'b SymbolOccurrence,
+    ) -> impl Iterator<Item = &'b SymbolOccurrence> + 'b {
+        self.occurrences_of(symbol)
+            .filter(|occurrence| occurrence.is_declaration)
+    }
+}
+
+#[derive(Default)]
+struct Indexer {
+    index: SymbolIndex,
+    // the variables that are in scope (innermost last)
+    scopes: Vec<(usize, Vec<String>)>,
+    scope_count: usize,
+}
+
+impl Indexer {
+    fn add(&mut self, kind: ElementKind, name: &str, position: &TokenPosition, is_declaration: bool) {
+        self.add_scoped(kind, name, position, is_declaration, 0);
+    }
+
+    fn add_scoped(
+        &mut self,
+        kind: ElementKind,
+        name: &str,
+        position: &TokenPosition,
+        is_declaration: bool,
+        scope: usize,
+    ) {
+        self.index.occurrences.push(SymbolOccurrence {
+            kind,
+            name: name.to_string(),
+            position: *position,
+            is_declaration,
+            scope,
+        });
+    }
+
+    fn declarations(&mut self, kind: ElementKind, symbols: &Vec<Symbol>) {
+        for symbol in symbols.iter() {
+            self.add(kind, symbol.name, &symbol.name_pos, true);
+        }
+        self.types(symbols);
+    }
+
+    // declares the parameters in a new scope
+    fn enter_scope(&mut self, parameters: &Vec<Symbol>) {
+        self.scope_count += 1;
+        let scope = self.scope_count;
+        for parameter in parameters.iter() {
+            self.add_scoped(ElementKind::Variable, parameter.name, &parameter.name_pos, true, scope);
+        }
+        self.types(parameters);
+        let names = parameters.iter().map(|parameter| parameter.name.to_string()).collect();
+        self.scopes.push((scope, names));
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn types(&mut self, symbols: &Vec<Symbol>) {
+        for symbol in symbols.iter() {
+            if let (Some(symbol_type), Some(type_pos)) = (symbol.symbol_type, &symbol.type_pos) {
+                self.add(ElementKind::Type, symbol_type, type_pos, false);
+            }
+        }
+    }
+
+    fn terms(&mut self, terms: &Vec<Symbol>) {
+        for term in terms.iter() {
+            self.term(term);
+        }
+    }
+
+    // terms that are not variables refer to constants (or objects)
+    fn term(&mut self, term: &Symbol) {
+        let lexeme_length = term.name_pos.end_offset - term.name_pos.offset;
+        if lexeme_length != term.name.len() + 1 {
+            self.add(ElementKind::Object, term.name, &term.name_pos, false);
+            return;
+        }
+        // undeclared variables belong to the innermost scope
+        let scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|(_, names)| names.iter().any(|name| name == term.name))
+            .or(self.scopes.last())
+            .map_or(0, |(scope, _)| *scope);
+        self.add_scoped(ElementKind::Variable, term.name, &term.name_pos, false, scope);
+    }
+
+    fn atom(&mut self, predicate: &Predicate) {
+        self.add(ElementKind::Predicate, predicate.name, &predicate.name_pos, false);
+        self.terms(&predicate.variables);
+    }
+
+    fn network(&mut self, tn: &HTN) {
+        for subtask in tn.subtasks.iter() {
+            self.add(ElementKind::Task, subtask.task.name, &subtask.task.name_pos, false);
+            self.terms(&subtask.terms);
+        }
+    }
+
+    fn formula(&mut self, formula: &Formula) {
+        match formula {
+            Formula::Empty => {}
+            Formula::Atom(predicate) => self.atom(predicate),
+            Formula::Equals(lhs, rhs) => {
+                self.term(lhs);
+                self.term(rhs);
+            }
+            Formula::Not(formula) => self.formula(formula),
+            Formula::And(formulas) | Formula::Or(formulas) | Formula::Xor(formulas) => {
+                for formula in formulas.iter() {
+                    self.formula(formula);
+                }
+            }
+            Formula::Imply(antecedents, consequents) => {
+                for formula in antecedents.iter().chain(consequents.iter()) {
+                    self.formula(formula);
+                }
+            }
+            Formula::Exists(variables, formula) | Formula::ForAll(variables, formula) => {
+                self.enter_scope(variables);
+                self.formula(formula);
+                self.exit_scope();
+            }
+            // only the arguments of functions refer to symbols
+            Formula::Comparison(_, _, _) => {
+                for function in formula.get_functions() {
+                    self.terms(&function.variables);
+                }
+            }
+        }
+    }
+
+    fn effect(&mut self, effect: &Effect) {
+        match effect {
+            Effect::Empty => {}
+            Effect::Add(predicate) | Effect::Delete(predicate) => self.atom(predicate),
+            Effect::And(effects) | Effect::OneOf(effects) => {
+                for effect in effects.iter() {
+                    self.effect(effect);
+                }
+            }
+            Effect::ForAll(variables, effect) => {
+                self.enter_scope(variables);
+                self.effect(effect);
+                self.exit_scope();
+            }
+            Effect::When(condition, effect) => {
+                self.formula(condition);
+                self.effect(effect);
+            }
+            Effect::Numeric(_, _, _) => {
+                for function in effect.get_functions() {
+                    self.terms(&function.variables);
+                }
+            }
+        }
+    }
+}
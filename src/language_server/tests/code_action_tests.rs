@@ -0,0 +1,207 @@
+use super::*;
+use crate::lexical_analyzer::{LexicalAnalyzer, TokenPosition};
+use crate::semantic_analyzer::missing_requirements;
+use crate::syntactic_analyzer::{AbstractSyntaxTree, Parser};
+use tower_lsp::lsp_types::{CodeActionOrCommand, Diagnostic, NumberOrString, TextEdit, Url};
+
+fn code(diagnostic: &Diagnostic) -> Option<&str> {
+    match &diagnostic.code {
+        Some(NumberOrString::String(code)) => Some(code.as_str()),
+        _ => None,
+    }
+}
+
+// the titles of the quick fixes, and the documents after applying each of them
+fn fixes(url: &Url, document: &AnalyzedDocument, domain: Option<(&Url, &AnalyzedDocument)>, diagnostics: &[Diagnostic]) -> Vec<(String, String)> {
+    code_actions(url, document, domain, diagnostics)
+        .into_iter()
+        .map(|action| {
+            let CodeActionOrCommand::CodeAction(action) = action else {
+                panic!("expected a code action");
+            };
+            let changes = action.edit.unwrap().changes.unwrap();
+            assert_eq!(changes.len(), 1);
+            let (edited_url, edits) = changes.into_iter().next().unwrap();
+            let content = match Some(&edited_url) == domain.map(|(domain_url, _)| domain_url) {
+                true => domain.unwrap().1.content(),
+                false => document.content(),
+            };
+            (action.title, apply(content, edits))
+        })
+        .collect()
+}
+
+fn apply(content: &[u8], mut edits: Vec<TextEdit>) -> String {
+    let mut content = content.to_vec();
+    edits.sort_by_key(|edit| std::cmp::Reverse(to_offset(&content, &edit.range.start)));
+    for edit in edits {
+        let start = to_offset(&content, &edit.range.start);
+        let end = to_offset(&content, &edit.range.end);
+        content.splice(start..end, edit.new_text.into_bytes());
+    }
+    String::from_utf8(content).unwrap()
+}
+
+#[test]
+pub fn missing_requirement_test() {
+    let domain = "(define (domain d)
+  (:requirements :hierarchy)
+  (:types location)
+  (:predicates (at ?l - location))
+  (:action move
+    :parameters (?l - location)
+    :precondition (not (at ?l))
+    :effect (at ?l)))";
+    let url = Url::parse("file:///d.hddl").unwrap();
+    let document = AnalyzedDocument::analyze(&url, domain.as_bytes().to_vec());
+    let diagnostics: Vec<Diagnostic> = document
+        .analysis()
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| code(diagnostic) == Some("MissingRequirement"))
+        .cloned()
+        .collect();
+    let messages: Vec<&str> = diagnostics.iter().map(|diagnostic| diagnostic.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        vec![
+            "requirement :typing is used but not declared",
+            "requirement :negative-preconditions is used but not declared",
+        ]
+    );
+    let fixed = fixes(&url, &document, None, &diagnostics);
+    assert_eq!(fixed[0].0, "Add requirement :typing");
+    assert!(fixed[0].1.contains("(:requirements :hierarchy :typing)"));
+    assert!(fixed[1].1.contains("(:requirements :hierarchy :negative-preconditions)"));
+    // the block is added when there is none
+    let document = AnalyzedDocument::analyze(&url, "(define (domain d)\n  (:types location))".as_bytes().to_vec());
+    let fixed = fixes(&url, &document, None, &document.analysis().diagnostics);
+    assert_eq!(fixed[0].1, "(define (domain d)\n  (:requirements :typing)\n  (:types location))");
+}
+
+#[test]
+pub fn missing_requirements_test() {
+    let domain = "(define (domain d)
+  (:requirements :typing :equality)
+  (:types location)
+  (:predicates (at ?l - location))
+  (:task visit :parameters (?l - location))
+  (:method m
+    :parameters (?l - location)
+    :task (visit ?l)
+    :precondition (forall (?k - location) (not (= ?k ?l)))
+    :ordered-subtasks (and)))";
+    let domain = domain.as_bytes().to_vec();
+    let lexer = LexicalAnalyzer::new(&domain);
+    let parser = Parser::new(lexer);
+    let Some(AbstractSyntaxTree::Domain(domain)) = parser.parse().ok() else {
+        panic!("expected a domain");
+    };
+    let missing: Vec<(String, TokenPosition)> = missing_requirements(&domain)
+        .into_iter()
+        .map(|(requirement, position)| (requirement.to_string(), position))
+        .collect();
+    let names: Vec<&str> = missing.iter().map(|(requirement, _)| requirement.as_str()).collect();
+    // a negated equality does not need negative preconditions
+    assert_eq!(names, vec![":method-preconditions", ":hierarchy", ":universal-preconditions"]);
+    assert_eq!(missing[1].1.line, 5);
+    assert_eq!(missing[2].1.line, 9);
+}
+
+#[test]
+pub fn missing_conditional_effects_test() {
+    let domain = "(define (domain d)
+  (:predicates (on ?r) (lit ?r))
+  (:action switch
+    :parameters (?r)
+    :effect (and
+      (not (on ?r))
+      (when (on ?r) (lit ?r)))))";
+    let domain = domain.as_bytes().to_vec();
+    let lexer = LexicalAnalyzer::new(&domain);
+    let parser = Parser::new(lexer);
+    let Some(AbstractSyntaxTree::Domain(domain)) = parser.parse().ok() else {
+        panic!("expected a domain");
+    };
+    let missing = missing_requirements(&domain);
+    // delete effects do not need negative preconditions
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].0.to_string(), ":conditional-effects");
+    assert_eq!(missing[0].1.line, 7);
+}
+
+#[test]
+pub fn unused_requirement_test() {
+    let domain = "(define (domain d)
+  (:requirements :typing :equality)
+  (:types location)
+  (:predicates (at ?l - location))
+  (:action move
+    :parameters (?l - location)
+    :effect (at ?l)))";
+    let url = Url::parse("file:///d.hddl").unwrap();
+    let document = AnalyzedDocument::analyze(&url, domain.as_bytes().to_vec());
+    let diagnostics: Vec<Diagnostic> = document
+        .analysis()
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| code(diagnostic) == Some("UnusedRequirement"))
+        .cloned()
+        .collect();
+    assert_eq!(diagnostics.len(), 1);
+    let fixed = fixes(&url, &document, None, &diagnostics);
+    assert_eq!(fixed[0].0, "Remove unused requirement :equality");
+    assert!(fixed[0].1.contains("(:requirements :typing)"));
+}
+
+#[test]
+pub fn undefined_domain_symbol_test() {
+    let domain = "(define (domain d)
+  (:requirements :typing :hierarchy :typing)
+  (:types location)
+  (:predicates
+    (at ?l - location)
+  )
+  (:action move
+    :parameters (?from ?to - location ?v - vehicle)
+    :precondition (and (at ?from) (connected ?from ?to))
+    :effect (and (at ?to ?from))))";
+    let url = Url::parse("file:///d.hddl").unwrap();
+    let document = AnalyzedDocument::analyze(&url, domain.as_bytes().to_vec());
+    let fixed = fixes(&url, &document, None, &document.analysis().diagnostics);
+    let titles: Vec<&str> = fixed.iter().map(|(title, _)| title.as_str()).collect();
+    assert!(titles.contains(&"Remove duplicate requirement :typing"));
+    assert!(titles.contains(&"Add type vehicle to :types"));
+    assert!(titles.contains(&"Declare predicate (connected ?from - location ?to - location)"));
+    assert!(titles.contains(&"Change to (at ?to)"));
+    let fixed_content = |title: &str| fixed.iter().find(|(fix, _)| fix == title).unwrap().1.clone();
+    assert!(fixed_content("Remove duplicate requirement :typing").contains("(:requirements :typing :hierarchy)"));
+    assert!(fixed_content("Add type vehicle to :types").contains("(:types location vehicle)"));
+    assert!(fixed_content("Declare predicate (connected ?from - location ?to - location)")
+        .contains("    (at ?l - location)\n    (connected ?from - location ?to - location)\n  )"));
+    assert!(fixed_content("Change to (at ?to)").contains(":effect (and (at ?to))"));
+}
+
+#[test]
+pub fn undefined_problem_symbol_test() {
+    let domain = "(define (domain d)
+  (:requirements :typing)
+  (:types location)
+  (:predicates (at ?l - location) (connected ?a ?b - location))
+  (:action noop :parameters ()))";
+    let problem = "(define (problem p) (:domain d)
+  (:objects home - location)
+  (:init (at home) (connected home work) (at home work)))";
+    let domain_url = Url::parse("file:///d.hddl").unwrap();
+    let problem_url = Url::parse("file:///p.hddl").unwrap();
+    let domain = AnalyzedDocument::analyze(&domain_url, domain.as_bytes().to_vec());
+    let problem = AnalyzedDocument::analyze(&problem_url, problem.as_bytes().to_vec());
+    let diagnostics = diagnose_problem(Some(&domain), &problem_url, &problem);
+    let fixed = fixes(&problem_url, &problem, Some((&domain_url, &domain)), &diagnostics);
+    assert_eq!(fixed.len(), 2);
+    // the type of the object is the one of the parameter it is passed to
+    assert_eq!(fixed[0].0, "Add work - location to :objects");
+    assert!(fixed[0].1.contains("(:objects home - location work - location)"));
+    assert_eq!(fixed[1].0, "Change to (at home)");
+    assert!(fixed[1].1.contains("(connected home work) (at home))"));
+}
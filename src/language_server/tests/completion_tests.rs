@@ -0,0 +1,93 @@
+use super::*;
+use tower_lsp::lsp_types::CompletionItem;
+
+const DOMAIN: &str = "(define (domain d)
+  (:requirements :typing :hierarchy)
+  (:types truck - vehicle vehicle location - object)
+  (:constants depot - location)
+  (:predicates (at ?v - vehicle ?l - location) (ready))
+  (:task deliver :parameters (?v - vehicle))
+  (:method m-deliver :parameters (?v - vehicle ?l - location)
+    :task (deliver ?v)
+    :ordered-subtasks (and (drive ?v ?l)))
+  (:action drive :parameters (?v - vehicle ?l - location)
+    :precondition (ready)
+    :effect (at ?v ?l)))";
+
+fn labels(items: Vec<CompletionItem>) -> Vec<String> {
+    items.into_iter().map(|item| item.label).collect()
+}
+
+// completions at the end of the text
+fn complete_at_end(text: &str, domain: Option<&Vec<u8>>) -> Vec<String> {
+    let content = text.as_bytes().to_vec();
+    labels(complete(&content, content.len(), domain))
+}
+
+#[test]
+pub fn completion_context_test() {
+    let context = |text: &str| completion_context(text.as_bytes(), text.len());
+    assert_eq!(context("(define (domain d) (:requirements :typ"), CompletionContext::Requirement);
+    assert_eq!(context("(define (domain d) (:action a :parameters (?x - "), CompletionContext::Type);
+    assert_eq!(context("(define (domain d) (:types a - o"), CompletionContext::Type);
+    assert_eq!(
+        context("(define (domain d) (:action a :parameters () :precondition (and (not ("),
+        CompletionContext::Predicate
+    );
+    assert_eq!(
+        context("(define (domain d) (:method m :parameters () :task (t) :subtasks (and (t1 (dr"),
+        CompletionContext::Task
+    );
+    assert_eq!(
+        context("(define (problem p) (:domain d) (:init (at t1 ; comment (\n "),
+        CompletionContext::Argument("at".to_string(), 1)
+    );
+    // variables of a precondition are not completed
+    assert_eq!(
+        context("(define (domain d) (:action a :parameters (?x) :precondition (at "),
+        CompletionContext::Unknown
+    );
+    assert_eq!(context("(define (domain d) (:predicates ("), CompletionContext::Unknown);
+}
+
+#[test]
+pub fn domain_completion_test() {
+    let domain = DOMAIN.as_bytes().to_vec();
+    let requirements = complete_at_end("(define (domain d) (:requirements ", None);
+    assert!(requirements.contains(&":hierarchy".to_string()));
+    // the incomplete action is dropped, but the rest of the domain is used
+    let text = format!("{}\n  (:action a :parameters (?x - ", &DOMAIN[..DOMAIN.len() - 1]);
+    assert_eq!(
+        complete_at_end(&text, Some(&text.as_bytes().to_vec())),
+        vec!["location", "object", "truck", "vehicle"]
+    );
+    let text = format!("{}\n  (:action a :parameters () :precondition (", &DOMAIN[..DOMAIN.len() - 1]);
+    let content = text.as_bytes().to_vec();
+    let items = complete(&content, content.len(), Some(&content));
+    assert_eq!(labels(items.clone()), vec!["at", "ready"]);
+    assert_eq!(items[0].detail.as_deref(), Some("(at ?v - vehicle ?l - location)"));
+    assert_eq!(
+        items[0].label_details.as_ref().unwrap().description.as_deref(),
+        Some("arity 2")
+    );
+    let text = DOMAIN.replace(":ordered-subtasks (and (drive ?v ?l))", ":ordered-subtasks (and (");
+    let offset = text.find("(and (").unwrap() + 6;
+    let items = complete(&text.as_bytes().to_vec(), offset, Some(&domain));
+    assert_eq!(labels(items), vec!["deliver", "drive"]);
+}
+
+#[test]
+pub fn init_completion_test() {
+    let domain = DOMAIN.as_bytes().to_vec();
+    let problem = "(define (problem p) (:domain d)
+  (:objects t1 - truck v1 - vehicle home - location other)
+  (:init (at ";
+    assert_eq!(complete_at_end(problem, Some(&domain)), vec!["t1", "v1"]);
+    let problem = format!("{}t1 ", problem);
+    assert_eq!(complete_at_end(&problem, Some(&domain)), vec!["home", "depot"]);
+    let problem = format!("{}home ", problem);
+    assert!(complete_at_end(&problem, Some(&domain)).is_empty());
+    // without the domain, every object fits
+    let problem = "(define (problem p) (:domain d) (:objects a b) (:init (at ";
+    assert_eq!(complete_at_end(problem, None), vec!["a", "b"]);
+}
@@ -0,0 +1,134 @@
+use super::*;
+use std::sync::Arc;
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+use tower_lsp::lsp_types::{Position, Range, TextDocumentContentChangeEvent, Url};
+
+fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(Range {
+            start: Position::new(start.0, start.1),
+            end: Position::new(end.0, end.1),
+        }),
+        range_length: None,
+        text: text.to_string(),
+    }
+}
+
+#[test]
+pub fn incremental_change_test() {
+    let mut document = Document::new(1, "(define (domain d)\n  (:predicates (at ?l)))".as_bytes().to_vec());
+    document.apply_changes(
+        2,
+        vec![
+            // replace
+            change((1, 16), (1, 18), "located"),
+            // insert (at the position after the previous change)
+            change((1, 26), (1, 26), " ?t"),
+            // delete the first line break
+            change((0, 18), (1, 1), ""),
+        ],
+        PositionEncoding::UTF16,
+    );
+    assert_eq!(document.version, 2);
+    assert_eq!(
+        String::from_utf8_lossy(document.content()),
+        "(define (domain d) (:predicates (located ?l ?t)))"
+    );
+    // changes without a range replace the whole content
+    document.apply_changes(
+        3,
+        vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "(define (domain e))".to_string(),
+        }],
+        PositionEncoding::UTF16,
+    );
+    assert_eq!(String::from_utf8_lossy(document.content()), "(define (domain e))");
+}
+
+#[test]
+pub fn non_ascii_change_test() {
+    // "ü" is two bytes, but a single UTF-16 code unit
+    let content = "(define (domain d) ; für\n  (:predicates (at ?l)))";
+    let mut document = Document::new(1, content.as_bytes().to_vec());
+    document.apply_changes(2, vec![change((0, 24), (0, 24), "s")], PositionEncoding::UTF16);
+    assert_eq!(
+        String::from_utf8_lossy(document.content()),
+        "(define (domain d) ; fürs\n  (:predicates (at ?l)))"
+    );
+    // clients that agreed to UTF-8 count the bytes
+    document.apply_changes(3, vec![change((0, 24), (0, 26), "")], PositionEncoding::UTF8);
+    assert_eq!(
+        String::from_utf8_lossy(document.content()),
+        "(define (domain d) ; fü\n  (:predicates (at ?l)))"
+    );
+}
+
+#[test]
+pub fn analysis_cache_test() {
+    let domain = "(define (domain d)
+  (:types location)
+  (:predicates (at ?l - location))
+  (:action noop :parameters ()))";
+    let url = Url::parse("file:///d.hddl").unwrap();
+    let mut document = Document::new(1, domain.as_bytes().to_vec());
+    let analysis = document.analysis(&url);
+    assert!(Arc::ptr_eq(&analysis, &document.analysis(&url)));
+    assert!(analysis.analysis().symbol_table.is_some());
+    assert!(analysis.analysis().index.is_some());
+    // the analysis is invalidated by a change
+    document.apply_changes(2, vec![change((2, 32), (2, 32), "-kind")], PositionEncoding::UTF16);
+    let changed = document.analysis(&url);
+    assert!(!Arc::ptr_eq(&analysis, &changed));
+    assert!(changed.analysis().symbol_table.is_none());
+    let errors: Vec<&Diagnostic> = changed
+        .analysis()
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+        .collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].range.start, Position::new(2, 24));
+    // replacing the content with the same content keeps the analysis
+    document.replace(document.content().clone());
+    assert!(Arc::ptr_eq(&changed, &document.analysis(&url)));
+}
+
+#[test]
+pub fn diagnose_problem_test() {
+    let domain_url = Url::parse("file:///d.hddl").unwrap();
+    let problem_url = Url::parse("file:///p.hddl").unwrap();
+    let domain = AnalyzedDocument::analyze(
+        &domain_url,
+        "(define (domain d)
+  (:types location)
+  (:predicates (at ?l - location)))"
+            .as_bytes()
+            .to_vec(),
+    );
+    let problem = AnalyzedDocument::analyze(
+        &problem_url,
+        "(define (problem p) (:domain d)
+  (:objects home - location)
+  (:init (at home) (at work)))"
+            .as_bytes()
+            .to_vec(),
+    );
+    let errors = |diagnostics: Vec<Diagnostic>| -> Vec<Position> {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+            .map(|diagnostic| diagnostic.range.start)
+            .collect()
+    };
+    assert_eq!(errors(diagnose_problem(Some(&domain), &problem_url, &problem)), vec![Position::new(2, 23)]);
+    // the codes are the ones of the command line reports
+    let codes: Vec<Option<NumberOrString>> = diagnose_problem(Some(&domain), &problem_url, &problem)
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect();
+    assert!(codes.contains(&Some(NumberOrString::String("UndefinedObject".to_string()))));
+    assert!(errors(diagnose_problem(None, &problem_url, &problem)).is_empty());
+    assert!(errors(diagnose_domain(&domain)).is_empty());
+}
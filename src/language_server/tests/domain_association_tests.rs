@@ -0,0 +1,81 @@
+use super::*;
+use std::path::{Path, PathBuf};
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::syntactic_analyzer::{AbstractSyntaxTree, Parser};
+use tower_lsp::lsp_types::Position;
+
+#[test]
+pub fn domain_mappings_test() {
+    let settings = serde_json::json!({
+        "domainMappings": {
+            "problems": "domains/transport.hddl",
+            "problems/rover": "domains/rover.hddl",
+            "/tmp/p01.hddl": "/tmp/d.hddl"
+        }
+    });
+    let mappings = DomainMappings::from_json(&settings, Path::new("/ipc"));
+    assert_eq!(
+        mappings.domain_of(Path::new("/ipc/problems/p01.hddl")),
+        Some(&PathBuf::from("/ipc/domains/transport.hddl"))
+    );
+    // the most specific mapping is used
+    assert_eq!(
+        mappings.domain_of(Path::new("/ipc/problems/rover/p01.hddl")),
+        Some(&PathBuf::from("/ipc/domains/rover.hddl"))
+    );
+    assert_eq!(mappings.domain_of(Path::new("/tmp/p01.hddl")), Some(&PathBuf::from("/tmp/d.hddl")));
+    // a folder is only matched as a whole
+    assert_eq!(mappings.domain_of(Path::new("/ipc/problems-2/p01.hddl")), None);
+    assert!(DomainMappings::from_json(&serde_json::json!({}), Path::new("/ipc")).is_empty());
+}
+
+#[test]
+pub fn choose_domain_test() {
+    let problem = Path::new("/ipc/transport/problems/p01.hddl");
+    let candidates = vec![
+        PathBuf::from("/ipc/rover/domain.hddl"),
+        PathBuf::from("/ipc/transport/domain.hddl"),
+        PathBuf::from("/ipc/transport/domain-2.hddl"),
+    ];
+    assert_eq!(choose_domain(problem, &[]), Association::Missing);
+    assert_eq!(
+        choose_domain(problem, &candidates[..1]),
+        Association::Unique(PathBuf::from("/ipc/rover/domain.hddl"))
+    );
+    // the closest domain is chosen (and the first one of the equally close ones)
+    match choose_domain(problem, &candidates) {
+        Association::Ambiguous(chosen, all) => {
+            assert_eq!(chosen, PathBuf::from("/ipc/transport/domain-2.hddl"));
+            assert_eq!(all.len(), 3);
+        }
+        association => panic!("expected an ambiguous association, found {:?}", association),
+    }
+}
+
+#[test]
+pub fn association_diagnostic_test() {
+    let problem = "(define (problem p) (:domain transport)\n  (:objects t - truck))".as_bytes().to_vec();
+    let (ast, _) = Parser::new(LexicalAnalyzer::new(&problem)).parse_partial();
+    let Some(AbstractSyntaxTree::Problem(problem)) = ast else {
+        panic!("expected a problem");
+    };
+    assert_eq!(problem.domain_name, "transport");
+    let missing = association_diagnostic(&problem.domain_name, &problem.domain_name_pos, &Association::Missing).unwrap();
+    assert_eq!(missing.range.start, Position::new(0, 29));
+    assert_eq!(missing.range.end, Position::new(0, 38));
+    let candidates = vec![PathBuf::from("/a/domain.hddl"), PathBuf::from("/b/domain.hddl")];
+    let ambiguous = Association::Ambiguous(candidates[0].clone(), candidates);
+    let diagnostic = association_diagnostic(&problem.domain_name, &problem.domain_name_pos, &ambiguous).unwrap();
+    assert!(diagnostic.message.starts_with("2 domains are named transport"));
+    assert_eq!(diagnostic.related_information.unwrap().len(), 2);
+    assert!(association_diagnostic("transport", &problem.domain_name_pos, &Association::Unique(PathBuf::new())).is_none());
+}
+
+#[test]
+pub fn definition_domain_name_test() {
+    let domain = "(define (domain transport) (:types truck))".as_bytes().to_vec();
+    let problem = "(define (problem p) (:domain transport))".as_bytes().to_vec();
+    assert_eq!(definition_domain_name(&domain), Some("transport".to_string()));
+    assert_eq!(definition_domain_name(&problem), None);
+    assert_eq!(definition_domain_name(&"(:types)".as_bytes().to_vec()), None);
+}
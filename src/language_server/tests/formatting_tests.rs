@@ -0,0 +1,41 @@
+use super::*;
+use tower_lsp::lsp_types::{FormattingOptions, Position, Range};
+
+#[test]
+pub fn position_offset_conversion_test() {
+    let content = "(define\n  (domain d)\n)".as_bytes().to_vec();
+    assert_eq!(to_position(&content, 0), Position::new(0, 0));
+    assert_eq!(to_position(&content, 10), Position::new(1, 2));
+    assert_eq!(to_position(&content, content.len()), Position::new(2, 1));
+    assert_eq!(to_offset(&content, &Position::new(1, 2)), 10);
+    // positions past the end of a line are clamped to it
+    assert_eq!(to_offset(&content, &Position::new(0, 50)), 7);
+    assert_eq!(to_offset(&content, &Position::new(9, 0)), content.len());
+}
+
+#[test]
+pub fn format_document_edits_test() {
+    let options = FormattingOptions {
+        tab_size: 4,
+        insert_spaces: true,
+        ..FormattingOptions::default()
+    };
+    let options = to_format_options(&options, 100);
+    let content = "(define (domain d) (:predicates (p)))".as_bytes().to_vec();
+    let edits = format_document(&content, options).unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        edits[0].range,
+        Range::new(Position::new(0, 0), Position::new(0, content.len() as u32))
+    );
+    assert_eq!(
+        edits[0].new_text,
+        "(define (domain d)\n    (:predicates\n        (p)\n    )\n)\n"
+    );
+    // formatted documents produce no edits
+    let formatted = edits[0].new_text.clone().into_bytes();
+    assert!(format_document(&formatted, options).unwrap().is_empty());
+    // documents that can not be parsed are not formatted
+    let invalid = "(define (domain d) (:predicates (p)".as_bytes().to_vec();
+    assert!(format_document(&invalid, options).is_none());
+}
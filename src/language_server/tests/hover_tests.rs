@@ -0,0 +1,62 @@
+use super::*;
+
+fn domain() -> Vec<u8> {
+    "(define (domain d)
+  (:types truck - vehicle vehicle - object)
+  (:constants depot - location)
+  (:predicates (at ?v - vehicle ?l - location))
+  (:task deliver :parameters (?v - vehicle ?l - location))
+  (:method m-drive :parameters (?v - vehicle ?l - location)
+    :task (deliver ?v ?l)
+    :ordered-subtasks (and (drive ?v ?l)))
+  (:method m-done :parameters (?v - vehicle ?l - location)
+    :task (deliver ?v ?l)
+    :precondition (at ?v ?l)
+    :ordered-subtasks (and))
+  (:action drive :parameters (?v - vehicle ?l - location)
+    :effect (at ?v ?l)))"
+        .as_bytes()
+        .to_vec()
+}
+
+#[test]
+pub fn hover_task_test() {
+    let description = describe_domain_symbol(&domain(), ElementKind::Task, "deliver").unwrap();
+    assert_eq!(
+        description,
+        "```hddl\n(:task deliver :parameters (?v - vehicle ?l - location))\n```\n\n\
+        compound task (nullable)\n\nmethods: `m-drive`, `m-done`"
+    );
+    let description = describe_domain_symbol(&domain(), ElementKind::Task, "drive").unwrap();
+    assert_eq!(
+        description,
+        "```hddl\n(:action drive :parameters (?v - vehicle ?l - location))\n```\n\nprimitive task"
+    );
+}
+
+#[test]
+pub fn hover_type_test() {
+    let description = describe_domain_symbol(&domain(), ElementKind::Type, "vehicle").unwrap();
+    assert_eq!(
+        description,
+        "```hddl\n(:types vehicle)\n```\n\nsupertypes: `object`\n\nsubtypes: `truck`"
+    );
+    assert!(describe_domain_symbol(&domain(), ElementKind::Type, "package").is_none());
+}
+
+#[test]
+pub fn hover_object_test() {
+    let problem = "(define (problem p) (:domain d)
+  (:objects t1 - truck home)
+  (:init (at t1 home)))"
+        .as_bytes()
+        .to_vec();
+    assert_eq!(
+        describe_problem_object(&problem, "t1").unwrap(),
+        "```hddl\nt1 - truck\n```\n\nobject"
+    );
+    assert_eq!(
+        describe_domain_symbol(&domain(), ElementKind::Object, "depot").unwrap(),
+        "```hddl\ndepot - location\n```\n\nconstant"
+    );
+}
@@ -0,0 +1,13 @@
+mod code_action_tests;
+mod completion_tests;
+mod document_store_tests;
+mod domain_association_tests;
+mod formatting_tests;
+mod hover_tests;
+mod outline_tests;
+mod rename_tests;
+mod request_handler_tests;
+mod semantic_tokens_tests;
+mod symbol_index_tests;
+
+use super::*;
@@ -0,0 +1,101 @@
+use super::*;
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind, Url};
+
+fn outline(symbols: &[DocumentSymbol]) -> Vec<(String, SymbolKind, usize)> {
+    symbols
+        .iter()
+        .map(|symbol| {
+            let children = symbol.children.as_ref().map_or(0, |children| children.len());
+            (symbol.name.clone(), symbol.kind, children)
+        })
+        .collect()
+}
+
+#[test]
+pub fn domain_outline_test() {
+    let domain = "(define (domain d)
+  (:types location - object)
+  (:predicates (at ?l - location))
+  (:task go :parameters (?l - location))
+  (:method m-go :parameters (?l - location)
+    :task (go ?l)
+    :ordered-subtasks (and (move ?l)))
+  (:method m-stay :parameters (?l - location)
+    :task (go ?l)
+    :precondition (at ?l)
+    :ordered-subtasks (and))
+  (:action move :parameters (?to - location)
+    :effect (at ?to)))"
+        .as_bytes()
+        .to_vec();
+    let symbols = document_symbols(&domain).unwrap();
+    assert_eq!(
+        outline(&symbols),
+        vec![
+            ("location".to_string(), SymbolKind::CLASS, 0),
+            ("at".to_string(), SymbolKind::PROPERTY, 0),
+            ("go".to_string(), SymbolKind::FUNCTION, 2),
+            ("move".to_string(), SymbolKind::FUNCTION, 0),
+        ]
+    );
+    // tasks and methods span their blocks
+    let task = &symbols[2];
+    assert_eq!((task.range.start.line, task.range.end.line), (3, 3));
+    assert_eq!((task.selection_range.start.character, task.selection_range.end.character), (9, 11));
+    let methods = task.children.as_ref().unwrap();
+    assert_eq!(outline(methods)[1].0, "m-stay");
+    assert_eq!((methods[1].range.start.line, methods[1].range.end.line), (7, 10));
+    assert_eq!(methods[1].detail.as_deref(), Some("?l - location"));
+}
+
+#[test]
+pub fn problem_outline_test() {
+    let problem = "(define (problem p) (:domain d)
+  (:objects home work - location)
+  (:htn :parameters ()
+    :ordered-subtasks (and (task0 (go work))))
+  (:init (at home))
+  (:goal (and (at work))))"
+        .as_bytes()
+        .to_vec();
+    let symbols = document_symbols(&problem).unwrap();
+    assert_eq!(
+        outline(&symbols),
+        vec![
+            ("objects".to_string(), SymbolKind::NAMESPACE, 2),
+            ("htn".to_string(), SymbolKind::NAMESPACE, 1),
+            ("init".to_string(), SymbolKind::NAMESPACE, 1),
+            ("goal".to_string(), SymbolKind::NAMESPACE, 1),
+        ]
+    );
+    let htn = &symbols[1];
+    assert_eq!((htn.range.start.line, htn.range.end.line), (2, 3));
+    let subtask = &htn.children.as_ref().unwrap()[0];
+    assert_eq!((subtask.name.as_str(), subtask.detail.as_deref()), ("(go work)", Some("task0")));
+    assert_eq!(symbols[2].children.as_ref().unwrap()[0].name, "(at home)");
+}
+
+#[test]
+pub fn workspace_symbols_test() {
+    let domain = "(define (domain d)
+  (:types truck location)
+  (:predicates (truck-at ?t - truck ?l - location))
+  (:action drive :parameters (?t - truck ?to - location)))"
+        .as_bytes()
+        .to_vec();
+    let url = Url::parse("file:///transport/domain.hddl").unwrap();
+    let names = |query: &str| -> Vec<String> {
+        workspace_symbols(&url, &domain, query)
+            .into_iter()
+            .map(|symbol| symbol.name)
+            .collect()
+    };
+    assert_eq!(names("trk"), vec!["truck", "truck-at"]);
+    assert_eq!(names("DRV"), vec!["drive"]);
+    assert_eq!(names("").len(), 4);
+    assert!(names("xyz").is_empty());
+    let symbol = &workspace_symbols(&url, &domain, "drive")[0];
+    assert_eq!(symbol.container_name.as_deref(), Some("domain.hddl"));
+    assert_eq!(fuzzy_score("trk", "truck"), Some(2));
+    assert!(fuzzy_score("tr", "truck-at") < fuzzy_score("ta", "truck-at"));
+}
@@ -0,0 +1,53 @@
+use super::*;
+use tower_lsp::lsp_types::TextEdit;
+
+#[test]
+pub fn validate_name_test() {
+    assert_eq!(validate_name("deliver-2"), Ok("deliver-2".to_string()));
+    assert_eq!(validate_name("?target"), Ok("target".to_string()));
+    assert!(validate_name("").is_err());
+    assert!(validate_name("2nd").is_err());
+    assert!(validate_name("a b").is_err());
+    assert!(validate_name("(go)").is_err());
+}
+
+#[test]
+pub fn rename_edits_test() {
+    let domain = "(define (domain d)
+  (:predicates (at ?l))
+  (:task go :parameters (?l))
+  (:method m-1 :parameters (?l)
+    :task (go ?l)
+    :ordered-subtasks (and (move ?l)))
+  (:method m-2 :parameters (?l)
+    :task (go ?l)
+    :precondition (at ?l)
+    :ordered-subtasks (and (move ?l)))
+  (:action move :parameters (?to)
+    :effect (at ?to)))"
+        .as_bytes()
+        .to_vec();
+    let index = index_document(&domain).unwrap();
+    let edit_positions = |edits: Vec<TextEdit>| -> Vec<(u32, u32, u32)> {
+        edits
+            .iter()
+            .map(|edit| (edit.range.start.line, edit.range.start.character, edit.range.end.character))
+            .collect()
+    };
+    // global symbols are renamed at their declaration and all of their uses
+    let offset = domain.windows(4).position(|w| w == b"move").unwrap();
+    let task = index.find(offset).unwrap();
+    let edits = rename_edits(&index, task, "walk");
+    assert!(edits.iter().all(|edit| edit.new_text == "walk"));
+    assert_eq!(edit_positions(edits), vec![(5, 28, 32), (9, 28, 32), (10, 11, 15)]);
+    // parameters keep their '?' and are only renamed in their own method
+    let offset = domain.windows(13).position(|w| w == b":precondition").unwrap();
+    let variable = index.find(offset + 19).unwrap();
+    let edits = rename_edits(&index, variable, "x");
+    assert!(edits.iter().all(|edit| edit.new_text == "?x"));
+    assert_eq!(edit_positions(edits), vec![(6, 28, 30), (7, 14, 16), (8, 22, 24), (9, 33, 35)]);
+    // names that are taken by symbols of the same kind are reported
+    assert!(is_declared(&index, task, "go"));
+    assert!(!is_declared(&index, task, "m-1"));
+    assert!(is_declared(&index, variable, "l"));
+}
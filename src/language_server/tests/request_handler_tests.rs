@@ -0,0 +1,213 @@
+use super::*;
+use std::path::PathBuf;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{LanguageServer, LspService};
+
+// two domains with the same predicate in one folder, each with a problem
+fn workspace(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("hddl-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&folder).unwrap();
+    let files = [
+        ("d1.hddl", "(define (domain logistics)\n  (:predicates (at ?x))\n  (:action a :parameters (?x) :effect (at ?x)))\n"),
+        ("d2.hddl", "(define (domain other)\n  (:predicates (at ?x))\n  (:action b :parameters (?x) :effect (at ?x)))\n"),
+        ("p1.hddl", "(define (problem p1) (:domain logistics)\n  (:objects o1)\n  (:init (at o1)))\n"),
+        ("p2.hddl", "(define (problem p2) (:domain other)\n  (:objects o2)\n  (:init (at o2)))\n"),
+    ];
+    for (file, content) in files {
+        std::fs::write(folder.join(file), content).unwrap();
+    }
+    folder
+}
+
+async fn open(handler: &RequestHandler, url: &Url) {
+    let text = std::fs::read_to_string(url.to_file_path().unwrap()).unwrap();
+    handler
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(url.clone(), "hddl".to_string(), 0, text),
+        })
+        .await;
+}
+
+fn position(url: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+    TextDocumentPositionParams::new(TextDocumentIdentifier::new(url.clone()), Position::new(line, character))
+}
+
+fn file_names<'a>(urls: impl Iterator<Item = &'a Url>) -> Vec<String> {
+    let mut names: Vec<String> = urls
+        .map(|url| url.path_segments().unwrap().next_back().unwrap().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[tokio::test]
+pub async fn references_of_domain_test() {
+    let folder = workspace("references");
+    let (service, _) = LspService::new(RequestHandler::new);
+    let handler = service.inner();
+    let domain_url = Url::from_file_path(folder.join("d1.hddl")).unwrap();
+    open(handler, &domain_url).await;
+    // the predicate "at" in (:predicates (at ?x))
+    let locations = handler
+        .references(ReferenceParams {
+            text_document_position: position(&domain_url, 1, 16),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: ReferenceContext {
+                include_declaration: true,
+            },
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(file_names(locations.iter().map(|location| &location.uri)), vec!["d1.hddl", "p1.hddl"]);
+    assert_eq!(locations.len(), 3);
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[tokio::test]
+pub async fn rename_in_domain_test() {
+    let folder = workspace("rename");
+    let (service, _) = LspService::new(RequestHandler::new);
+    let handler = service.inner();
+    let domain_url = Url::from_file_path(folder.join("d1.hddl")).unwrap();
+    open(handler, &domain_url).await;
+    let edit = handler
+        .rename(RenameParams {
+            text_document_position: position(&domain_url, 1, 16),
+            new_name: "located".to_string(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let changes = edit.changes.unwrap();
+    // the problem of the other domain is left alone
+    assert_eq!(file_names(changes.keys()), vec!["d1.hddl", "p1.hddl"]);
+    std::fs::remove_dir_all(folder).unwrap();
+}
+
+async fn initialize(handler: &RequestHandler, position_encodings: Option<Vec<PositionEncodingKind>>) -> InitializeResult {
+    let mut params = InitializeParams::default();
+    params.capabilities.general = Some(GeneralClientCapabilities {
+        position_encodings,
+        ..GeneralClientCapabilities::default()
+    });
+    handler.initialize(params).await.unwrap()
+}
+
+// "ü" is two bytes, but a single UTF-16 code unit
+#[tokio::test]
+pub async fn non_ascii_positions_test() {
+    let url = Url::parse("file:///non-ascii/domain.hddl").unwrap();
+    for (offered, column) in [(None, 26), (Some(vec![PositionEncodingKind::UTF8]), 27)] {
+        let (service, _) = LspService::new(RequestHandler::new);
+        let handler = service.inner();
+        let result = initialize(handler, offered.clone()).await;
+        let expected = offered.map_or(PositionEncodingKind::UTF16, |_| PositionEncodingKind::UTF8);
+        assert_eq!(result.capabilities.position_encoding, Some(expected));
+        let text = "(define (domain d)\n  (:predicates (üer ?x) (at ?x)))\n";
+        handler
+            .did_open(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(url.clone(), "hddl".to_string(), 0, text.to_string()),
+            })
+            .await;
+        // insert after the "ü"
+        let after = column - 9;
+        handler
+            .did_change(DidChangeTextDocumentParams {
+                text_document: VersionedTextDocumentIdentifier::new(url.clone(), 1),
+                content_changes: vec![TextDocumentContentChangeEvent {
+                    range: Some(Range::new(Position::new(1, after), Position::new(1, after))),
+                    range_length: None,
+                    text: "b".to_string(),
+                }],
+            })
+            .await;
+        // the predicate "at" after "über"
+        let hover = handler
+            .hover(HoverParams {
+                text_document_position_params: position(&url, 1, column),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(hover.range, Some(Range::new(Position::new(1, column), Position::new(1, column + 2))));
+    }
+}
+
+async fn send(writer: &mut (impl tokio::io::AsyncWrite + Unpin), message: serde_json::Value) {
+    use tokio::io::AsyncWriteExt;
+    let body = message.to_string();
+    let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    writer.write_all(frame.as_bytes()).await.unwrap();
+}
+
+async fn receive(reader: &mut (impl tokio::io::AsyncBufRead + Unpin)) -> serde_json::Value {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt};
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).await.unwrap();
+        match header.trim().strip_prefix("Content-Length: ") {
+            Some(value) => length = value.parse().unwrap(),
+            None if header.trim().is_empty() => break,
+            None => {}
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+// the diagnostics of a domain with a duplicate predicate are published when it is opened
+#[tokio::test]
+pub async fn publish_duplicate_diagnostics_test() {
+    let path = std::env::current_dir()
+        .unwrap()
+        .join("tests/flawed_domains/duplicate-predicate-domain.hddl");
+    let url = Url::from_file_path(&path).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let (client, server) = tokio::io::duplex(1 << 16);
+    let (server_reader, server_writer) = tokio::io::split(server);
+    let (client_reader, mut client_writer) = tokio::io::split(client);
+    let (service, socket) = LspService::new(RequestHandler::new);
+    tokio::spawn(tower_lsp::Server::new(server_reader, server_writer, socket).serve(service));
+    let mut client_reader = tokio::io::BufReader::new(client_reader);
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} }
+    });
+    send(&mut client_writer, initialize).await;
+    // the messages after the initialization are rejected until it is answered
+    let response = receive(&mut client_reader).await;
+    assert_eq!(response["id"], 1);
+    // clients are asked to notify the server when a document is saved
+    assert_eq!(response["result"]["capabilities"]["textDocumentSync"]["save"], true);
+    send(&mut client_writer, serde_json::json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} })).await;
+    let did_open = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": url, "languageId": "hddl", "version": 1, "text": text } }
+    });
+    send(&mut client_writer, did_open).await;
+    let published = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        loop {
+            let message = receive(&mut client_reader).await;
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message;
+            }
+        }
+    })
+    .await
+    .expect("the diagnostics are not published");
+    let diagnostics: Vec<Diagnostic> = serde_json::from_value(published["params"]["diagnostics"].clone()).unwrap();
+    let duplicate = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.code == Some(NumberOrString::String("DuplicatePredicateDeclaration".to_string())))
+        .unwrap();
+    // the first declaration is in the same document
+    let related = duplicate.related_information.as_ref().unwrap();
+    assert_eq!(related[0].location.uri, url);
+}
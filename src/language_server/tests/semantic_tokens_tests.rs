@@ -0,0 +1,113 @@
+use super::*;
+use tower_lsp::lsp_types::{SemanticToken, Url};
+
+// the text, type and modifiers of each token
+fn decode(content: &str, tokens: Vec<SemanticToken>) -> Vec<(String, HighlightKind, u32)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let kinds = [
+        HighlightKind::Keyword,
+        HighlightKind::Requirement,
+        HighlightKind::Type,
+        HighlightKind::Predicate,
+        HighlightKind::PrimitiveTask,
+        HighlightKind::CompoundTask,
+        HighlightKind::Method,
+        HighlightKind::Variable,
+        HighlightKind::Constant,
+        HighlightKind::Object,
+    ];
+    let (mut line, mut start) = (0, 0);
+    tokens
+        .into_iter()
+        .map(|token| {
+            if token.delta_line > 0 {
+                start = 0;
+            }
+            line += token.delta_line as usize;
+            start += token.delta_start as usize;
+            let text = lines[line][start..start + token.length as usize].to_string();
+            (text, kinds[token.token_type as usize], token.token_modifiers_bitset)
+        })
+        .collect()
+}
+
+const DOMAIN: &str = "(define (domain d)
+  (:requirements :hierarchy :typing)
+  (:types location)
+  (:constants depot - location)
+  (:predicates (at ?l - location))
+  (:task deliver :parameters (?l - location))
+  (:method m-deliver
+    :parameters (?l - location)
+    :task (deliver ?l)
+    :ordered-subtasks (and (move ?l) (fly ?k)))
+  (:action move
+    :parameters (?l - location)
+    :precondition (at depot)
+    :effect (at ?l)))";
+
+#[test]
+pub fn domain_tokens_test() {
+    let url = Url::parse("file:///d.hddl").unwrap();
+    let document = AnalyzedDocument::analyze(&url, DOMAIN.as_bytes().to_vec());
+    let tokens = semantic_tokens(document.content(), document.ast(), document.analysis().index.as_ref(), None, None, PositionEncoding::UTF16);
+    let tokens = decode(DOMAIN, tokens);
+    let find = |text: &str| {
+        tokens
+            .iter()
+            .filter(|(token, _, _)| token == text)
+            .map(|(_, kind, modifiers)| (*kind, *modifiers))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(find("define"), vec![(HighlightKind::Keyword, 0)]);
+    assert_eq!(find(":typing"), vec![(HighlightKind::Requirement, 0)]);
+    assert_eq!(find("and"), vec![(HighlightKind::Keyword, 0)]);
+    assert_eq!(find("location")[0], (HighlightKind::Type, 1));
+    assert_eq!(find("location")[1], (HighlightKind::Type, 0));
+    assert_eq!(find("depot"), vec![(HighlightKind::Constant, 1), (HighlightKind::Constant, 0)]);
+    assert_eq!(find("at")[0], (HighlightKind::Predicate, 1));
+    assert_eq!(find("deliver"), vec![(HighlightKind::CompoundTask, 1), (HighlightKind::CompoundTask, 0)]);
+    assert_eq!(find("m-deliver"), vec![(HighlightKind::Method, 1)]);
+    assert_eq!(find("move"), vec![(HighlightKind::PrimitiveTask, 0), (HighlightKind::PrimitiveTask, 1)]);
+    // undefined symbols
+    assert_eq!(find("fly"), vec![(HighlightKind::CompoundTask, 2)]);
+    assert_eq!(find("?k"), vec![(HighlightKind::Variable, 2)]);
+    assert_eq!(find("?l")[0], (HighlightKind::Variable, 1));
+}
+
+#[test]
+pub fn problem_tokens_test() {
+    let problem = "(define (problem p) (:domain d)
+  (:objects home - location)
+  (:htn :ordered-subtasks (and (deliver home)))
+  (:init (at depot) (at work)))";
+    let domain = AnalyzedDocument::analyze(&Url::parse("file:///d.hddl").unwrap(), DOMAIN.as_bytes().to_vec());
+    let document = AnalyzedDocument::analyze(&Url::parse("file:///p.hddl").unwrap(), problem.as_bytes().to_vec());
+    let index = document.analysis().index.as_ref();
+    let tokens = semantic_tokens(document.content(), document.ast(), index, domain.ast(), None, PositionEncoding::UTF16);
+    let tokens = decode(problem, tokens);
+    let symbols: Vec<(&str, HighlightKind, u32)> = tokens
+        .iter()
+        .filter(|(_, kind, _)| *kind != HighlightKind::Keyword)
+        .map(|(text, kind, modifiers)| (text.as_str(), *kind, *modifiers))
+        .collect();
+    assert_eq!(
+        symbols,
+        vec![
+            ("home", HighlightKind::Object, 1),
+            ("location", HighlightKind::Type, 0),
+            ("deliver", HighlightKind::CompoundTask, 0),
+            ("home", HighlightKind::Object, 0),
+            ("at", HighlightKind::Predicate, 0),
+            ("depot", HighlightKind::Constant, 0),
+            ("at", HighlightKind::Predicate, 0),
+            ("work", HighlightKind::Object, 2),
+        ]
+    );
+    // only the tokens that start in the range (the ":init" line)
+    let start = problem.find("(:init").unwrap();
+    let tokens = semantic_tokens(document.content(), document.ast(), index, domain.ast(), Some((start, problem.len())), PositionEncoding::UTF16);
+    let tokens = decode(problem, tokens);
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[0].0, ":init");
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+fn occurrences(index: &SymbolIndex, kind: ElementKind, name: &str) -> Vec<(u32, u32, bool)> {
+    index
+        .occurrences
+        .iter()
+        .filter(|occurrence| occurrence.kind == kind && occurrence.name == name)
+        .map(|occurrence| {
+            (occurrence.position.line, occurrence.position.column, occurrence.is_declaration)
+        })
+        .collect()
+}
+
+#[test]
+pub fn domain_index_test() {
+    let domain = "(define (domain d)
+  (:types location)
+  (:constants home - location)
+  (:predicates (at ?l - location))
+  (:task go :parameters (?l - location))
+  (:method m-go :parameters (?l - location)
+    :task (go ?l)
+    :ordered-subtasks (and (move home ?l)))
+  (:action move :parameters (?from ?to - location)
+    :precondition (at ?from)
+    :effect (and (not (at ?from)) (at ?to))))"
+        .as_bytes()
+        .to_vec();
+    let index = index_document(&domain).unwrap();
+    assert_eq!(
+        occurrences(&index, ElementKind::Task, "move"),
+        vec![(8, 29, false), (9, 12, true)]
+    );
+    assert_eq!(
+        occurrences(&index, ElementKind::Task, "go"),
+        vec![(5, 10, true), (7, 12, false)]
+    );
+    assert_eq!(
+        occurrences(&index, ElementKind::Predicate, "at"),
+        vec![(4, 17, true), (10, 20, false), (11, 24, false), (11, 36, false)]
+    );
+    assert_eq!(occurrences(&index, ElementKind::Object, "home"), vec![(3, 15, true), (8, 34, false)]);
+    assert_eq!(occurrences(&index, ElementKind::Type, "location").len(), 7);
+    assert_eq!(occurrences(&index, ElementKind::Method, "m-go"), vec![(6, 12, true)]);
+    // the name of the subtask is found at any of its characters
+    let offset = domain.windows(10).position(|w| w == b"(move home").unwrap();
+    let occurrence = index.find(offset + 3).unwrap();
+    assert_eq!((occurrence.kind, occurrence.name.as_str()), (ElementKind::Task, "move"));
+    // variables are scoped by their block
+    let offset = domain.windows(5).position(|w| w == b"?from").unwrap();
+    let variable = index.find(offset + 2).unwrap();
+    assert_eq!((variable.kind, variable.is_declaration), (ElementKind::Variable, true));
+    let uses: Vec<(u32, u32)> = index
+        .occurrences_of(variable)
+        .map(|occurrence| (occurrence.position.line, occurrence.position.column))
+        .collect();
+    assert_eq!(uses, vec![(9, 30), (10, 23), (11, 27)]);
+    assert_eq!(occurrences(&index, ElementKind::Variable, "l").len(), 5);
+    let offset = domain.windows(7).position(|w| w == b"(go ?l)").unwrap();
+    let variable = index.find(offset + 5).unwrap();
+    assert_eq!(index.occurrences_of(variable).count(), 3);
+}
+
+#[test]
+pub fn problem_index_test() {
+    let problem = "(define (problem p) (:domain d)
+  (:objects work - location)
+  (:htn :ordered-subtasks (and (go work)))
+  (:init (at home)))"
+        .as_bytes()
+        .to_vec();
+    let index = index_document(&problem).unwrap();
+    assert_eq!(occurrences(&index, ElementKind::Object, "work"), vec![(2, 13, true), (3, 36, false)]);
+    assert_eq!(occurrences(&index, ElementKind::Object, "home"), vec![(4, 14, false)]);
+    assert_eq!(occurrences(&index, ElementKind::Task, "go"), vec![(3, 33, false)]);
+    assert_eq!(occurrences(&index, ElementKind::Predicate, "at"), vec![(4, 11, false)]);
+    assert_eq!(occurrences(&index, ElementKind::Type, "location"), vec![(2, 20, false)]);
+}
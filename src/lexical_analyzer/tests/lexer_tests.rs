@@ -260,4 +260,128 @@ mod lexer_test {
             assert_eq!(peek.unwrap(), actual.unwrap());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn token_position_test() {
+        let program = String::from("(:action ; comment\n  ?var_1 move)").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        // (col, end_col, offset, end_offset, line)
+        let expected = vec![
+            (1, 2, 0, 1, 1),
+            (2, 9, 1, 8, 1),
+            (3, 9, 21, 27, 2),
+            (10, 14, 28, 32, 2),
+            (14, 15, 32, 33, 2),
+        ];
+        for (column, end_column, offset, end_offset, line) in expected {
+            let _ = lexer.get_token();
+            let position = lexer.get_last_token_position();
+            assert_eq!(position.line, line);
+            assert_eq!(position.column, column);
+            assert_eq!(position.end_column, end_column);
+            assert_eq!(position.offset, offset);
+            assert_eq!(position.end_offset, end_offset);
+        }
+    }
+
+    #[test]
+    pub fn numeric_token_test() {
+        let program = String::from("(:functions (increase (total-cost) 1.5) (* 3 ?x) (/ 1e3 x1))").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let expected = vec![
+            Token::Punctuator(PunctuationType::LParentheses),
+            Token::Keyword(KeywordName::Functions),
+            Token::Punctuator(PunctuationType::LParentheses),
+            Token::Operator(OperationType::Increase),
+            Token::Punctuator(PunctuationType::LParentheses),
+            Token::Identifier("total-cost"),
+            Token::Punctuator(PunctuationType::RParentheses),
+            Token::Number("1.5"),
+            Token::Punctuator(PunctuationType::RParentheses),
+            Token::Punctuator(PunctuationType::LParentheses),
+            Token::Operator(OperationType::Times),
+            Token::Number("3"),
+            Token::Identifier("x"),
+            Token::Punctuator(PunctuationType::RParentheses),
+            Token::Punctuator(PunctuationType::LParentheses),
+            Token::Operator(OperationType::Divide),
+            Token::Number("1e3"),
+            Token::Identifier("x1"),
+            Token::Punctuator(PunctuationType::RParentheses),
+            Token::Punctuator(PunctuationType::RParentheses),
+            Token::EOF,
+        ];
+        for token in expected {
+            assert_eq!(lexer.get_token().unwrap(), token);
+        }
+        // '.' is only valid in numbers
+        let program = String::from("a.b").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        assert!(lexer.get_token().is_err());
+        let program = String::from(":numeric-fluents :action-costs :metric ").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        assert_eq!(lexer.get_token().unwrap(), Token::Requirement(RequirementType::NumericFluents));
+        assert_eq!(lexer.get_token().unwrap(), Token::Requirement(RequirementType::ActionCosts));
+        assert_eq!(lexer.get_token().unwrap(), Token::Keyword(KeywordName::Metric));
+    }
+
+    #[test]
+    pub fn conditional_effect_token_test() {
+        let program = String::from(":conditional-effects (when (imply ").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        assert_eq!(lexer.get_token().unwrap(), Token::Requirement(RequirementType::ConditionalEffects));
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::LParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Operator(OperationType::When));
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::LParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Operator(OperationType::Implication));
+    }
+
+    #[test]
+    pub fn pddl_requirement_token_test() {
+        let program = String::from(
+            ":adl :existential-preconditions :quantified-preconditions :disjunctive-preconditions \
+             :fluents :object-fluents :durative-actions :duration-inequalities :continuous-effects \
+             :derived-predicates :timed-initial-literals :preferences :constraints ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let expected = [
+            RequirementType::ADL,
+            RequirementType::ExistentialPreconditions,
+            RequirementType::QuantifiedPreconditions,
+            RequirementType::DisjunctivePreconditions,
+            RequirementType::Fluents,
+            RequirementType::ObjectFluents,
+            RequirementType::DurativeActions,
+            RequirementType::DurationInequalities,
+            RequirementType::ContinuousEffects,
+            RequirementType::DerivedPredicates,
+            RequirementType::TimedInitialLiterals,
+            RequirementType::Preferences,
+        ];
+        for requirement in expected {
+            assert_eq!(lexer.get_token().unwrap(), Token::Requirement(requirement));
+        }
+        // the parser reads it as a requirement inside ":requirements"
+        assert_eq!(lexer.get_token().unwrap(), Token::Keyword(KeywordName::Constraints));
+    }
+
+    #[test]
+    pub fn temporal_keyword_token_test() {
+        let program = String::from("(:durative-action a :duration (= ?duration 2) :condition (at start ").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::LParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Keyword(KeywordName::DurativeAction));
+        assert_eq!(lexer.get_token().unwrap(), Token::Identifier("a"));
+        assert_eq!(lexer.get_token().unwrap(), Token::Keyword(KeywordName::Duration));
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::LParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Operator(OperationType::Equal));
+        assert_eq!(lexer.get_token().unwrap(), Token::Identifier("duration"));
+        assert_eq!(lexer.get_token().unwrap(), Token::Number("2"));
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::RParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Keyword(KeywordName::Condition));
+        assert_eq!(lexer.get_token().unwrap(), Token::Punctuator(PunctuationType::LParentheses));
+        assert_eq!(lexer.get_token().unwrap(), Token::Identifier("at"));
+        assert_eq!(lexer.get_token().unwrap(), Token::Identifier("start"));
+    }
+}
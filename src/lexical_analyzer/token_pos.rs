@@ -2,7 +2,13 @@ use std::fmt;
 
 use serde::Serialize;
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize)]
 pub struct TokenPosition {
     pub line: u32,
-}
\ No newline at end of file
+    // columns are 1-based, and the end column is exclusive
+    pub column: u32,
+    pub end_column: u32,
+    // byte offsets of the token in the source (end is exclusive)
+    pub offset: usize,
+    pub end_offset: usize,
+}
@@ -6,6 +6,7 @@ use serde::Serialize;
 pub enum Token<'a> {
     Keyword(KeywordName),
     Identifier(&'a str),
+    Number(&'a str),
     Operator(OperationType),
     Punctuator(PunctuationType),
     Requirement(RequirementType),
@@ -17,6 +18,7 @@ impl <'a> fmt::Display for Token<'a> {
         match self {
             Token::Keyword(keyword) => write!(fmt, "Keyword {}", keyword),
             Token::Identifier(id) => write!(fmt, "Identifier {}", id),
+            Token::Number(number) => write!(fmt, "Number {}", number),
             Token::Operator(op) => write!(fmt, "{}", op),
             Token::Punctuator(punc) => write!(fmt, "{}", punc),
             Token::Requirement(req) => write!(fmt, "Requirement {}", req),
@@ -42,7 +44,7 @@ impl fmt::Display for PunctuationType {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
 pub enum OperationType {
     // Logic
     Or,
@@ -52,12 +54,25 @@ pub enum OperationType {
     ForAll,
     Exists,
     Implication,
+    // Conditional Effects
+    When,
     // Ordering
     Equal,
     LessThan,
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    // Arithmetic (the minus is lexed as a dash)
+    Plus,
+    Minus,
+    Times,
+    Divide,
+    // Numeric Effects
+    Increase,
+    Decrease,
+    Assign,
+    ScaleUp,
+    ScaleDown,
 }
 
 impl fmt::Display for OperationType {
@@ -69,12 +84,22 @@ impl fmt::Display for OperationType {
             OperationType::Xor => write!(fmt, "oneof"),
             OperationType::ForAll => write!(fmt, "forall"),
             OperationType::Exists => write!(fmt, "exists"),
-            OperationType::Implication => write!(fmt, "when"),
+            OperationType::Implication => write!(fmt, "imply"),
+            OperationType::When => write!(fmt, "when"),
             OperationType::Equal => write!(fmt, "="),
             OperationType::LessThan => write!(fmt, "<"),
             OperationType::GreaterThan => write!(fmt, ">"),
             OperationType::LessThanOrEqual => write!(fmt, "<="),
             OperationType::GreaterThanOrEqual => write!(fmt, ">="),
+            OperationType::Plus => write!(fmt, "+"),
+            OperationType::Minus => write!(fmt, "-"),
+            OperationType::Times => write!(fmt, "*"),
+            OperationType::Divide => write!(fmt, "/"),
+            OperationType::Increase => write!(fmt, "increase"),
+            OperationType::Decrease => write!(fmt, "decrease"),
+            OperationType::Assign => write!(fmt, "assign"),
+            OperationType::ScaleUp => write!(fmt, "scale-up"),
+            OperationType::ScaleDown => write!(fmt, "scale-down"),
         }
     }
 }
@@ -87,7 +112,85 @@ pub enum RequirementType {
     NegativePreconditions,
     UniversalPreconditions,
     Equality,
-    STRIPS
+    #[allow(clippy::upper_case_acronyms)]
+    STRIPS,
+    NumericFluents,
+    ActionCosts,
+    ConditionalEffects,
+    ExistentialPreconditions,
+    // universal and existential preconditions
+    QuantifiedPreconditions,
+    DisjunctivePreconditions,
+    // strips, typing, negative, disjunctive, quantified preconditions, equality and conditional effects
+    #[allow(clippy::upper_case_acronyms)]
+    ADL,
+    ObjectFluents,
+    // numeric and object fluents
+    Fluents,
+    DurativeActions,
+    DurationInequalities,
+    ContinuousEffects,
+    DerivedPredicates,
+    TimedInitialLiterals,
+    Preferences,
+    Constraints,
+}
+
+impl RequirementType {
+    pub const ALL: [RequirementType; 23] = [
+        RequirementType::MethodPreconditions,
+        RequirementType::Hierarchy,
+        RequirementType::TypedObjects,
+        RequirementType::NegativePreconditions,
+        RequirementType::UniversalPreconditions,
+        RequirementType::Equality,
+        RequirementType::STRIPS,
+        RequirementType::NumericFluents,
+        RequirementType::ActionCosts,
+        RequirementType::ConditionalEffects,
+        RequirementType::ExistentialPreconditions,
+        RequirementType::QuantifiedPreconditions,
+        RequirementType::DisjunctivePreconditions,
+        RequirementType::ADL,
+        RequirementType::ObjectFluents,
+        RequirementType::Fluents,
+        RequirementType::DurativeActions,
+        RequirementType::DurationInequalities,
+        RequirementType::ContinuousEffects,
+        RequirementType::DerivedPredicates,
+        RequirementType::TimedInitialLiterals,
+        RequirementType::Preferences,
+        RequirementType::Constraints,
+    ];
+
+    // the requirements that declaring this one declares as well
+    pub fn implies(&self) -> &'static [RequirementType] {
+        match self {
+            RequirementType::ADL => &[
+                RequirementType::STRIPS,
+                RequirementType::TypedObjects,
+                RequirementType::NegativePreconditions,
+                RequirementType::DisjunctivePreconditions,
+                RequirementType::Equality,
+                RequirementType::QuantifiedPreconditions,
+                RequirementType::ExistentialPreconditions,
+                RequirementType::UniversalPreconditions,
+                RequirementType::ConditionalEffects,
+            ],
+            RequirementType::QuantifiedPreconditions => &[
+                RequirementType::ExistentialPreconditions,
+                RequirementType::UniversalPreconditions,
+            ],
+            RequirementType::Fluents => &[
+                RequirementType::NumericFluents,
+                RequirementType::ObjectFluents,
+                RequirementType::ActionCosts,
+            ],
+            // action costs are a special case of numeric fluents
+            RequirementType::NumericFluents => &[RequirementType::ActionCosts],
+            _ => &[],
+        }
+    }
 }
 
 impl fmt::Display for RequirementType {
@@ -100,6 +203,22 @@ impl fmt::Display for RequirementType {
             RequirementType::UniversalPreconditions => ":universal-preconditions",
             RequirementType::Equality => ":equality",
             RequirementType::STRIPS => ":strips",
+            RequirementType::NumericFluents => ":numeric-fluents",
+            RequirementType::ActionCosts => ":action-costs",
+            RequirementType::ConditionalEffects => ":conditional-effects",
+            RequirementType::ExistentialPreconditions => ":existential-preconditions",
+            RequirementType::QuantifiedPreconditions => ":quantified-preconditions",
+            RequirementType::DisjunctivePreconditions => ":disjunctive-preconditions",
+            RequirementType::ADL => ":adl",
+            RequirementType::ObjectFluents => ":object-fluents",
+            RequirementType::Fluents => ":fluents",
+            RequirementType::DurativeActions => ":durative-actions",
+            RequirementType::DurationInequalities => ":duration-inequalities",
+            RequirementType::ContinuousEffects => ":continuous-effects",
+            RequirementType::DerivedPredicates => ":derived-predicates",
+            RequirementType::TimedInitialLiterals => ":timed-initial-literals",
+            RequirementType::Preferences => ":preferences",
+            RequirementType::Constraints => ":constraints",
         };
         write!(f, "{}", requirement)
     }
@@ -127,7 +246,12 @@ pub enum KeywordName {
     OrderedSubtasks, // either "ordered-tasks" or "ordered-subtasks"
     Ordering,
     Constraints,
-    Goal
+    Goal,
+    Functions,
+    Metric,
+    DurativeAction,
+    Duration,
+    Condition,
 }
 
 impl fmt::Display for KeywordName {
@@ -154,6 +278,11 @@ impl fmt::Display for KeywordName {
             KeywordName::Ordering => ":ordering",
             KeywordName::Constraints => ":constraints",
             KeywordName::Goal => ":goal",
+            KeywordName::Functions => ":functions",
+            KeywordName::Metric => ":metric",
+            KeywordName::DurativeAction => ":durative-action",
+            KeywordName::Duration => ":duration",
+            KeywordName::Condition => ":condition",
         };
         write!(f, "{}", keyword)
     }
@@ -5,6 +5,9 @@ use super::*;
 pub struct LexicalAnalyzer<'a> {
     program: &'a Vec<u8>,
     cursor: Cell<usize>,
+    line: Cell<u32>,
+    // byte offset at which the current line starts
+    line_start: Cell<usize>,
     last_token_pos: Cell<TokenPosition>,
 }
 
@@ -13,17 +16,27 @@ impl<'a> LexicalAnalyzer<'a> {
         LexicalAnalyzer {
             program,
             cursor: Cell::new(0),
-            last_token_pos: Cell::new(TokenPosition { line: 1 }),
+            line: Cell::new(1),
+            line_start: Cell::new(0),
+            last_token_pos: Cell::new(TokenPosition {
+                line: 1,
+                column: 1,
+                end_column: 1,
+                offset: 0,
+                end_offset: 0,
+            }),
         }
     }
 
     // sets cursor to its initial position at zero
     pub fn reset_cursor(&self) {
         self.cursor.set(0);
+        self.line.set(1);
+        self.line_start.set(0);
     }
 
     // get the next token without advancing the cursor
-    pub fn lookahead(&self) -> Result<Token, LexicalError> {
+    pub fn lookahead(&self) -> Result<Token<'a>, LexicalError> {
         return self.parse(true);
     }
 
@@ -31,12 +44,36 @@ impl<'a> LexicalAnalyzer<'a> {
         self.last_token_pos.get()
     }
 
-    pub fn get_token(&self) -> Result<Token, LexicalError> {
+    pub fn get_token(&self) -> Result<Token<'a>, LexicalError> {
         return self.parse(false);
     }
 
-    fn parse(&self, peek: bool) -> Result<Token, LexicalError> {
+    fn parse(&self, peek: bool) -> Result<Token<'a>, LexicalError> {
         self.skip_whitespaces();
+        let start = self.cursor.get();
+        let token = self.scan(peek);
+        if !peek {
+            match &token {
+                Ok(_) => self.last_token_pos.set(self.span(start, self.cursor.get())),
+                Err(error) => self.last_token_pos.set(error.position),
+            }
+        }
+        token
+    }
+
+    // span of the lexeme between the two byte offsets (on the current line)
+    fn span(&self, start: usize, end: usize) -> TokenPosition {
+        let line_start = self.line_start.get();
+        TokenPosition {
+            line: self.line.get(),
+            column: (start - line_start) as u32 + 1,
+            end_column: (end - line_start) as u32 + 1,
+            offset: start,
+            end_offset: end,
+        }
+    }
+
+    fn scan(&self, peek: bool) -> Result<Token<'a>, LexicalError> {
         if self.cursor.get() == self.program.len() {
             return Ok(Token::EOF);
         }
@@ -49,6 +86,10 @@ impl<'a> LexicalAnalyzer<'a> {
                 '-' => Ok(Token::Punctuator(PunctuationType::Dash)),
                 '(' => Ok(Token::Punctuator(PunctuationType::LParentheses)),
                 ')' => Ok(Token::Punctuator(PunctuationType::RParentheses)),
+                // Arithmetic Operators
+                '+' => Ok(Token::Operator(OperationType::Plus)),
+                '*' => Ok(Token::Operator(OperationType::Times)),
+                '/' => Ok(Token::Operator(OperationType::Divide)),
                 // Ordering Relations
                 p @ ('<' | '>' | '=') => Ok(Token::Operator(self.ordering_type(&p, peek))),
                 // Variables
@@ -88,6 +129,37 @@ impl<'a> LexicalAnalyzer<'a> {
                         "universal-preconditions" => {
                             Ok(Token::Requirement(RequirementType::UniversalPreconditions))
                         }
+                        "numeric-fluents" => Ok(Token::Requirement(RequirementType::NumericFluents)),
+                        "action-costs" => Ok(Token::Requirement(RequirementType::ActionCosts)),
+                        "conditional-effects" => {
+                            Ok(Token::Requirement(RequirementType::ConditionalEffects))
+                        }
+                        "existential-preconditions" => {
+                            Ok(Token::Requirement(RequirementType::ExistentialPreconditions))
+                        }
+                        "quantified-preconditions" => {
+                            Ok(Token::Requirement(RequirementType::QuantifiedPreconditions))
+                        }
+                        "disjunctive-preconditions" => {
+                            Ok(Token::Requirement(RequirementType::DisjunctivePreconditions))
+                        }
+                        "adl" => Ok(Token::Requirement(RequirementType::ADL)),
+                        "object-fluents" => Ok(Token::Requirement(RequirementType::ObjectFluents)),
+                        "fluents" => Ok(Token::Requirement(RequirementType::Fluents)),
+                        "durative-actions" => Ok(Token::Requirement(RequirementType::DurativeActions)),
+                        "duration-inequalities" => {
+                            Ok(Token::Requirement(RequirementType::DurationInequalities))
+                        }
+                        "continuous-effects" => {
+                            Ok(Token::Requirement(RequirementType::ContinuousEffects))
+                        }
+                        "derived-predicates" => {
+                            Ok(Token::Requirement(RequirementType::DerivedPredicates))
+                        }
+                        "timed-initial-literals" => {
+                            Ok(Token::Requirement(RequirementType::TimedInitialLiterals))
+                        }
+                        "preferences" => Ok(Token::Requirement(RequirementType::Preferences)),
                         // Keywords
                         "requirements" => Ok(Token::Keyword(KeywordName::Requirements)),
                         "objects" => Ok(Token::Keyword(KeywordName::Objects)),
@@ -109,24 +181,20 @@ impl<'a> LexicalAnalyzer<'a> {
                         "ordering" | "order" => Ok(Token::Keyword(KeywordName::Ordering)),
                         "constraints" => Ok(Token::Keyword(KeywordName::Constraints)),
                         "goal" => Ok(Token::Keyword(KeywordName::Goal)),
+                        "functions" => Ok(Token::Keyword(KeywordName::Functions)),
+                        "metric" => Ok(Token::Keyword(KeywordName::Metric)),
+                        "durative-action" => Ok(Token::Keyword(KeywordName::DurativeAction)),
+                        "duration" => Ok(Token::Keyword(KeywordName::Duration)),
+                        "condition" => Ok(Token::Keyword(KeywordName::Condition)),
                         "domain" => return Ok(Token::Keyword(KeywordName::Domain)),
                         "problem" => return Ok(Token::Keyword(KeywordName::Problem)),
                         _ => Err(LexicalError {
                             error_type: LexicalErrorType::InvalidKeyword,
                             lexeme: lexeme.to_string(),
-                            position: self.last_token_pos.get(),
+                            position: self.span(init_cur_pos - 1, new_cur_pos),
                         }),
                     }
                 }
-                // Comment
-                ';' => {
-                    let mut current = self.program[self.cursor.get()] as char;
-                    while current != '\n' {
-                        self.cursor.set(self.cursor.get() + 1);
-                        current = self.program[self.cursor.get()] as char;
-                    }
-                    return self.parse(peek);
-                }
                 // Other
                 _ => {
                     let mut init_cur_pos = self.cursor.get() - 1;
@@ -142,8 +210,10 @@ impl<'a> LexicalAnalyzer<'a> {
                         "define" => return Ok(Token::Keyword(KeywordName::Define)),
                         "domain" => return Ok(Token::Keyword(KeywordName::Domain)),
                         "problem" => return Ok(Token::Keyword(KeywordName::Problem)),
+                        // Numbers
+                        _ if LexicalAnalyzer::is_number(lexeme) => Ok(Token::Number(lexeme)),
                         _ => {
-                            // Logical Operators
+                            // Logical and Numeric Operators
                             match LexicalAnalyzer::is_logical_operator(&lexeme) {
                                 Some(x) => return Ok(Token::Operator(x)),
                                 // Identifier
@@ -157,7 +227,7 @@ impl<'a> LexicalAnalyzer<'a> {
                                         Err(LexicalError {
                                             error_type: LexicalErrorType::InvalidIdentifier,
                                             lexeme: lexeme.to_string(),
-                                            position: self.last_token_pos.get(),
+                                            position: self.span(init_cur_pos, new_cur_pos),
                                         })
                                     }
                                 }
@@ -172,7 +242,7 @@ impl<'a> LexicalAnalyzer<'a> {
     }
 
     // get next lexeme and new cursor position (to commit peek)
-    fn peek_lexeme(&self, init_cur_pos: usize) -> Result<(&str, usize), LexicalError> {
+    fn peek_lexeme(&self, init_cur_pos: usize) -> Result<(&'a str, usize), LexicalError> {
         let mut cursor_pos = init_cur_pos;
         let mut next_ch = self.program[cursor_pos] as char;
         let mut is_invalid = false;
@@ -198,19 +268,16 @@ impl<'a> LexicalAnalyzer<'a> {
                 break;
             }
         }
-        if is_invalid {
-            return Err(LexicalError {
+        let lexeme = from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap();
+        // decimal numbers (e.g. "1.5") are the only lexemes with a '.'
+        if is_invalid && !LexicalAnalyzer::is_number(lexeme) {
+            Err(LexicalError {
                 error_type: LexicalErrorType::InvalidIdentifier,
-                lexeme: from_utf8(&self.program[init_cur_pos..cursor_pos])
-                    .unwrap()
-                    .to_string(),
-                position: self.last_token_pos.get(),
-            });
+                lexeme: lexeme.to_string(),
+                position: self.span(init_cur_pos, cursor_pos),
+            })
         } else {
-            return Ok((
-                from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap(),
-                cursor_pos,
-            ));
+            Ok((lexeme, cursor_pos))
         }
     }
 
@@ -222,15 +289,24 @@ impl<'a> LexicalAnalyzer<'a> {
         Some(current)
     }
 
+    // skips whitespaces and comments
     fn skip_whitespaces(&self) {
         while self.cursor.get() != self.program.len() {
             let current = self.program[self.cursor.get()] as char;
+            if current == ';' {
+                // comments run until the end of line
+                while self.cursor.get() != self.program.len()
+                    && self.program[self.cursor.get()] as char != '\n'
+                {
+                    self.cursor.set(self.cursor.get() + 1);
+                }
+                continue;
+            }
             if !LexicalAnalyzer::is_whitespace(&current) {
                 break;
             } else if current == '\n' {
-                self.last_token_pos.set(TokenPosition {
-                    line: self.last_token_pos.get().line + 1,
-                });
+                self.line.set(self.line.get() + 1);
+                self.line_start.set(self.cursor.get() + 1);
             }
             self.cursor.set(self.cursor.get() + 1);
         }
@@ -245,10 +321,20 @@ impl<'a> LexicalAnalyzer<'a> {
             "forall" => Some(OperationType::ForAll),
             "exists" => Some(OperationType::Exists),
             "imply" => Some(OperationType::Implication),
+            "when" => Some(OperationType::When),
+            "increase" => Some(OperationType::Increase),
+            "decrease" => Some(OperationType::Decrease),
+            "assign" => Some(OperationType::Assign),
+            "scale-up" => Some(OperationType::ScaleUp),
+            "scale-down" => Some(OperationType::ScaleDown),
             _ => None,
         }
     }
 
+    fn is_number(lexeme: &str) -> bool {
+        lexeme.starts_with(|c: char| c.is_ascii_digit()) && lexeme.parse::<f64>().is_ok()
+    }
+
     fn ordering_type(&self, c: &char, peek: bool) -> OperationType {
         match c {
             '<' => match self.peek_next_char() {
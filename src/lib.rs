@@ -3,6 +3,10 @@ mod output;
 mod semantic_analyzer;
 mod syntactic_analyzer;
 
+mod plan_verifier;
+mod grounding;
+mod formatter;
+
 mod language_server;
 mod hddl_json_parser;
 
@@ -13,11 +17,17 @@ use std::collections::HashMap;
 use crate::lexical_analyzer::TokenPosition;
 use lexical_analyzer::LexicalAnalyzer;
 use output::MetaData;
-pub use output::{LexicalErrorType, ParsingError, SemanticErrorType, SyntacticError, WarningType};
+pub use output::{LexicalErrorType, ParsingError, SemanticErrorType, SyntacticError, WarningType, VerificationReport, DiagnosticRenderer, Severity, Label, PlanErrorType, PlanStepError, PlanReport};
 use semantic_analyzer::*;
 use syntactic_analyzer::AbstractSyntaxTree;
 use syntactic_analyzer::FileVariant;
 use crate::hddl_json_parser::HDDLJsonParser;
+use plan_verifier::{Plan, PlanVerifier};
+use grounding::Grounder;
+use formatter::PrettyPrinter;
+pub use formatter::FormatOptions;
+pub use semantic_analyzer::VerifyOptions;
+pub use grounding::{GroundedModel, GroundFact, GroundAction, GroundTask, GroundMethod, GroundNetwork, GroundSubtask, GroundFormula, GroundEffect, GroundSnapshot};
 
 pub struct HDDLAnalyzer {}
 
@@ -56,6 +66,172 @@ impl HDDLAnalyzer {
         }
     }
 
+    // unlike `verify`, does not stop at the first syntax or semantic error
+    // (semantic analysis only runs on files without syntax errors)
+    pub fn verify_all(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> VerificationReport {
+        HDDLAnalyzer::verify_with(domain, problem, VerifyOptions::default())
+    }
+
+    // like `verify_all`, but features used without their requirement are errors
+    pub fn verify_all_strict(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> VerificationReport {
+        let options = VerifyOptions {
+            strict_requirements: true,
+            ..VerifyOptions::default()
+        };
+        HDDLAnalyzer::verify_with(domain, problem, options)
+    }
+
+    // like `verify_all`, with the checks selected by the options (e.g. for plain PDDL files)
+    pub fn verify_with(
+        domain: &Vec<u8>,
+        problem: Option<&Vec<u8>>,
+        options: VerifyOptions,
+    ) -> VerificationReport {
+        let mut report = VerificationReport::default();
+        let lexer = LexicalAnalyzer::new(&domain);
+        let domain_parser = syntactic_analyzer::Parser::new(lexer);
+        let (domain_ast, syntax_errors) = domain_parser.parse_partial();
+        if !syntax_errors.is_empty() {
+            report.domain_errors = syntax_errors;
+            return report;
+        }
+        let d = match domain_ast {
+            Some(AbstractSyntaxTree::Domain(d)) => d,
+            Some(AbstractSyntaxTree::Problem(_)) => {
+                report.domain_errors.push(ParsingError::Syntactic(SyntacticError {
+                    expected: "a domain definition".to_string(),
+                    found: "a problem definition".to_string(),
+                    position: domain_parser.tokenizer.get_last_token_position(),
+                }));
+                return report;
+            }
+            None => return report,
+        };
+        let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d)
+            .with_strict_requirements(options.strict_requirements)
+            .with_classical(options.classical)
+            .with_immutable_predicates(options.immutable_predicates);
+        let (symbol_table, domain_errors) = domain_semantic_verifier.verify_domain_all();
+        report.domain_warnings = symbol_table.warnings.clone();
+        report
+            .domain_errors
+            .extend(domain_errors.into_iter().map(ParsingError::Semantic));
+        if let Some(p) = problem {
+            let lexer = LexicalAnalyzer::new(p);
+            let problem_parser = syntactic_analyzer::Parser::new(lexer);
+            match problem_parser.parse_partial() {
+                (_, syntax_errors) if !syntax_errors.is_empty() => {
+                    report.problem_errors = syntax_errors;
+                }
+                (Some(AbstractSyntaxTree::Problem(p_ast)), _) => {
+                    let problem_semantic_verifier = ProblemSemanticAnalyzer::new(&p_ast, symbol_table.clone())
+                        .with_classical(options.classical);
+                    let (warnings, problem_errors) = problem_semantic_verifier.verify_problem_all();
+                    report.domain_warnings = warnings;
+                    report
+                        .problem_errors
+                        .extend(problem_errors.into_iter().map(ParsingError::Semantic));
+                    // grounding is only sound for verified files
+                    if options.classical && !report.has_errors() {
+                        report.problem_warnings = unreachable_goals(&d, &p_ast, &symbol_table);
+                    }
+                }
+                (Some(AbstractSyntaxTree::Domain(_)), _) => {
+                    report.problem_errors.push(ParsingError::Syntactic(SyntacticError {
+                        expected: "a problem definition".to_string(),
+                        found: "a domain definition".to_string(),
+                        position: problem_parser.tokenizer.get_last_token_position(),
+                    }));
+                }
+                (None, _) => {}
+            }
+        }
+        report
+    }
+
+    // checks the decomposition of a plan (in the IPC format), and executes its primitive steps
+    // (the domain and the problem are verified first)
+    pub fn verify_plan(
+        domain: &Vec<u8>,
+        problem: &Vec<u8>,
+        plan: &[u8],
+    ) -> Result<PlanReport, ParsingError> {
+        let lexer = LexicalAnalyzer::new(domain);
+        let domain_parser = syntactic_analyzer::Parser::new(lexer);
+        let domain_ast = match domain_parser.parse()? {
+            AbstractSyntaxTree::Domain(d) => d,
+            AbstractSyntaxTree::Problem(_) => {
+                return Err(ParsingError::Syntactic(SyntacticError {
+                    expected: "a domain definition".to_string(),
+                    found: "a problem definition".to_string(),
+                    position: domain_parser.tokenizer.get_last_token_position(),
+                }));
+            }
+        };
+        let lexer = LexicalAnalyzer::new(problem);
+        let problem_parser = syntactic_analyzer::Parser::new(lexer);
+        let problem_ast = match problem_parser.parse()? {
+            AbstractSyntaxTree::Problem(p) => p,
+            AbstractSyntaxTree::Domain(_) => {
+                return Err(ParsingError::Syntactic(SyntacticError {
+                    expected: "a problem definition".to_string(),
+                    found: "a domain definition".to_string(),
+                    position: problem_parser.tokenizer.get_last_token_position(),
+                }));
+            }
+        };
+        // plan errors are only meaningful for verified files
+        let symbol_table = DomainSemanticAnalyzer::new(&domain_ast).verify_domain()?;
+        ProblemSemanticAnalyzer::new(&problem_ast, symbol_table).verify_problem()?;
+        let plan_text = String::from_utf8_lossy(plan);
+        let (plan, mut errors) = Plan::parse(&plan_text);
+        let verifier = PlanVerifier::new(&domain_ast, &problem_ast, &plan);
+        errors.extend(verifier.verify());
+        Ok(PlanReport { errors })
+    }
+
+    // instantiates the actions, tasks and methods of a verified domain over the objects of the problem
+    pub fn ground(domain: &Vec<u8>, problem: &Vec<u8>) -> Result<GroundedModel, ParsingError> {
+        let lexer = LexicalAnalyzer::new(domain);
+        let domain_parser = syntactic_analyzer::Parser::new(lexer);
+        let domain_ast = match domain_parser.parse()? {
+            AbstractSyntaxTree::Domain(d) => d,
+            AbstractSyntaxTree::Problem(_) => {
+                return Err(ParsingError::Syntactic(SyntacticError {
+                    expected: "a domain definition".to_string(),
+                    found: "a problem definition".to_string(),
+                    position: domain_parser.tokenizer.get_last_token_position(),
+                }));
+            }
+        };
+        let lexer = LexicalAnalyzer::new(problem);
+        let problem_parser = syntactic_analyzer::Parser::new(lexer);
+        let problem_ast = match problem_parser.parse()? {
+            AbstractSyntaxTree::Problem(p) => p,
+            AbstractSyntaxTree::Domain(_) => {
+                return Err(ParsingError::Syntactic(SyntacticError {
+                    expected: "a problem definition".to_string(),
+                    found: "a domain definition".to_string(),
+                    position: problem_parser.tokenizer.get_last_token_position(),
+                }));
+            }
+        };
+        let symbol_table = DomainSemanticAnalyzer::new(&domain_ast).verify_domain()?;
+        ProblemSemanticAnalyzer::new(&problem_ast, symbol_table.clone()).verify_problem()?;
+        Ok(Grounder::new(&domain_ast, &problem_ast, &symbol_table).ground())
+    }
+
+    // prints a domain or a problem with canonical keywords and indentation (keeping its comments)
+    pub fn format(program: &Vec<u8>, options: FormatOptions) -> Result<String, ParsingError> {
+        let lexer = LexicalAnalyzer::new(program);
+        let parser = syntactic_analyzer::Parser::new(lexer);
+        let printer = PrettyPrinter::with_comments(program, options);
+        match parser.parse()? {
+            AbstractSyntaxTree::Domain(d) => Ok(printer.print_domain(&d)),
+            AbstractSyntaxTree::Problem(p) => Ok(printer.print_problem(&p)),
+        }
+    }
+
     pub fn get_metadata(
         domain: &Vec<u8>,
         problem: Option<&Vec<u8>>,
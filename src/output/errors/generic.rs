@@ -25,6 +25,32 @@ impl From<SemanticErrorType> for ParsingError {
     }
 }
 
+impl ParsingError {
+    pub fn position(&self) -> TokenPosition {
+        match self {
+            Self::Lexiacal(error) => error.position,
+            Self::Syntactic(error) => error.position,
+            Self::Semantic(error) => error.position()
+        }
+    }
+
+    // stable identifier of the error
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Lexiacal(error) => error.error_type.code(),
+            Self::Syntactic(_) => "SyntacticError",
+            Self::Semantic(error) => error.code()
+        }
+    }
+
+    pub fn labels(&self) -> Vec<Label> {
+        match self {
+            Self::Semantic(error) => error.labels(),
+            _ => vec![]
+        }
+    }
+}
+
 impl std::fmt::Display for ParsingError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
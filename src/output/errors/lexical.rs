@@ -14,6 +14,16 @@ pub struct LexicalError {
     pub position: TokenPosition,
 }
 
+impl LexicalErrorType {
+    // stable identifier of the error (i.e. its variant name)
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexicalErrorType::InvalidIdentifier => "InvalidIdentifier",
+            LexicalErrorType::InvalidKeyword => "InvalidKeyword",
+        }
+    }
+}
+
 impl fmt::Display for LexicalError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.error_type {
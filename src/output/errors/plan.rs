@@ -0,0 +1,166 @@
+use crate::lexical_analyzer::TokenPosition;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum PlanErrorType {
+    // Plan Format Errors
+    MalformedPlan(PlanStepError),
+    DuplicateStep(PlanStepError),
+    UndefinedStep(PlanStepError),
+    OrphanStep(PlanStepError),
+    // Undefined Entities
+    UndefinedAction(PlanStepError),
+    UndefinedTask(PlanStepError),
+    UndefinedMethod(PlanStepError),
+    UndefinedObject(PlanStepError),
+    // Inconsistency Errors
+    InconsistentArity(PlanStepError),
+    InconsistentArgType(PlanStepError),
+    // Decomposition Errors
+    RootMismatch(PlanStepError),
+    MethodTaskMismatch(PlanStepError),
+    SubtaskMismatch(PlanStepError),
+    OrderingViolation(PlanStepError),
+    ConstraintViolation(PlanStepError),
+    UnsatisfiedMethodPrecondition(PlanStepError),
+    // Execution Errors
+    UnsatisfiedPrecondition(PlanStepError),
+    UnsatisfiedGoal(PlanStepError),
+}
+
+// an error in a (primitive or compound) step of the plan
+#[derive(Debug)]
+pub struct PlanStepError {
+    // id of the step, if the error refers to one
+    pub step: Option<u32>,
+    pub symbol: String,
+    // what exactly went wrong
+    pub detail: String,
+    // the line of the plan that contains the step
+    pub position: TokenPosition,
+}
+
+impl PlanErrorType {
+    pub fn info(&self) -> &PlanStepError {
+        match self {
+            PlanErrorType::MalformedPlan(info)
+            | PlanErrorType::DuplicateStep(info)
+            | PlanErrorType::UndefinedStep(info)
+            | PlanErrorType::OrphanStep(info)
+            | PlanErrorType::UndefinedAction(info)
+            | PlanErrorType::UndefinedTask(info)
+            | PlanErrorType::UndefinedMethod(info)
+            | PlanErrorType::UndefinedObject(info)
+            | PlanErrorType::InconsistentArity(info)
+            | PlanErrorType::InconsistentArgType(info)
+            | PlanErrorType::RootMismatch(info)
+            | PlanErrorType::MethodTaskMismatch(info)
+            | PlanErrorType::SubtaskMismatch(info)
+            | PlanErrorType::OrderingViolation(info)
+            | PlanErrorType::ConstraintViolation(info)
+            | PlanErrorType::UnsatisfiedMethodPrecondition(info)
+            | PlanErrorType::UnsatisfiedPrecondition(info)
+            | PlanErrorType::UnsatisfiedGoal(info) => info,
+        }
+    }
+
+    // where the error occurs (in the plan file)
+    pub fn position(&self) -> TokenPosition {
+        self.info().position
+    }
+
+    // the step that failed, if any
+    pub fn step(&self) -> Option<u32> {
+        self.info().step
+    }
+
+    // stable identifier of the error (i.e. its variant name)
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlanErrorType::MalformedPlan(_) => "MalformedPlan",
+            PlanErrorType::DuplicateStep(_) => "DuplicateStep",
+            PlanErrorType::UndefinedStep(_) => "UndefinedStep",
+            PlanErrorType::OrphanStep(_) => "OrphanStep",
+            PlanErrorType::UndefinedAction(_) => "UndefinedAction",
+            PlanErrorType::UndefinedTask(_) => "UndefinedTask",
+            PlanErrorType::UndefinedMethod(_) => "UndefinedMethod",
+            PlanErrorType::UndefinedObject(_) => "UndefinedObject",
+            PlanErrorType::InconsistentArity(_) => "InconsistentArity",
+            PlanErrorType::InconsistentArgType(_) => "InconsistentArgType",
+            PlanErrorType::RootMismatch(_) => "RootMismatch",
+            PlanErrorType::MethodTaskMismatch(_) => "MethodTaskMismatch",
+            PlanErrorType::SubtaskMismatch(_) => "SubtaskMismatch",
+            PlanErrorType::OrderingViolation(_) => "OrderingViolation",
+            PlanErrorType::ConstraintViolation(_) => "ConstraintViolation",
+            PlanErrorType::UnsatisfiedMethodPrecondition(_) => "UnsatisfiedMethodPrecondition",
+            PlanErrorType::UnsatisfiedPrecondition(_) => "UnsatisfiedPrecondition",
+            PlanErrorType::UnsatisfiedGoal(_) => "UnsatisfiedGoal",
+        }
+    }
+}
+
+impl fmt::Display for PlanErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = self.info();
+        write!(f, "line {}: ", info.position.line)?;
+        if let Some(step) = info.step {
+            write!(f, "step {}: ", step)?;
+        }
+        match self {
+            PlanErrorType::MalformedPlan(info) => {
+                write!(f, "malformed plan, expected {}, but found '{}'.", info.detail, info.symbol)
+            }
+            PlanErrorType::DuplicateStep(info) => {
+                write!(f, "{} {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::UndefinedStep(info) => {
+                write!(f, "subtask {} is not defined in the plan.", info.symbol)
+            }
+            PlanErrorType::OrphanStep(info) => {
+                write!(f, "{} is not reachable from the root tasks.", info.symbol)
+            }
+            PlanErrorType::UndefinedAction(info) => {
+                write!(f, "action {} is not defined.", info.symbol)
+            }
+            PlanErrorType::UndefinedTask(info) => {
+                write!(f, "task {} is not defined.", info.symbol)
+            }
+            PlanErrorType::UndefinedMethod(info) => {
+                write!(f, "method {} is not defined.", info.symbol)
+            }
+            PlanErrorType::UndefinedObject(info) => {
+                write!(f, "object {} is not defined.", info.symbol)
+            }
+            PlanErrorType::InconsistentArity(info) => {
+                write!(f, "{} {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::InconsistentArgType(info) => {
+                write!(f, "argument {} {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::RootMismatch(info) => {
+                write!(f, "root tasks do not match the initial task network, {}.", info.detail)
+            }
+            PlanErrorType::MethodTaskMismatch(info) => {
+                write!(f, "method {} {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::SubtaskMismatch(info) => {
+                write!(f, "subtasks do not match method {}, {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::OrderingViolation(info) => {
+                write!(f, "ordering of method {} is violated, {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::ConstraintViolation(info) => {
+                write!(f, "constraint of method {} is violated, {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::UnsatisfiedMethodPrecondition(info) => {
+                write!(f, "precondition of method {} does not hold, {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::UnsatisfiedPrecondition(info) => {
+                write!(f, "precondition of action {} does not hold, {}.", info.symbol, info.detail)
+            }
+            PlanErrorType::UnsatisfiedGoal(info) => {
+                write!(f, "goal does not hold after the last step, {}.", info.detail)
+            }
+        }
+    }
+}
@@ -1,16 +1,18 @@
-use crate::lexical_analyzer::{RequirementType, TokenPosition};
+use crate::lexical_analyzer::TokenPosition;
+use crate::output::Label;
 use std::fmt;
 
 #[derive(Debug)]
 pub enum SemanticErrorType {
     // Duplicate Errors
     DuplicateObjectDeclaration(DuplicateError),
-    DuplicateRequirementDeclaration(RequirementType),
+    DuplicateRequirementDeclaration(DuplicateError),
     DuplicatePredicateDeclaration(DuplicateError),
     DuplicateActionDeclaration(DuplicateError),
     DuplicateCompoundTaskDeclaration(DuplicateError),
     DuplicateMethodDeclaration(DuplicateError),
     DuplicateParameterDeclaration(DuplicateError),
+    DuplicateFunctionDeclaration(DuplicateError),
     // Undefined Entities
     UndefinedPredicate(UndefinedSymbolError),
     UndefinedType(UndefinedSymbolError),
@@ -18,18 +20,26 @@ pub enum SemanticErrorType {
     UndefinedTask(UndefinedSymbolError),
     UndefinedParameter(UndefinedSymbolError),
     UndefinedObject(UndefinedSymbolError),
+    UndefinedFunction(UndefinedSymbolError),
+    UndeclaredRequirement(UndefinedSymbolError),
     // Inconsistency Error
     InconsistentPredicateArity(ArityError),
     InconsistentTaskArity(ArityError),
+    InconsistentFunctionArity(ArityError),
     InconsistentPredicateArgType(TypeError),
     InconsistentTaskArgType(TypeError),
+    InconsistentFunctionArgType(TypeError),
     // Ordering Errors
-    CyclicTypeDeclaration,
+    CyclicTypeDeclaration(TokenPosition),
     CyclicOrderingDeclaration(TokenPosition),
     // Complementary Errors
     ComplementaryActionEffect(TokenPosition),
     ComplementaryActionPrecondition(TokenPosition),
-    ComplementaryMethodPrecondition(TokenPosition)
+    ComplementaryMethodPrecondition(TokenPosition),
+    ComplementaryGoal(TokenPosition),
+    // Classical (PDDL) Errors
+    NonClassicalConstruct(UndefinedSymbolError),
+    MissingGoal(TokenPosition)
 }
 
 impl fmt::Display for SemanticErrorType {
@@ -39,8 +49,8 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::DuplicateObjectDeclaration(duplicate) => {
                 write!(f, "object {}", duplicate)
             }
-            SemanticErrorType::DuplicateRequirementDeclaration(req) => {
-                write!(f, "requirement {}", req)
+            SemanticErrorType::DuplicateRequirementDeclaration(duplicate) => {
+                write!(f, "requirement {}", duplicate)
             }
             SemanticErrorType::DuplicatePredicateDeclaration(duplicate) => {
                 write!(f, "predicate {}", duplicate)
@@ -57,6 +67,9 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::DuplicateParameterDeclaration(duplicate) => {
                 write!(f, "parameter {}", duplicate)
             }
+            SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => {
+                write!(f, "function {}", duplicate)
+            }
             // Undefined Entities
             SemanticErrorType::UndefinedPredicate(undefined) => {
                 write!(
@@ -80,6 +93,12 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::UndefinedObject(undefined) => {
                 write!(f, "line {}: object {} is not defined.", undefined.position.line, undefined.symbol)
             }
+            SemanticErrorType::UndefinedFunction(undefined) => {
+                write!(f, "line {}: function {} is not defined.", undefined.position.line, undefined.symbol)
+            }
+            SemanticErrorType::UndeclaredRequirement(undefined) => {
+                write!(f, "line {}: requirement {} is used, but not declared.", undefined.position.line, undefined.symbol)
+            }
             // Inconsistency Error
             SemanticErrorType::InconsistentPredicateArity(ar_error) => {
                 write!(
@@ -95,13 +114,21 @@ impl fmt::Display for SemanticErrorType {
                     ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
                 )
             }
+            SemanticErrorType::InconsistentFunctionArity(ar_error) => {
+                write!(
+                    f,
+                    "line {}: function {} takes {} parameters, but {} are given.",
+                    ar_error.position.line, ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
+                )
+            }
             SemanticErrorType::InconsistentPredicateArgType(type_error) => {
                 write!(f, "{}", type_error)
             }
             SemanticErrorType::InconsistentTaskArgType(type_error) => write!(f, "{}", type_error),
+            SemanticErrorType::InconsistentFunctionArgType(type_error) => write!(f, "{}", type_error),
             // Ordering Errors
-            SemanticErrorType::CyclicTypeDeclaration => {
-                write!(f, "Type hierarchy is cyclic.")
+            SemanticErrorType::CyclicTypeDeclaration(pos) => {
+                write!(f, "line {}: type hierarchy is cyclic.", pos.line)
             }
             SemanticErrorType::CyclicOrderingDeclaration(pos) => {
                 write!(f, "line {}: task ordering is cyclic.", pos.line)
@@ -116,6 +143,110 @@ impl fmt::Display for SemanticErrorType {
             SemanticErrorType::ComplementaryMethodPrecondition(pos) => {
                 write!(f, "line {}: method has contradictory preconditions.", pos.line)
             }
+            SemanticErrorType::ComplementaryGoal(pos) => {
+                write!(f, "line {}: goal is contradictory.", pos.line)
+            }
+            // Classical (PDDL) Errors
+            SemanticErrorType::NonClassicalConstruct(undefined) => {
+                write!(f, "line {}: {} is not allowed in a classical (PDDL) model.", undefined.position.line, undefined.symbol)
+            }
+            SemanticErrorType::MissingGoal(pos) => {
+                write!(f, "line {}: classical problem does not have a goal.", pos.line)
+            }
+        }
+    }
+}
+
+impl SemanticErrorType {
+    // where the error occurs
+    pub fn position(&self) -> TokenPosition {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(duplicate)
+            | SemanticErrorType::DuplicateRequirementDeclaration(duplicate)
+            | SemanticErrorType::DuplicatePredicateDeclaration(duplicate)
+            | SemanticErrorType::DuplicateActionDeclaration(duplicate)
+            | SemanticErrorType::DuplicateCompoundTaskDeclaration(duplicate)
+            | SemanticErrorType::DuplicateMethodDeclaration(duplicate)
+            | SemanticErrorType::DuplicateParameterDeclaration(duplicate)
+            | SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => duplicate.second_pos,
+            SemanticErrorType::UndefinedPredicate(undefined)
+            | SemanticErrorType::UndefinedType(undefined)
+            | SemanticErrorType::UndefinedSubtask(undefined)
+            | SemanticErrorType::UndefinedTask(undefined)
+            | SemanticErrorType::UndefinedParameter(undefined)
+            | SemanticErrorType::UndefinedObject(undefined)
+            | SemanticErrorType::UndefinedFunction(undefined)
+            | SemanticErrorType::UndeclaredRequirement(undefined)
+            | SemanticErrorType::NonClassicalConstruct(undefined) => undefined.position,
+            SemanticErrorType::InconsistentPredicateArity(ar_error)
+            | SemanticErrorType::InconsistentTaskArity(ar_error)
+            | SemanticErrorType::InconsistentFunctionArity(ar_error) => ar_error.position,
+            SemanticErrorType::InconsistentPredicateArgType(type_error)
+            | SemanticErrorType::InconsistentTaskArgType(type_error)
+            | SemanticErrorType::InconsistentFunctionArgType(type_error) => type_error.position,
+            SemanticErrorType::CyclicTypeDeclaration(pos)
+            | SemanticErrorType::CyclicOrderingDeclaration(pos)
+            | SemanticErrorType::ComplementaryActionEffect(pos)
+            | SemanticErrorType::ComplementaryActionPrecondition(pos)
+            | SemanticErrorType::ComplementaryMethodPrecondition(pos)
+            | SemanticErrorType::ComplementaryGoal(pos)
+            | SemanticErrorType::MissingGoal(pos) => *pos,
+        }
+    }
+
+    // stable identifier of the error (i.e. its variant name)
+    pub fn code(&self) -> &'static str {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(_) => "DuplicateObjectDeclaration",
+            SemanticErrorType::DuplicateRequirementDeclaration(_) => "DuplicateRequirementDeclaration",
+            SemanticErrorType::DuplicatePredicateDeclaration(_) => "DuplicatePredicateDeclaration",
+            SemanticErrorType::DuplicateActionDeclaration(_) => "DuplicateActionDeclaration",
+            SemanticErrorType::DuplicateCompoundTaskDeclaration(_) => "DuplicateCompoundTaskDeclaration",
+            SemanticErrorType::DuplicateMethodDeclaration(_) => "DuplicateMethodDeclaration",
+            SemanticErrorType::DuplicateParameterDeclaration(_) => "DuplicateParameterDeclaration",
+            SemanticErrorType::DuplicateFunctionDeclaration(_) => "DuplicateFunctionDeclaration",
+            SemanticErrorType::UndefinedPredicate(_) => "UndefinedPredicate",
+            SemanticErrorType::UndefinedType(_) => "UndefinedType",
+            SemanticErrorType::UndefinedSubtask(_) => "UndefinedSubtask",
+            SemanticErrorType::UndefinedTask(_) => "UndefinedTask",
+            SemanticErrorType::UndefinedParameter(_) => "UndefinedParameter",
+            SemanticErrorType::UndefinedObject(_) => "UndefinedObject",
+            SemanticErrorType::UndefinedFunction(_) => "UndefinedFunction",
+            SemanticErrorType::UndeclaredRequirement(_) => "UndeclaredRequirement",
+            SemanticErrorType::InconsistentPredicateArity(_) => "InconsistentPredicateArity",
+            SemanticErrorType::InconsistentTaskArity(_) => "InconsistentTaskArity",
+            SemanticErrorType::InconsistentFunctionArity(_) => "InconsistentFunctionArity",
+            SemanticErrorType::InconsistentPredicateArgType(_) => "InconsistentPredicateArgType",
+            SemanticErrorType::InconsistentTaskArgType(_) => "InconsistentTaskArgType",
+            SemanticErrorType::InconsistentFunctionArgType(_) => "InconsistentFunctionArgType",
+            SemanticErrorType::CyclicTypeDeclaration(_) => "CyclicTypeDeclaration",
+            SemanticErrorType::CyclicOrderingDeclaration(_) => "CyclicOrderingDeclaration",
+            SemanticErrorType::ComplementaryActionEffect(_) => "ComplementaryActionEffect",
+            SemanticErrorType::ComplementaryActionPrecondition(_) => "ComplementaryActionPrecondition",
+            SemanticErrorType::ComplementaryMethodPrecondition(_) => "ComplementaryMethodPrecondition",
+            SemanticErrorType::ComplementaryGoal(_) => "ComplementaryGoal",
+            SemanticErrorType::NonClassicalConstruct(_) => "NonClassicalConstruct",
+            SemanticErrorType::MissingGoal(_) => "MissingGoal",
+        }
+    }
+
+    // other locations related to the error
+    pub fn labels(&self) -> Vec<Label> {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(duplicate)
+            | SemanticErrorType::DuplicateRequirementDeclaration(duplicate)
+            | SemanticErrorType::DuplicatePredicateDeclaration(duplicate)
+            | SemanticErrorType::DuplicateActionDeclaration(duplicate)
+            | SemanticErrorType::DuplicateCompoundTaskDeclaration(duplicate)
+            | SemanticErrorType::DuplicateMethodDeclaration(duplicate)
+            | SemanticErrorType::DuplicateParameterDeclaration(duplicate)
+            | SemanticErrorType::DuplicateFunctionDeclaration(duplicate) => {
+                vec![Label {
+                    position: duplicate.first_pos,
+                    message: "first declared here".to_string(),
+                }]
+            }
+            _ => vec![],
         }
     }
 }
@@ -1,7 +1,13 @@
 mod errors;
 mod warnings;
 mod metadata;
+mod report;
+#[cfg(test)]
+mod tests;
+mod renderer;
 
 pub use errors::*;
 pub use warnings::*;
-pub use metadata::*;
\ No newline at end of file
+pub use metadata::*;
+pub use report::*;
+pub use renderer::*;
\ No newline at end of file
@@ -0,0 +1,206 @@
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+use serde::Serialize;
+
+// ANSI escape color codes
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+// a secondary location attached to a diagnostic (e.g. a previous declaration)
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub position: TokenPosition,
+    pub message: String,
+}
+
+// renders errors and warnings of a single file in the style of rustc, i.e.
+//
+// error[UndefinedPredicate]: predicate pred_5 is not defined.
+//  --> domain.hddl:9:41
+//   |
+// 9 |     :precondition (and (not (at ?p_1)) (pred_5))
+//   |                                         ^^^^^^
+pub struct DiagnosticRenderer<'a> {
+    file_name: &'a str,
+    source: &'a [u8],
+    color: bool,
+}
+
+impl<'a> DiagnosticRenderer<'a> {
+    pub fn new(file_name: &'a str, source: &'a [u8], color: bool) -> DiagnosticRenderer<'a> {
+        DiagnosticRenderer {
+            file_name,
+            source,
+            color,
+        }
+    }
+
+    pub fn render_error(&self, error: &ParsingError) -> String {
+        self.render(
+            Severity::Error,
+            error.code(),
+            &error.to_string(),
+            Some(error.position()),
+            &error.labels(),
+        )
+    }
+
+    pub fn render_warning(&self, warning: &WarningType) -> String {
+        self.render(
+            Severity::Warning,
+            warning.code(),
+            &warning.to_string(),
+            Some(warning.position()),
+            &[],
+        )
+    }
+
+    pub fn render_plan_error(&self, error: &PlanErrorType) -> String {
+        self.render(
+            Severity::Error,
+            error.code(),
+            &error.to_string(),
+            Some(error.position()),
+            &[],
+        )
+    }
+
+    pub fn render(
+        &self,
+        severity: Severity,
+        code: &str,
+        message: &str,
+        position: Option<TokenPosition>,
+        labels: &[Label],
+    ) -> String {
+        let (severity_name, severity_color) = match severity {
+            Severity::Error => ("error", RED),
+            Severity::Warning => ("warning", YELLOW),
+        };
+        let mut output = format!(
+            "{}{}",
+            self.paint(&format!("{}[{}]", severity_name, code), severity_color),
+            self.paint(&format!(": {}", strip_line_prefix(message)), BOLD),
+        );
+        output.push('\n');
+        let position = match position {
+            // positions of line 0 do not point to the source
+            Some(position) if position.line != 0 => position,
+            _ => {
+                output.push_str(&format!(" {} {}\n", self.paint("-->", BLUE), self.file_name));
+                return output;
+            }
+        };
+        // width of the line number gutter
+        let width = labels
+            .iter()
+            .map(|label| label.position.line)
+            .chain(std::iter::once(position.line))
+            .max()
+            .unwrap()
+            .to_string()
+            .len();
+        output.push_str(&format!(
+            "{:width$}{} {}:{}:{}\n",
+            "",
+            self.paint("-->", BLUE),
+            self.file_name,
+            position.line,
+            position.column,
+            width = width
+        ));
+        output.push_str(&self.snippet(&position, '^', severity_color, "", width));
+        for label in labels.iter().filter(|label| label.position.line != 0) {
+            output.push_str(&self.snippet(&label.position, '-', BLUE, &label.message, width));
+        }
+        output
+    }
+
+    // the source line of the position, underlined with the marker
+    fn snippet(
+        &self,
+        position: &TokenPosition,
+        marker: char,
+        color: &str,
+        message: &str,
+        width: usize,
+    ) -> String {
+        let line = match self.source_line(position.line) {
+            Some(line) => line,
+            None => return String::new(),
+        };
+        let gutter = self.paint(&format!("{:width$} |", "", width = width), BLUE);
+        // columns count bytes, while the underline is aligned by characters
+        let start = char_boundary(&line, position.column.saturating_sub(1) as usize);
+        let end = char_boundary(&line, position.end_column.saturating_sub(1) as usize).max(start);
+        // keep tabs, so that the underline is aligned with the source
+        let padding: String = line[..start]
+            .chars()
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let length = line[start..end].chars().count().max(1);
+        let mut underline = marker.to_string().repeat(length);
+        if !message.is_empty() {
+            underline = format!("{} {}", underline, message);
+        }
+        format!(
+            "{}\n{} {}\n{} {}{}\n",
+            gutter,
+            self.paint(&format!("{:>width$} |", position.line, width = width), BLUE),
+            line,
+            gutter,
+            padding,
+            self.paint(&underline, color),
+        )
+    }
+
+    fn source_line(&self, line: u32) -> Option<String> {
+        let content = self.source.split(|c| *c == b'\n').nth(line as usize - 1)?;
+        Some(
+            String::from_utf8_lossy(content)
+                .trim_end_matches('\r')
+                .to_string(),
+        )
+    }
+
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.color {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// the largest character boundary of the line that is not after the byte index
+fn char_boundary(line: &str, index: usize) -> usize {
+    let mut index = index.min(line.len());
+    while !line.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+// messages are prefixed with their line number (i.e. "line 12: ..."),
+// which is redundant next to the location of the snippet
+pub fn strip_line_prefix(message: &str) -> &str {
+    let message = message.trim_end();
+    if let Some(rest) = message.strip_prefix("line ") {
+        if let Some((line, text)) = rest.split_once(": ") {
+            if line.chars().all(|c| c.is_ascii_digit()) {
+                return text;
+            }
+        }
+    }
+    message
+}
@@ -0,0 +1,160 @@
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Debug, Default)]
+pub struct VerificationReport {
+    pub domain_errors: Vec<ParsingError>,
+    pub domain_warnings: Vec<WarningType>,
+    pub problem_errors: Vec<ParsingError>,
+    pub problem_warnings: Vec<WarningType>,
+}
+
+// the result of the verification of a plan
+#[derive(Debug, Default)]
+pub struct PlanReport {
+    pub errors: Vec<PlanErrorType>,
+}
+
+// an error or a warning in a machine readable form
+#[derive(Debug, Serialize)]
+pub struct DiagnosticRecord {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    pub file: String,
+    pub position: Option<TokenPosition>,
+}
+
+impl VerificationReport {
+    pub fn has_errors(&self) -> bool {
+        !self.domain_errors.is_empty() || !self.problem_errors.is_empty()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ParsingError> {
+        self.domain_errors.iter().chain(self.problem_errors.iter())
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &WarningType> {
+        self.domain_warnings.iter().chain(self.problem_warnings.iter())
+    }
+
+    // errors and warnings of both files (errors first)
+    pub fn records(&self, domain_file: &str, problem_file: Option<&str>) -> Vec<DiagnosticRecord> {
+        let problem_file = problem_file.unwrap_or_default();
+        let mut records = vec![];
+        let errors = self
+            .domain_errors
+            .iter()
+            .map(|error| (error, domain_file))
+            .chain(self.problem_errors.iter().map(|error| (error, problem_file)));
+        for (error, file) in errors {
+            records.push(DiagnosticRecord {
+                code: error.code(),
+                severity: Severity::Error,
+                message: strip_line_prefix(&error.to_string()).to_string(),
+                file: file.to_string(),
+                position: Some(error.position()),
+            });
+        }
+        let warnings = self
+            .domain_warnings
+            .iter()
+            .map(|warning| (warning, domain_file))
+            .chain(self.problem_warnings.iter().map(|warning| (warning, problem_file)));
+        for (warning, file) in warnings {
+            records.push(DiagnosticRecord {
+                code: warning.code(),
+                severity: Severity::Warning,
+                message: strip_line_prefix(&warning.to_string()).to_string(),
+                file: file.to_string(),
+                position: Some(warning.position()),
+            });
+        }
+        records
+    }
+
+    pub fn to_json(&self, domain_file: &str, problem_file: Option<&str>) -> String {
+        let records = self.records(domain_file, problem_file);
+        serde_json::to_string_pretty(&records).unwrap()
+    }
+
+    pub fn to_sarif(&self, domain_file: &str, problem_file: Option<&str>) -> String {
+        sarif_log(&self.records(domain_file, problem_file))
+    }
+}
+
+impl PlanReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn records(&self, plan_file: &str) -> Vec<DiagnosticRecord> {
+        self.errors
+            .iter()
+            .map(|error| DiagnosticRecord {
+                code: error.code(),
+                severity: Severity::Error,
+                message: strip_line_prefix(&error.to_string()).to_string(),
+                file: plan_file.to_string(),
+                position: Some(error.position()),
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self, plan_file: &str) -> String {
+        serde_json::to_string_pretty(&self.records(plan_file)).unwrap()
+    }
+
+    pub fn to_sarif(&self, plan_file: &str) -> String {
+        sarif_log(&self.records(plan_file))
+    }
+}
+
+// SARIF v2.1.0 log with a single run
+fn sarif_log(records: &[DiagnosticRecord]) -> String {
+    let mut rules: Vec<&str> = records.iter().map(|record| record.code).collect();
+    rules.sort();
+    rules.dedup();
+    let results: Vec<serde_json::Value> = records
+        .iter()
+        .map(|record| {
+            let mut location = json!({
+                "physicalLocation": {
+                    "artifactLocation": { "uri": record.file }
+                }
+            });
+            if let Some(position) = record.position.filter(|pos| pos.line != 0) {
+                location["physicalLocation"]["region"] = json!({
+                    "startLine": position.line,
+                    "startColumn": position.column,
+                    "endColumn": position.end_column,
+                    "byteOffset": position.offset,
+                    "byteLength": position.end_offset - position.offset,
+                });
+            }
+            json!({
+                "ruleId": record.code,
+                "level": record.severity,
+                "message": { "text": record.message },
+                "locations": [location],
+            })
+        })
+        .collect();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "hddl_analyzer",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).unwrap()
+}
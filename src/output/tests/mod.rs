@@ -0,0 +1,4 @@
+mod renderer_tests;
+mod report_tests;
+
+use super::*;
\ No newline at end of file
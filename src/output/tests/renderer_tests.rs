@@ -0,0 +1,80 @@
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+fn position(line: u32, column: u32, end_column: u32) -> TokenPosition {
+    TokenPosition {
+        line,
+        column,
+        end_column,
+        offset: 0,
+        end_offset: 0,
+    }
+}
+
+#[test]
+pub fn undefined_symbol_rendering_test() {
+    let source = "(define (domain bal)\n    (:action a_1 :precondition (pred_5))\n)".as_bytes();
+    let renderer = DiagnosticRenderer::new("domain.hddl", source, false);
+    let error = ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(
+        UndefinedSymbolError {
+            symbol: "pred_5".to_string(),
+            position: position(2, 33, 39),
+        },
+    ));
+    assert_eq!(
+        renderer.render_error(&error),
+        "error[UndefinedPredicate]: predicate pred_5 is not defined.\n \
+         --> domain.hddl:2:33\n  \
+         |\n\
+         2 |     (:action a_1 :precondition (pred_5))\n  \
+         |                                 ^^^^^^\n"
+    );
+}
+
+#[test]
+pub fn multibyte_rendering_test() {
+    // columns count bytes, and "ü" takes two of them
+    let source = "(define (domain bal)\n    (:action fahrt_ü :precondition (pred_5))\n)".as_bytes();
+    let renderer = DiagnosticRenderer::new("domain.hddl", source, false);
+    let error = ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(
+        UndefinedSymbolError {
+            symbol: "pred_5".to_string(),
+            position: position(2, 38, 44),
+        },
+    ));
+    assert!(renderer.render_error(&error).ends_with(
+        "2 |     (:action fahrt_ü :precondition (pred_5))\n  \
+         |                                     ^^^^^^\n"
+    ));
+}
+
+#[test]
+pub fn duplicate_rendering_test() {
+    let source = "(:predicates (at ?x)\n\t(at ?y))".as_bytes();
+    let renderer = DiagnosticRenderer::new("domain.hddl", source, false);
+    let error = ParsingError::Semantic(SemanticErrorType::DuplicatePredicateDeclaration(
+        DuplicateError {
+            symbol: "at".to_string(),
+            first_pos: position(1, 15, 17),
+            second_pos: position(2, 3, 5),
+        },
+    ));
+    let rendered = renderer.render_error(&error);
+    // the underline keeps the tabs of the source line
+    assert!(rendered.contains("2 | \t(at ?y))\n  | \t ^^\n"));
+    assert!(rendered.contains("1 | (:predicates (at ?x)\n  |               -- first declared here\n"));
+    // no color codes outside of terminals
+    assert!(!rendered.contains("\x1b["));
+}
+
+#[test]
+pub fn colored_rendering_test() {
+    let source = "(define (domain bal))".as_bytes();
+    let renderer = DiagnosticRenderer::new("domain.hddl", source, true);
+    let warning = WarningType::NoPrimitiveRefinement(WarningInfo {
+        symbol: "deliver".to_string(),
+        position: position(1, 17, 20),
+    });
+    let rendered = renderer.render_warning(&warning);
+    assert!(rendered.starts_with("\x1b[1;33mwarning[NoPrimitiveRefinement]\x1b[0m"));
+}
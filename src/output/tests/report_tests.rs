@@ -0,0 +1,64 @@
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+fn sample_report() -> VerificationReport {
+    let position = TokenPosition {
+        line: 3,
+        column: 5,
+        end_column: 9,
+        offset: 40,
+        end_offset: 44,
+    };
+    VerificationReport {
+        domain_errors: vec![],
+        domain_warnings: vec![WarningType::NoPrimitiveRefinement(WarningInfo {
+            symbol: "deliver".to_string(),
+            position,
+        })],
+        problem_errors: vec![ParsingError::Semantic(SemanticErrorType::UndefinedObject(
+            UndefinedSymbolError {
+                symbol: "truck".to_string(),
+                position,
+            },
+        ))],
+        problem_warnings: vec![],
+    }
+}
+
+#[test]
+pub fn json_report_test() {
+    let report = sample_report();
+    assert!(report.has_errors());
+    let json: serde_json::Value =
+        serde_json::from_str(&report.to_json("domain.hddl", Some("p1.hddl"))).unwrap();
+    let records = json.as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    // errors come first
+    assert_eq!(records[0]["code"], "UndefinedObject");
+    assert_eq!(records[0]["severity"], "error");
+    assert_eq!(records[0]["file"], "p1.hddl");
+    assert_eq!(records[0]["message"], "object truck is not defined.");
+    assert_eq!(records[0]["position"]["line"], 3);
+    assert_eq!(records[0]["position"]["column"], 5);
+    assert_eq!(records[1]["code"], "NoPrimitiveRefinement");
+    assert_eq!(records[1]["severity"], "warning");
+    assert_eq!(records[1]["file"], "domain.hddl");
+}
+
+#[test]
+pub fn sarif_report_test() {
+    let report = sample_report();
+    let sarif: serde_json::Value =
+        serde_json::from_str(&report.to_sarif("domain.hddl", Some("p1.hddl"))).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "UndefinedObject");
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "p1.hddl");
+    assert_eq!(location["region"]["startLine"], 3);
+    assert_eq!(location["region"]["startColumn"], 5);
+    assert_eq!(location["region"]["endColumn"], 9);
+}
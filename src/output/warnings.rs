@@ -3,42 +3,86 @@ use crate::lexical_analyzer::TokenPosition;
 #[derive(Debug, Clone)]
 pub enum WarningType {
     // Action Errors
-    // TODO: implement
-    ImmutablePredicate(String),
+    ImmutablePredicate(WarningInfo),
     // Compound Task errors
     NoPrimitiveRefinement(WarningInfo),
     // Redundant Elements
-    // TODO: implement
-    UnusedType(String),
-    // TODO: implement
-    UnusedPredicate(String),
-    // TODO: implement
-    UnusedParameter(String),
-    // TODO: implement
-    RedundantEffect
+    UnusedType(WarningInfo),
+    UnusedPredicate(WarningInfo),
+    UnusedParameter(WarningInfo),
+    RedundantEffect(WarningInfo),
+    // Requirements
+    MissingRequirement(WarningInfo),
+    UnusedRequirement(WarningInfo),
+    // Goals
+    UnreachableGoal(WarningInfo)
+}
+
+impl WarningType {
+    // stable identifier of the warning (i.e. its variant name)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ImmutablePredicate(_) => "ImmutablePredicate",
+            Self::NoPrimitiveRefinement(_) => "NoPrimitiveRefinement",
+            Self::UnusedType(_) => "UnusedType",
+            Self::UnusedPredicate(_) => "UnusedPredicate",
+            Self::UnusedParameter(_) => "UnusedParameter",
+            Self::RedundantEffect(_) => "RedundantEffect",
+            Self::MissingRequirement(_) => "MissingRequirement",
+            Self::UnusedRequirement(_) => "UnusedRequirement",
+            Self::UnreachableGoal(_) => "UnreachableGoal",
+        }
+    }
+
+    pub fn info(&self) -> &WarningInfo {
+        match self {
+            Self::ImmutablePredicate(info)
+            | Self::NoPrimitiveRefinement(info)
+            | Self::UnusedType(info)
+            | Self::UnusedPredicate(info)
+            | Self::UnusedParameter(info)
+            | Self::RedundantEffect(info)
+            | Self::MissingRequirement(info)
+            | Self::UnusedRequirement(info)
+            | Self::UnreachableGoal(info) => info
+        }
+    }
+
+    // where the warning occurs
+    pub fn position(&self) -> TokenPosition {
+        self.info().position
+    }
 }
 
 impl std::fmt::Display for WarningType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
-            Self::ImmutablePredicate(predicate) => {
-                write!(f, "Predicate {} does not appear in the effect of any action", predicate)
+            Self::ImmutablePredicate(info) => {
+                write!(f, "line {}: predicate {} does not appear in the effect of any action", info.position.line, info.symbol)
             }
             Self::NoPrimitiveRefinement(info) => {
                 write!(f, "line {}: compound task {} does not have a primitive refinement", info.position.line, info.symbol)
             }
-            Self::UnusedType(type_name) => {
-                write!(f, "Type {} is declared, but never used", type_name)
+            Self::UnusedType(info) => {
+                write!(f, "line {}: type {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::UnusedPredicate(info) => {
+                write!(f, "line {}: predicate {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::UnusedParameter(info) => {
+                write!(f, "line {}: parameter {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::RedundantEffect(info) => {
+                write!(f, "line {}: effect {} is redundant, since the precondition already asserts it", info.position.line, info.symbol)
             }
-            Self::UnusedPredicate(predicate) => {
-                write!(f, "Predicate {} is declared, but never used", predicate)
+            Self::MissingRequirement(info) => {
+                write!(f, "line {}: requirement {} is used, but not declared", info.position.line, info.symbol)
             }
-            Self::UnusedParameter(parameter) => {
-                write!(f, "Parameter {} is declared, but never used", parameter)
+            Self::UnusedRequirement(info) => {
+                write!(f, "line {}: requirement {} is declared, but never used", info.position.line, info.symbol)
             }
-            Self::RedundantEffect => {
-                // TODO:
-                todo!()
+            Self::UnreachableGoal(info) => {
+                write!(f, "line {}: goal {} is not reachable from the initial state", info.position.line, info.symbol)
             }
         }
     }
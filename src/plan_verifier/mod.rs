@@ -0,0 +1,20 @@
+mod plan;
+mod state;
+mod verifier;
+#[cfg(test)]
+mod tests;
+
+use crate::lexical_analyzer::{OperationType, TokenPosition};
+use crate::output::*;
+use crate::syntactic_analyzer::*;
+
+pub use plan::*;
+use state::*;
+pub use verifier::PlanVerifier;
+
+// planners do not necessarily preserve the case of the identifiers,
+// so names of the plan are compared case insensitively (the files are verified
+// first, so their own references already have the case of the declarations)
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+// a plan in the format of the IPC 2020/2023 HTN tracks, i.e.
+//
+// ==>
+// 0 pick-up b1
+// 1 stack b1 b2
+// root 2
+// 2 do_put_on b1 b2 -> m4_do_move 0 1
+// <==
+#[derive(Debug, Default)]
+pub struct Plan<'a> {
+    // primitive actions in the order of their execution
+    pub steps: Vec<PrimitiveStep<'a>>,
+    // ids of the tasks of the initial task network (if the plan has a root line)
+    pub root: Option<Vec<u32>>,
+    pub root_pos: TokenPosition,
+    pub decompositions: Vec<Decomposition<'a>>,
+    // position of the last line of the plan
+    pub end_pos: TokenPosition,
+}
+
+#[derive(Debug)]
+pub struct PrimitiveStep<'a> {
+    pub id: u32,
+    pub action: &'a str,
+    pub args: Vec<&'a str>,
+    pub position: TokenPosition,
+}
+
+// id task args -> method subtask-ids
+#[derive(Debug)]
+pub struct Decomposition<'a> {
+    pub id: u32,
+    pub task: &'a str,
+    pub args: Vec<&'a str>,
+    pub method: &'a str,
+    pub subtasks: Vec<u32>,
+    pub position: TokenPosition,
+}
+
+impl<'a> Plan<'a> {
+    // malformed lines are reported, and skipped
+    pub fn parse(text: &'a str) -> (Plan<'a>, Vec<PlanErrorType>) {
+        let mut plan = Plan::default();
+        let mut errors = vec![];
+        // lines before the start marker (e.g. statistics of the planner) are ignored
+        let has_start_marker = text.lines().any(|line| line.trim() == "==>");
+        let mut started = !has_start_marker;
+        let mut offset = 0;
+        for (index, raw_line) in text.split('\n').enumerate() {
+            let line_offset = offset;
+            offset += raw_line.len() + 1;
+            let content = raw_line.trim_end_matches('\r');
+            let content = match content.find(';') {
+                Some(comment) => &content[..comment],
+                None => content,
+            };
+            let trimmed = content.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let start = content.len() - content.trim_start().len();
+            let end = content.trim_end().len();
+            let position = TokenPosition {
+                line: index as u32 + 1,
+                column: start as u32 + 1,
+                end_column: end as u32 + 1,
+                offset: line_offset + start,
+                end_offset: line_offset + end,
+            };
+            if !started {
+                started = trimmed == "==>";
+                continue;
+            }
+            if trimmed == "==>" {
+                continue;
+            }
+            plan.end_pos = position;
+            if trimmed == "<==" {
+                break;
+            }
+            if let Err(error) = plan.parse_line(trimmed, position) {
+                errors.push(error);
+            }
+        }
+        (plan, errors)
+    }
+
+    fn parse_line(&mut self, line: &'a str, position: TokenPosition) -> Result<(), PlanErrorType> {
+        let mut tokens = line.split_whitespace();
+        let first = tokens.next().unwrap();
+        if first == "root" {
+            self.root = Some(parse_ids(tokens, position)?);
+            self.root_pos = position;
+            return Ok(());
+        }
+        let id = parse_id(first, position)?;
+        let name = match tokens.next() {
+            Some(name) => name,
+            None => return Err(malformed("a task or action name", "end of line", position)),
+        };
+        let mut args = vec![];
+        loop {
+            match tokens.next() {
+                // primitive action
+                None => {
+                    self.steps.push(PrimitiveStep {
+                        id,
+                        action: name,
+                        args,
+                        position,
+                    });
+                    return Ok(());
+                }
+                // decomposition
+                Some("->") => {
+                    let method = match tokens.next() {
+                        Some(method) => method,
+                        None => {
+                            return Err(malformed("a method name after '->'", "end of line", position))
+                        }
+                    };
+                    self.decompositions.push(Decomposition {
+                        id,
+                        task: name,
+                        args,
+                        method,
+                        subtasks: parse_ids(tokens, position)?,
+                        position,
+                    });
+                    return Ok(());
+                }
+                Some(arg) => args.push(arg),
+            }
+        }
+    }
+}
+
+fn parse_ids<'a>(
+    tokens: impl Iterator<Item = &'a str>,
+    position: TokenPosition,
+) -> Result<Vec<u32>, PlanErrorType> {
+    tokens.map(|token| parse_id(token, position)).collect()
+}
+
+fn parse_id(token: &str, position: TokenPosition) -> Result<u32, PlanErrorType> {
+    token
+        .parse::<u32>()
+        .map_err(|_| malformed("a step id", token, position))
+}
+
+fn malformed(expected: &str, found: &str, position: TokenPosition) -> PlanErrorType {
+    PlanErrorType::MalformedPlan(PlanStepError {
+        step: None,
+        symbol: found.to_string(),
+        detail: expected.to_string(),
+        position,
+    })
+}
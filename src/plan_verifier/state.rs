@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+// a ground atom, e.g. ("on", ["b1", "b2"])
+pub type Atom = (String, Vec<String>);
+
+// values of the (lifted) variables of an action, a method, or a quantifier
+pub type Bindings<'a> = HashMap<&'a str, &'a str>;
+
+pub fn atom_to_string(atom: &Atom) -> String {
+    let mut s = format!("({}", atom.0);
+    for arg in atom.1.iter() {
+        s.push(' ');
+        s.push_str(arg);
+    }
+    s.push(')');
+    s
+}
+
+// the objects (and constants) of a problem together with the type hierarchy of the domain
+pub struct Universe<'a> {
+    objects: HashMap<String, &'a Symbol<'a>>,
+    // in order of declaration (so that quantifiers are deterministic)
+    object_names: Vec<&'a Symbol<'a>>,
+    super_types: HashMap<String, Vec<&'a str>>,
+}
+
+impl<'a> Universe<'a> {
+    pub fn new(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Universe<'a> {
+        let mut objects = HashMap::new();
+        let mut object_names = vec![];
+        let constants = domain.constants.iter().flatten();
+        for object in constants.chain(problem.objects.iter()) {
+            if let std::collections::hash_map::Entry::Vacant(e) = objects.entry(normalize(object.name)) {
+                e.insert(object);
+                object_names.push(object);
+            }
+        }
+        let mut super_types: HashMap<String, Vec<&str>> = HashMap::new();
+        for declared_type in domain.types.iter().flatten() {
+            if let Some(parent) = declared_type.symbol_type {
+                super_types
+                    .entry(normalize(declared_type.name))
+                    .or_default()
+                    .push(parent);
+            }
+        }
+        Universe {
+            objects,
+            object_names,
+            super_types,
+        }
+    }
+
+    pub fn get_object(&self, name: &str) -> Option<&'a Symbol<'a>> {
+        self.objects.get(&normalize(name)).copied()
+    }
+
+    // whether an object of type found can be used where type expected is required
+    pub fn is_subtype(&self, found: Option<&str>, expected: Option<&str>) -> bool {
+        let expected = match expected {
+            None => return true,
+            Some(expected) => normalize(expected),
+        };
+        if expected == "object" {
+            return true;
+        }
+        let mut queue: Vec<String> = found.into_iter().map(normalize).collect();
+        let mut visited = HashSet::new();
+        while let Some(current) = queue.pop() {
+            if current == expected {
+                return true;
+            }
+            if visited.insert(current.clone()) {
+                if let Some(parents) = self.super_types.get(&current) {
+                    queue.extend(parents.iter().map(|parent| normalize(parent)));
+                }
+            }
+        }
+        false
+    }
+
+    // every assignment of objects to the (typed) variables
+    fn assignments(&self, vars: &Vec<Symbol<'a>>) -> Vec<Vec<&'a str>> {
+        let mut assignments = vec![vec![]];
+        for var in vars.iter() {
+            let candidates: Vec<&'a str> = self
+                .object_names
+                .iter()
+                .filter(|object| self.is_subtype(object.symbol_type, var.symbol_type))
+                .map(|object| object.name)
+                .collect();
+            assignments = assignments
+                .into_iter()
+                .flat_map(|assignment| {
+                    candidates.iter().map(move |candidate| {
+                        let mut extended = assignment.clone();
+                        extended.push(*candidate);
+                        extended
+                    })
+                })
+                .collect();
+        }
+        assignments
+    }
+
+    fn bind(
+        &self,
+        bindings: &Bindings<'a>,
+        vars: &Vec<Symbol<'a>>,
+        values: &Vec<&'a str>,
+    ) -> Bindings<'a> {
+        let mut extended = bindings.clone();
+        for (var, value) in vars.iter().zip(values.iter()) {
+            extended.insert(var.name, value);
+        }
+        extended
+    }
+}
+
+// the value of a term (i.e. either a bound variable or a constant)
+pub fn resolve(term: &str, bindings: &Bindings) -> String {
+    match bindings.get(term) {
+        Some(value) => normalize(value),
+        None => normalize(term),
+    }
+}
+
+pub fn ground(predicate: &Predicate, bindings: &Bindings) -> Atom {
+    (
+        normalize(predicate.name),
+        predicate
+            .variables
+            .iter()
+            .map(|var| resolve(var.name, bindings))
+            .collect(),
+    )
+}
+
+// the set of atoms that are true (i.e. under the closed world assumption),
+// and the values of the numeric functions (the others are undefined)
+pub struct State {
+    atoms: HashSet<Atom>,
+    values: HashMap<Atom, f64>,
+}
+
+impl State {
+    pub fn new(init_state: &Vec<Predicate>, init_values: &[FunctionValue]) -> State {
+        let bindings = Bindings::new();
+        State {
+            atoms: init_state
+                .iter()
+                .map(|predicate| ground(predicate, &bindings))
+                .collect(),
+            values: init_values
+                .iter()
+                .filter_map(|init| Some((ground(&init.function, &bindings), init.value.parse().ok()?)))
+                .collect(),
+        }
+    }
+
+    fn evaluate(&self, expression: &NumericExpression, bindings: &Bindings) -> Result<f64, String> {
+        let value_of = |function: &Predicate| self.values.get(&ground(function, bindings)).copied();
+        match expression.evaluate(&value_of) {
+            Some(value) => Ok(value),
+            None => {
+                let undefined = expression
+                    .get_functions()
+                    .into_iter()
+                    .map(|function| ground(function, bindings))
+                    .find(|function| !self.values.contains_key(function));
+                match undefined {
+                    Some(function) => Err(format!("{} is undefined", atom_to_string(&function))),
+                    None => Err(format!("{} can not be evaluated", expression)),
+                }
+            }
+        }
+    }
+
+    // returns the reason why the formula does not hold
+    pub fn check<'a>(
+        &self,
+        formula: &Formula<'a>,
+        bindings: &Bindings<'a>,
+        universe: &Universe<'a>,
+    ) -> Result<(), String> {
+        match formula {
+            Formula::Empty => Ok(()),
+            Formula::Atom(predicate) => {
+                let atom = ground(predicate, bindings);
+                if self.atoms.contains(&atom) {
+                    Ok(())
+                } else {
+                    Err(format!("{} is false", atom_to_string(&atom)))
+                }
+            }
+            Formula::Not(new_formula) => match &**new_formula {
+                Formula::Atom(predicate) => {
+                    let atom = ground(predicate, bindings);
+                    if self.atoms.contains(&atom) {
+                        Err(format!("{} is true", atom_to_string(&atom)))
+                    } else {
+                        Ok(())
+                    }
+                }
+                _ => match self.check(new_formula, bindings, universe) {
+                    Ok(()) => Err("a negated formula holds".to_string()),
+                    Err(_) => Ok(()),
+                },
+            },
+            Formula::And(new_formula) => {
+                for f in new_formula {
+                    self.check(f, bindings, universe)?;
+                }
+                Ok(())
+            }
+            Formula::Or(new_formula) => {
+                if new_formula
+                    .iter()
+                    .any(|f| self.check(f, bindings, universe).is_ok())
+                {
+                    Ok(())
+                } else {
+                    Err("none of the disjuncts holds".to_string())
+                }
+            }
+            Formula::Xor(new_formula) => {
+                let n_satisfied = new_formula
+                    .iter()
+                    .filter(|f| self.check(f, bindings, universe).is_ok())
+                    .count();
+                if n_satisfied == 1 {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} operands of an exclusive disjunction hold",
+                        n_satisfied
+                    ))
+                }
+            }
+            Formula::Imply(ps, qs) => {
+                if ps.iter().all(|p| self.check(p, bindings, universe).is_ok()) {
+                    for q in qs {
+                        self.check(q, bindings, universe)?;
+                    }
+                }
+                Ok(())
+            }
+            Formula::Exists(vars, new_formula) => {
+                let is_satisfied = universe.assignments(vars).iter().any(|values| {
+                    let extended = universe.bind(bindings, vars, values);
+                    self.check(new_formula, &extended, universe).is_ok()
+                });
+                if is_satisfied {
+                    Ok(())
+                } else {
+                    let names: Vec<&str> = vars.iter().map(|var| var.name).collect();
+                    Err(format!("there is no {} that satisfies the formula", names.join(" ")))
+                }
+            }
+            Formula::ForAll(vars, new_formula) => {
+                for values in universe.assignments(vars) {
+                    let extended = universe.bind(bindings, vars, &values);
+                    self.check(new_formula, &extended, universe)?;
+                }
+                Ok(())
+            }
+            Formula::Equals(a, b) => {
+                let (a, b) = (resolve(a.name, bindings), resolve(b.name, bindings));
+                if a == b {
+                    Ok(())
+                } else {
+                    Err(format!("{} and {} are not equal", a, b))
+                }
+            }
+            Formula::Comparison(operation, lhs, rhs) => {
+                let (lhs, rhs) = (self.evaluate(lhs, bindings)?, self.evaluate(rhs, bindings)?);
+                if compare(operation, lhs, rhs) {
+                    Ok(())
+                } else {
+                    Err(format!("{} {} {} is false", lhs, operation, rhs))
+                }
+            }
+        }
+    }
+
+    // deletes are applied before adds, and conditions (and the new values of the
+    // functions) are evaluated in the current state
+    pub fn apply<'a>(
+        &mut self,
+        effect: &Effect<'a>,
+        bindings: &Bindings<'a>,
+        universe: &Universe<'a>,
+    ) {
+        let mut adds = vec![];
+        let mut deletes = vec![];
+        let mut assignments = vec![];
+        self.collect_effects(effect, bindings, universe, &mut adds, &mut deletes, &mut assignments);
+        for atom in deletes {
+            self.atoms.remove(&atom);
+        }
+        self.atoms.extend(adds);
+        for (function, value) in assignments {
+            match value {
+                Some(value) => self.values.insert(function, value),
+                None => self.values.remove(&function),
+            };
+        }
+    }
+
+    fn collect_effects<'a>(
+        &self,
+        effect: &Effect<'a>,
+        bindings: &Bindings<'a>,
+        universe: &Universe<'a>,
+        adds: &mut Vec<Atom>,
+        deletes: &mut Vec<Atom>,
+        assignments: &mut Vec<(Atom, Option<f64>)>,
+    ) {
+        match effect {
+            Effect::Add(predicate) => adds.push(ground(predicate, bindings)),
+            Effect::Delete(predicate) => deletes.push(ground(predicate, bindings)),
+            Effect::And(effects) => {
+                for e in effects {
+                    self.collect_effects(e, bindings, universe, adds, deletes, assignments);
+                }
+            }
+            Effect::ForAll(vars, new_effect) => {
+                for values in universe.assignments(vars) {
+                    let extended = universe.bind(bindings, vars, &values);
+                    self.collect_effects(new_effect, &extended, universe, adds, deletes, assignments);
+                }
+            }
+            Effect::When(condition, new_effect) => {
+                if self.check(condition, bindings, universe).is_ok() {
+                    self.collect_effects(new_effect, bindings, universe, adds, deletes, assignments);
+                }
+            }
+            // functions without a value (or with an undefined new value) become undefined
+            Effect::Numeric(operation, function, expression) => {
+                let function = ground(function, bindings);
+                let current = self.values.get(&function).copied();
+                let value = self.evaluate(expression, bindings).ok();
+                let new_value = match operation {
+                    OperationType::Assign => value,
+                    OperationType::Increase => current.zip(value).map(|(current, value)| current + value),
+                    OperationType::Decrease => current.zip(value).map(|(current, value)| current - value),
+                    OperationType::ScaleUp => current.zip(value).map(|(current, value)| current * value),
+                    OperationType::ScaleDown => current.zip(value).map(|(current, value)| current / value),
+                    _ => None,
+                };
+                assignments.push((function, new_value));
+            }
+            // the outcome of non-deterministic effects is unknown
+            Effect::Empty | Effect::OneOf(_) => {}
+        }
+    }
+}
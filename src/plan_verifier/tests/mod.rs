@@ -0,0 +1,5 @@
+mod plan_parser_tests;
+mod verifier_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
@@ -0,0 +1,65 @@
+use super::*;
+
+#[test]
+pub fn plan_parser_test() {
+    let text = "\
+found a plan after 0.3 seconds
+==>
+0 pick-up b1 ; a comment
+1 stack b1 b2
+root 2
+2 do_put_on b1 b2 -> m_move 0 1
+3 do_clear b2 -> m_clear
+<==
+";
+    let (plan, errors) = Plan::parse(text);
+    assert!(errors.is_empty());
+    assert_eq!(plan.steps.len(), 2);
+    assert_eq!(plan.steps[0].id, 0);
+    assert_eq!(plan.steps[0].action, "pick-up");
+    assert_eq!(plan.steps[0].args, vec!["b1"]);
+    assert_eq!(plan.steps[0].position.line, 3);
+    assert_eq!(plan.steps[0].position.column, 1);
+    assert_eq!(plan.steps[0].position.end_column, 13);
+    assert_eq!(plan.steps[1].args, vec!["b1", "b2"]);
+    assert_eq!(plan.root, Some(vec![2]));
+    assert_eq!(plan.root_pos.line, 5);
+    assert_eq!(plan.decompositions.len(), 2);
+    let decomposition = &plan.decompositions[0];
+    assert_eq!(decomposition.id, 2);
+    assert_eq!(decomposition.task, "do_put_on");
+    assert_eq!(decomposition.args, vec!["b1", "b2"]);
+    assert_eq!(decomposition.method, "m_move");
+    assert_eq!(decomposition.subtasks, vec![0, 1]);
+    // methods without subtasks
+    assert_eq!(plan.decompositions[1].method, "m_clear");
+    assert!(plan.decompositions[1].subtasks.is_empty());
+    assert_eq!(plan.end_pos.line, 8);
+}
+
+#[test]
+pub fn plan_without_markers_test() {
+    let text = "0 pick-up b1\n1 stack b1 b2\n";
+    let (plan, errors) = Plan::parse(text);
+    assert!(errors.is_empty());
+    assert_eq!(plan.steps.len(), 2);
+    assert!(plan.root.is_none());
+}
+
+#[test]
+pub fn malformed_plan_test() {
+    let text = "==>\nx pick-up b1\n1 stack b1 b2\nroot 2 a\n2 do_put_on b1 b2 ->\n<==\n";
+    let (plan, errors) = Plan::parse(text);
+    assert_eq!(plan.steps.len(), 1);
+    assert_eq!(errors.len(), 3);
+    for error in errors.iter() {
+        assert!(matches!(error, PlanErrorType::MalformedPlan(_)));
+    }
+    assert_eq!(errors[0].position().line, 2);
+    assert_eq!(
+        errors[0].to_string(),
+        "line 2: malformed plan, expected a step id, but found 'x'."
+    );
+    assert_eq!(errors[1].position().line, 4);
+    assert_eq!(errors[2].position().line, 5);
+}
@@ -0,0 +1,328 @@
+use super::*;
+
+fn get_domain() -> Vec<u8> {
+    "
+    (define (domain blocks)
+        (:requirements :hierarchy :typing :negative-preconditions :method-preconditions)
+        (:types block)
+        (:predicates (on ?x - block ?y - block) (ontable ?x - block) (clear ?x - block) (handempty) (holding ?x - block))
+        (:task do_put_on :parameters (?x - block ?y - block))
+        (:method m_move
+            :parameters (?x - block ?y - block)
+            :task (do_put_on ?x ?y)
+            :precondition (and (clear ?x) (clear ?y) (handempty) (ontable ?x))
+            :ordered-subtasks (and (t1 (pick-up ?x)) (t2 (stack ?x ?y))))
+        (:method m_done
+            :parameters (?x - block ?y - block)
+            :task (do_put_on ?x ?y)
+            :precondition (on ?x ?y)
+            :ordered-subtasks (and (t1 (nop))))
+        (:method m_skip
+            :parameters (?x - block ?y - block)
+            :task (do_put_on ?x ?y)
+            :precondition (on ?x ?y)
+            :ordered-subtasks ())
+        (:action pick-up
+            :parameters (?x - block)
+            :precondition (and (clear ?x) (ontable ?x) (handempty))
+            :effect (and (not (ontable ?x)) (not (clear ?x)) (not (handempty)) (holding ?x)))
+        (:action stack
+            :parameters (?x - block ?y - block)
+            :precondition (and (holding ?x) (clear ?y))
+            :effect (and (not (holding ?x)) (not (clear ?y)) (clear ?x) (handempty) (on ?x ?y)))
+        (:action nop
+            :parameters ()
+            :precondition ()
+            :effect ())
+    )"
+    .as_bytes()
+    .to_vec()
+}
+
+fn get_problem(goal: &str) -> Vec<u8> {
+    format!(
+        "
+    (define (problem p1)
+        (:domain blocks)
+        (:objects b1 b2 - block)
+        (:htn :parameters () :ordered-subtasks (and (task1 (do_put_on b1 b2))))
+        (:init (handempty) (ontable b1) (ontable b2) (clear b1) (clear b2))
+        (:goal {})
+    )",
+        goal
+    )
+    .into_bytes()
+}
+
+fn verify(plan: &str, goal: &str) -> Vec<PlanErrorType> {
+    let domain = get_domain();
+    let problem = get_problem(goal);
+    let domain_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let (plan, mut errors) = Plan::parse(plan);
+            errors.extend(PlanVerifier::new(&d, &p, &plan).verify());
+            errors
+        }
+        _ => panic!("expected a domain and a problem"),
+    }
+}
+
+#[test]
+pub fn valid_plan_test() {
+    let plan = "==>\n0 pick-up b1\n1 stack b1 b2\nroot 2\n2 do_put_on b1 b2 -> m_move 0 1\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert!(errors.is_empty(), "{:?}", errors);
+    // planners may change the case of the identifiers
+    let plan = "==>\n0 PICK-UP B1\n1 stack b1 b2\nroot 2\n2 do_put_on b1 b2 -> M_MOVE 0 1\n<==\n";
+    assert!(verify(plan, "(on b1 b2)").is_empty());
+    // subtasks can be listed in any order
+    let plan = "==>\n0 pick-up b1\n1 stack b1 b2\nroot 2\n2 do_put_on b1 b2 -> m_move 1 0\n<==\n";
+    assert!(verify(plan, "(on b1 b2)").is_empty());
+}
+
+#[test]
+pub fn unsatisfied_precondition_test() {
+    // the steps are executed in the wrong order
+    let plan = "==>\n1 stack b1 b2\n0 pick-up b1\nroot 2\n2 do_put_on b1 b2 -> m_move 0 1\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    match &errors[0] {
+        PlanErrorType::OrderingViolation(info) => {
+            assert_eq!(info.step, Some(2));
+            assert_eq!(info.symbol, "m_move");
+            assert_eq!(info.position.line, 5);
+            assert_eq!(info.detail, "step 0 must be executed before step 1");
+        }
+        error => panic!("{:?}", error),
+    }
+    match &errors[1] {
+        PlanErrorType::UnsatisfiedPrecondition(info) => {
+            assert_eq!(info.step, Some(1));
+            assert_eq!(info.symbol, "stack");
+            assert_eq!(info.position.line, 2);
+        }
+        error => panic!("{:?}", error),
+    }
+    assert_eq!(
+        errors[1].to_string(),
+        "line 2: step 1: precondition of action stack does not hold, (holding b1) is false."
+    );
+}
+
+#[test]
+pub fn unsatisfied_method_precondition_test() {
+    let plan = "==>\n0 nop\nroot 1\n1 do_put_on b1 b2 -> m_done 0\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    match &errors[0] {
+        PlanErrorType::UnsatisfiedMethodPrecondition(info) => {
+            assert_eq!(info.step, Some(1));
+            assert_eq!(info.symbol, "m_done");
+            assert_eq!(info.detail, "(on b1 b2) is false before step 0");
+        }
+        error => panic!("{:?}", error),
+    }
+}
+
+#[test]
+pub fn empty_method_precondition_test() {
+    let plan = "==>\nroot 1\n1 do_put_on b1 b2 -> m_skip\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    match &errors[0] {
+        PlanErrorType::UnsatisfiedMethodPrecondition(info) => {
+            assert_eq!(info.step, Some(1));
+            assert_eq!(info.symbol, "m_skip");
+            assert_eq!(info.detail, "(on b1 b2) is false after the last step");
+        }
+        error => panic!("{:?}", error),
+    }
+}
+
+#[test]
+pub fn swapped_files_test() {
+    let plan = "==>\nroot\n<==\n".as_bytes();
+    let result = crate::HDDLAnalyzer::verify_plan(&get_problem("()"), &get_domain(), plan);
+    assert!(matches!(result, Err(ParsingError::Syntactic(_))));
+}
+
+#[test]
+pub fn unverified_domain_test() {
+    let domain = String::from_utf8(get_domain()).unwrap().replace("(handempty))", "(hand-empty))");
+    let plan = "==>\nroot 1\n1 do_put_on b1 b2 -> m_skip\n<==\n".as_bytes();
+    let result = crate::HDDLAnalyzer::verify_plan(&domain.into_bytes(), &get_problem("()"), plan);
+    assert!(matches!(
+        result,
+        Err(ParsingError::Semantic(SemanticErrorType::UndefinedPredicate(_)))
+    ));
+}
+
+#[test]
+pub fn unsatisfied_goal_test() {
+    let plan = "==>\n0 pick-up b1\n1 stack b1 b2\nroot 2\n2 do_put_on b1 b2 -> m_move 0 1\n<==\n";
+    let errors = verify(plan, "(and (on b1 b2) (not (clear b1)))");
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    match &errors[0] {
+        PlanErrorType::UnsatisfiedGoal(info) => {
+            assert_eq!(info.detail, "(clear b1) is true");
+            assert_eq!(info.position.line, 6);
+        }
+        error => panic!("{:?}", error),
+    }
+}
+
+#[test]
+pub fn decomposition_errors_test() {
+    let plan = "\
+==>
+0 pick-up b1
+1 stack b1 b2
+3 fly b1
+root 2
+2 do_put_on b1 b2 -> m_move 0
+4 do_put_on b1 b3 -> m_jump 0 1 7
+<==
+";
+    let errors = verify(plan, "(on b1 b2)");
+    let codes: Vec<&str> = errors.iter().map(|error| error.code()).collect();
+    assert_eq!(
+        codes,
+        vec![
+            "UndefinedAction",
+            "SubtaskMismatch",
+            "UndefinedObject",
+            "DuplicateStep",
+            "OrphanStep",
+            "OrphanStep",
+            "OrphanStep",
+        ]
+    );
+    assert_eq!(errors[0].step(), Some(3));
+    assert_eq!(
+        errors[1].to_string(),
+        "line 6: step 2: subtasks do not match method m_move, expected 2 subtasks, but found 1."
+    );
+    assert_eq!(errors[2].step(), Some(4));
+    // step 0 is a subtask of both decompositions
+    assert_eq!(errors[3].step(), Some(0));
+    assert_eq!(errors[3].position().line, 2);
+    assert_eq!(errors[4].step(), Some(1));
+    assert_eq!(errors[5].step(), Some(3));
+    assert_eq!(errors[6].step(), Some(4));
+}
+
+#[test]
+pub fn root_mismatch_test() {
+    let plan = "==>\n0 pick-up b1\n1 stack b1 b2\nroot 2\n2 do_put_on b2 b1 -> m_move 0 1\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert!(matches!(errors[0], PlanErrorType::RootMismatch(_)), "{:?}", errors);
+    assert_eq!(errors[0].position().line, 4);
+    let plan = "==>\n0 pick-up b1\n1 stack b1 b2\n<==\n";
+    let errors = verify(plan, "(on b1 b2)");
+    assert!(matches!(errors[0], PlanErrorType::RootMismatch(_)), "{:?}", errors);
+}
+
+fn get_numeric_files(fuel: u32) -> (Vec<u8>, Vec<u8>) {
+    let domain = "
+    (define (domain transport)
+        (:requirements :hierarchy :typing :numeric-fluents)
+        (:types location vehicle)
+        (:predicates (at ?v - vehicle ?l - location))
+        (:functions (road-length ?l1 ?l2 - location) - number (fuel ?v - vehicle) - number)
+        (:task round-trip :parameters (?v - vehicle ?a ?b - location))
+        (:method m_round-trip
+            :parameters (?v - vehicle ?a ?b - location)
+            :task (round-trip ?v ?a ?b)
+            :ordered-subtasks (and (t1 (drive ?v ?a ?b)) (t2 (drive ?v ?b ?a))))
+        (:action drive
+            :parameters (?v - vehicle ?from ?to - location)
+            :precondition (and (at ?v ?from) (>= (fuel ?v) (road-length ?from ?to)))
+            :effect (and (not (at ?v ?from)) (at ?v ?to) (decrease (fuel ?v) (road-length ?from ?to))))
+    )";
+    let problem = format!(
+        "
+    (define (problem p1)
+        (:domain transport)
+        (:objects a b - location t1 - vehicle)
+        (:htn :parameters () :ordered-subtasks (and (task1 (round-trip t1 a b))))
+        (:init (at t1 a) (= (road-length a b) 3) (= (road-length b a) 3) (= (fuel t1) {}))
+        (:goal (>= (fuel t1) 1))
+    )",
+        fuel
+    );
+    (domain.as_bytes().to_vec(), problem.into_bytes())
+}
+
+#[test]
+pub fn numeric_condition_test() {
+    let plan = "==>\n0 drive t1 a b\n1 drive t1 b a\nroot 2\n2 round-trip t1 a b -> m_round-trip 0 1\n<==\n";
+    let (domain, problem) = get_numeric_files(7);
+    let report = crate::HDDLAnalyzer::verify_plan(&domain, &problem, plan.as_bytes()).unwrap();
+    assert!(report.is_valid(), "{:?}", report.errors);
+    // the second drive is short of fuel
+    let (domain, problem) = get_numeric_files(5);
+    let report = crate::HDDLAnalyzer::verify_plan(&domain, &problem, plan.as_bytes()).unwrap();
+    assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+    match &report.errors[0] {
+        PlanErrorType::UnsatisfiedPrecondition(info) => {
+            assert_eq!(info.step, Some(1));
+            assert_eq!(info.detail, "2 >= 3 is false");
+        }
+        error => panic!("{:?}", error),
+    }
+    // the fuel is used up at the end
+    let (domain, problem) = get_numeric_files(6);
+    let report = crate::HDDLAnalyzer::verify_plan(&domain, &problem, plan.as_bytes()).unwrap();
+    match &report.errors[..] {
+        [PlanErrorType::UnsatisfiedGoal(info)] => assert_eq!(info.detail, "0 >= 1 is false"),
+        errors => panic!("{:?}", errors),
+    }
+}
+
+fn get_durative_files(init: &str) -> (Vec<u8>, Vec<u8>) {
+    let domain = "
+    (define (domain doors)
+        (:requirements :hierarchy :typing :negative-preconditions :durative-actions)
+        (:types room)
+        (:predicates (at ?r - room) (open ?r - room) (door ?a ?b - room))
+        (:task go :parameters (?b - room))
+        (:method m_go
+            :parameters (?a ?b - room)
+            :task (go ?b)
+            :ordered-subtasks (and (t1 (walk ?a ?b))))
+        (:durative-action walk
+            :parameters (?a ?b - room)
+            :duration (= ?duration 1)
+            :condition (and (at start (at ?a)) (over all (door ?a ?b)) (at end (open ?b)) (at end (not (at ?a))))
+            :effect (and (at start (not (at ?a))) (at end (at ?b))))
+    )";
+    let problem = format!(
+        "
+    (define (problem p1)
+        (:domain doors)
+        (:objects r1 r2 - room)
+        (:htn :parameters () :ordered-subtasks (and (task1 (go r2))))
+        (:init (at r1) (door r1 r2) {})
+        (:goal (at r2))
+    )",
+        init
+    );
+    (domain.as_bytes().to_vec(), problem.into_bytes())
+}
+
+#[test]
+pub fn durative_action_test() {
+    let plan = "==>\n0 walk r1 r2\nroot 1\n1 go r2 -> m_go 0\n<==\n";
+    // the conditions of the end are checked after the effects of the start
+    let (domain, problem) = get_durative_files("(open r2)");
+    let report = crate::HDDLAnalyzer::verify_plan(&domain, &problem, plan.as_bytes()).unwrap();
+    assert!(report.is_valid(), "{:?}", report.errors);
+    let (domain, problem) = get_durative_files("");
+    let report = crate::HDDLAnalyzer::verify_plan(&domain, &problem, plan.as_bytes()).unwrap();
+    match &report.errors[..] {
+        [PlanErrorType::UnsatisfiedPrecondition(info)] => assert_eq!(info.detail, "(open r2) is false at end"),
+        errors => panic!("{:?}", errors),
+    }
+}
@@ -0,0 +1,874 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::*;
+
+#[derive(Clone, Copy)]
+enum StepRef {
+    Primitive(usize),
+    Compound(usize),
+}
+
+// a task network (of a method or of the problem), whose subtasks are
+// assigned to steps of the plan
+struct NetworkInstance<'a> {
+    // the decomposition (none for the initial task network)
+    step: Option<u32>,
+    method: Option<&'a Method<'a>>,
+    tn: &'a HTN<'a>,
+    // names of the parameters of the network
+    variables: Vec<&'a str>,
+    bindings: Bindings<'a>,
+    // ids of the steps, in the order of the subtasks of the network
+    assignment: Vec<u32>,
+    position: TokenPosition,
+}
+
+pub struct PlanVerifier<'a> {
+    problem: &'a ProblemAST<'a>,
+    plan: &'a Plan<'a>,
+    universe: Universe<'a>,
+    actions: HashMap<String, &'a Action<'a>>,
+    tasks: HashMap<String, &'a Task<'a>>,
+    methods: HashMap<String, &'a Method<'a>>,
+    steps: HashMap<u32, StepRef>,
+}
+
+impl<'a> PlanVerifier<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        plan: &'a Plan<'a>,
+    ) -> PlanVerifier<'a> {
+        let mut steps = HashMap::new();
+        for (index, step) in plan.steps.iter().enumerate() {
+            steps.entry(step.id).or_insert(StepRef::Primitive(index));
+        }
+        for (index, decomposition) in plan.decompositions.iter().enumerate() {
+            steps
+                .entry(decomposition.id)
+                .or_insert(StepRef::Compound(index));
+        }
+        PlanVerifier {
+            problem,
+            plan,
+            universe: Universe::new(domain, problem),
+            actions: domain
+                .actions
+                .iter()
+                .map(|action| (normalize(action.name), action))
+                .collect(),
+            tasks: domain
+                .compound_tasks
+                .iter()
+                .map(|task| (normalize(task.name), task))
+                .collect(),
+            methods: domain
+                .methods
+                .iter()
+                .map(|method| (normalize(method.name.name), method))
+                .collect(),
+            steps,
+        }
+    }
+
+    pub fn verify(&self) -> Vec<PlanErrorType> {
+        let mut errors = self.check_step_ids();
+        for step in self.plan.steps.iter() {
+            if let Some(action) = self.actions.get(&normalize(step.action)) {
+                self.check_arguments(
+                    step.id,
+                    step.action,
+                    &action.parameters,
+                    &step.args,
+                    step.position,
+                    &mut errors,
+                );
+            } else {
+                errors.push(PlanErrorType::UndefinedAction(PlanStepError {
+                    step: Some(step.id),
+                    symbol: step.action.to_string(),
+                    detail: String::new(),
+                    position: step.position,
+                }));
+            }
+        }
+        let mut instances = vec![];
+        if let Some(root) = self.check_root(&mut errors) {
+            instances.push(root);
+        }
+        for decomposition in self.plan.decompositions.iter() {
+            if let Some(instance) = self.check_decomposition(decomposition, &mut errors) {
+                instances.push(instance);
+            }
+        }
+        let depths = self.check_references(&mut errors);
+        let mut spans = HashMap::new();
+        for instance in instances.iter() {
+            self.check_orderings(instance, &mut spans, &mut errors);
+            self.check_constraints(instance, &mut errors);
+        }
+        // method preconditions are evaluated right before the first primitive
+        // step of the decomposition, and those of decompositions without primitive
+        // steps as early as the orderings of their network allow
+        let mut parents = HashMap::new();
+        for (index, instance) in instances.iter().enumerate() {
+            for (position, id) in instance.assignment.iter().enumerate() {
+                parents.entry(*id).or_insert((index, position));
+            }
+        }
+        let mut schedule: HashMap<usize, Vec<&NetworkInstance>> = HashMap::new();
+        for instance in instances.iter() {
+            if let (Some(step), Some(_)) = (instance.step, instance.method) {
+                let first = self.start_index(step, &instances, &parents, &mut spans, &mut HashSet::new());
+                schedule.entry(first).or_default().push(instance);
+            }
+        }
+        for scheduled in schedule.values_mut() {
+            scheduled.sort_by_key(|instance| depths.get(&instance.step.unwrap()));
+        }
+        self.simulate(&schedule, &mut errors);
+        errors
+    }
+
+    fn check_step_ids(&self) -> Vec<PlanErrorType> {
+        let mut errors = vec![];
+        let mut declared = HashMap::new();
+        let definitions = self
+            .plan
+            .steps
+            .iter()
+            .map(|step| (step.id, step.action, step.position))
+            .chain(
+                self.plan
+                    .decompositions
+                    .iter()
+                    .map(|d| (d.id, d.task, d.position)),
+            );
+        for (id, name, position) in definitions {
+            if let Some(first_pos) = declared.insert(id, position) {
+                errors.push(PlanErrorType::DuplicateStep(PlanStepError {
+                    step: Some(id),
+                    symbol: name.to_string(),
+                    detail: format!("has the same id as the step in line {}", first_pos.line),
+                    position,
+                }));
+            }
+        }
+        errors
+    }
+
+    // arity, existence and types of the arguments of an action or a task
+    fn check_arguments(
+        &self,
+        step: u32,
+        name: &str,
+        parameters: &Vec<Symbol<'a>>,
+        args: &Vec<&'a str>,
+        position: TokenPosition,
+        errors: &mut Vec<PlanErrorType>,
+    ) -> bool {
+        if parameters.len() != args.len() {
+            errors.push(PlanErrorType::InconsistentArity(PlanStepError {
+                step: Some(step),
+                symbol: name.to_string(),
+                detail: format!(
+                    "takes {} parameters, but {} are given",
+                    parameters.len(),
+                    args.len()
+                ),
+                position,
+            }));
+            return false;
+        }
+        let mut is_consistent = true;
+        for (parameter, arg) in parameters.iter().zip(args.iter()) {
+            if let Some(error) = self.check_object(step, name, parameter, arg, position) {
+                errors.push(error);
+                is_consistent = false;
+            }
+        }
+        is_consistent
+    }
+
+    fn check_object(
+        &self,
+        step: u32,
+        name: &str,
+        parameter: &Symbol<'a>,
+        value: &str,
+        position: TokenPosition,
+    ) -> Option<PlanErrorType> {
+        let info = |detail: String| PlanStepError {
+            step: Some(step),
+            symbol: value.to_string(),
+            detail,
+            position,
+        };
+        match self.universe.get_object(value) {
+            None => Some(PlanErrorType::UndefinedObject(info(String::new()))),
+            Some(object) => {
+                if self
+                    .universe
+                    .is_subtype(object.symbol_type, parameter.symbol_type)
+                {
+                    None
+                } else {
+                    Some(PlanErrorType::InconsistentArgType(info(format!(
+                        "of {} must be of type {}, but is of type {}",
+                        name,
+                        parameter.symbol_type.unwrap(),
+                        object.symbol_type.unwrap_or("object")
+                    ))))
+                }
+            }
+        }
+    }
+
+    fn check_root(&self, errors: &mut Vec<PlanErrorType>) -> Option<NetworkInstance<'a>> {
+        let root_error = |detail: String, position: TokenPosition| {
+            PlanErrorType::RootMismatch(PlanStepError {
+                step: None,
+                symbol: String::new(),
+                detail,
+                position,
+            })
+        };
+        let (init_tn, root) = match (&self.problem.init_tn, &self.plan.root) {
+            (Some(init_tn), Some(root)) => (init_tn, root),
+            (Some(_), None) => {
+                errors.push(root_error(
+                    "the plan does not have a root line".to_string(),
+                    self.plan.end_pos,
+                ));
+                return None;
+            }
+            (None, Some(root)) if !root.is_empty() => {
+                errors.push(root_error(
+                    "the problem does not have an initial task network".to_string(),
+                    self.plan.root_pos,
+                ));
+                return None;
+            }
+            (None, _) => return None,
+        };
+        if !self.check_subtask_ids(None, root, self.plan.root_pos, errors) {
+            return None;
+        }
+        let variables: Vec<&str> = init_tn
+            .parameters
+            .iter()
+            .flatten()
+            .map(|parameter| parameter.name)
+            .collect();
+        match self.match_subtasks(&init_tn.tn.subtasks, &variables, root, Bindings::new()) {
+            Some((assignment, bindings)) => Some(NetworkInstance {
+                step: None,
+                method: None,
+                tn: &init_tn.tn,
+                variables,
+                bindings,
+                assignment,
+                position: self.plan.root_pos,
+            }),
+            None => {
+                errors.push(root_error(
+                    self.describe_mismatch(&init_tn.tn.subtasks, root),
+                    self.plan.root_pos,
+                ));
+                None
+            }
+        }
+    }
+
+    fn check_decomposition(
+        &self,
+        decomposition: &'a Decomposition<'a>,
+        errors: &mut Vec<PlanErrorType>,
+    ) -> Option<NetworkInstance<'a>> {
+        let info = |symbol: &str, detail: String| PlanStepError {
+            step: Some(decomposition.id),
+            symbol: symbol.to_string(),
+            detail,
+            position: decomposition.position,
+        };
+        let task = match self.tasks.get(&normalize(decomposition.task)) {
+            Some(task) => task,
+            None => {
+                errors.push(PlanErrorType::UndefinedTask(info(decomposition.task, String::new())));
+                return None;
+            }
+        };
+        if !self.check_arguments(
+            decomposition.id,
+            decomposition.task,
+            &task.parameters,
+            &decomposition.args,
+            decomposition.position,
+            errors,
+        ) {
+            return None;
+        }
+        let method = match self.methods.get(&normalize(decomposition.method)) {
+            Some(method) => method,
+            None => {
+                errors.push(PlanErrorType::UndefinedMethod(info(decomposition.method, String::new())));
+                return None;
+            }
+        };
+        if normalize(method.task.name) != normalize(decomposition.task) {
+            errors.push(PlanErrorType::MethodTaskMismatch(info(
+                decomposition.method,
+                format!("decomposes task {}, not {}", method.task.name, decomposition.task),
+            )));
+            return None;
+        }
+        let variables: Vec<&str> = method.params.iter().map(|parameter| parameter.name).collect();
+        let mut bindings = Bindings::new();
+        if !unify(&method.task_terms, &variables, &decomposition.args, &mut bindings) {
+            errors.push(PlanErrorType::MethodTaskMismatch(info(
+                decomposition.method,
+                format!("cannot decompose task {} with these arguments", decomposition.task),
+            )));
+            return None;
+        }
+        if !self.check_subtask_ids(
+            Some(decomposition.id),
+            &decomposition.subtasks,
+            decomposition.position,
+            errors,
+        ) {
+            return None;
+        }
+        let (assignment, bindings) =
+            match self.match_subtasks(&method.tn.subtasks, &variables, &decomposition.subtasks, bindings) {
+                Some(result) => result,
+                None => {
+                    errors.push(PlanErrorType::SubtaskMismatch(info(
+                        decomposition.method,
+                        self.describe_mismatch(&method.tn.subtasks, &decomposition.subtasks),
+                    )));
+                    return None;
+                }
+            };
+        for parameter in method.params.iter() {
+            if let Some(value) = bindings.get(parameter.name) {
+                if let Some(error) = self.check_object(
+                    decomposition.id,
+                    method.name.name,
+                    parameter,
+                    value,
+                    decomposition.position,
+                ) {
+                    errors.push(error);
+                }
+            }
+        }
+        Some(NetworkInstance {
+            step: Some(decomposition.id),
+            method: Some(method),
+            tn: &method.tn,
+            variables,
+            bindings,
+            assignment,
+            position: decomposition.position,
+        })
+    }
+
+    fn check_subtask_ids(
+        &self,
+        step: Option<u32>,
+        ids: &[u32],
+        position: TokenPosition,
+        errors: &mut Vec<PlanErrorType>,
+    ) -> bool {
+        let mut is_defined = true;
+        for id in ids.iter() {
+            if !self.steps.contains_key(id) {
+                errors.push(PlanErrorType::UndefinedStep(PlanStepError {
+                    step,
+                    symbol: id.to_string(),
+                    detail: String::new(),
+                    position,
+                }));
+                is_defined = false;
+            }
+        }
+        is_defined
+    }
+
+    // name and arguments of a step
+    fn signature(&self, id: u32) -> (&'a str, &'a Vec<&'a str>) {
+        match self.steps[&id] {
+            StepRef::Primitive(index) => {
+                let step = &self.plan.steps[index];
+                (step.action, &step.args)
+            }
+            StepRef::Compound(index) => {
+                let decomposition = &self.plan.decompositions[index];
+                (decomposition.task, &decomposition.args)
+            }
+        }
+    }
+
+    // assigns the steps to the subtasks of a network, such that their names
+    // match, and their arguments are consistent with the bindings
+    fn match_subtasks(
+        &self,
+        subtasks: &'a Vec<Subtask<'a>>,
+        variables: &Vec<&'a str>,
+        ids: &Vec<u32>,
+        bindings: Bindings<'a>,
+    ) -> Option<(Vec<u32>, Bindings<'a>)> {
+        if subtasks.len() != ids.len() {
+            return None;
+        }
+        let mut assignment = vec![];
+        let mut used = vec![false; ids.len()];
+        let bindings =
+            self.assign_subtask(subtasks, variables, ids, &mut used, &mut assignment, bindings)?;
+        Some((assignment, bindings))
+    }
+
+    fn assign_subtask(
+        &self,
+        subtasks: &'a Vec<Subtask<'a>>,
+        variables: &Vec<&'a str>,
+        ids: &Vec<u32>,
+        used: &mut Vec<bool>,
+        assignment: &mut Vec<u32>,
+        bindings: Bindings<'a>,
+    ) -> Option<Bindings<'a>> {
+        let subtask = match subtasks.get(assignment.len()) {
+            Some(subtask) => subtask,
+            None => return Some(bindings),
+        };
+        for (index, id) in ids.iter().enumerate() {
+            if used[index] {
+                continue;
+            }
+            let (name, args) = self.signature(*id);
+            if normalize(name) != normalize(subtask.task.name) {
+                continue;
+            }
+            let mut extended = bindings.clone();
+            if !unify(&subtask.terms, variables, args, &mut extended) {
+                continue;
+            }
+            used[index] = true;
+            assignment.push(*id);
+            if let Some(result) =
+                self.assign_subtask(subtasks, variables, ids, used, assignment, extended)
+            {
+                return Some(result);
+            }
+            used[index] = false;
+            assignment.pop();
+        }
+        None
+    }
+
+    fn describe_mismatch(&self, subtasks: &[Subtask<'a>], ids: &[u32]) -> String {
+        if subtasks.len() != ids.len() {
+            return format!("expected {} subtasks, but found {}", subtasks.len(), ids.len());
+        }
+        let expected: Vec<String> = subtasks
+            .iter()
+            .map(|subtask| {
+                let mut s = format!("({}", subtask.task.name);
+                for term in subtask.terms.iter() {
+                    s.push(' ');
+                    s.push_str(term.name);
+                }
+                s.push(')');
+                s
+            })
+            .collect();
+        let found: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+        format!(
+            "steps {} cannot be assigned to {}",
+            found.join(", "),
+            expected.join(" ")
+        )
+    }
+
+    // every step has to be the subtask of exactly one task (or a root task),
+    // returns the depth of the decompositions
+    fn check_references(&self, errors: &mut Vec<PlanErrorType>) -> HashMap<u32, usize> {
+        let mut parents: HashMap<u32, Option<u32>> = HashMap::new();
+        let references = self
+            .plan
+            .root
+            .iter()
+            .flatten()
+            .map(|id| (*id, None))
+            .chain(
+                self.plan
+                    .decompositions
+                    .iter()
+                    .flat_map(|d| d.subtasks.iter().map(|id| (*id, Some(d.id)))),
+            );
+        let describe = |parent: Option<u32>| match parent {
+            None => "the root".to_string(),
+            Some(id) => format!("step {}", id),
+        };
+        for (id, parent) in references {
+            if !self.steps.contains_key(&id) {
+                continue;
+            }
+            if let Some(first_parent) = parents.insert(id, parent) {
+                errors.push(PlanErrorType::DuplicateStep(PlanStepError {
+                    step: Some(id),
+                    symbol: self.signature(id).0.to_string(),
+                    detail: format!(
+                        "is a subtask of both {} and {}",
+                        describe(first_parent),
+                        describe(parent)
+                    ),
+                    position: self.step_position(id),
+                }));
+            }
+        }
+        let mut depths = HashMap::new();
+        let root = match &self.plan.root {
+            Some(root) => root,
+            None => return depths,
+        };
+        let mut queue: VecDeque<(u32, usize)> = root.iter().map(|id| (*id, 0)).collect();
+        while let Some((id, depth)) = queue.pop_front() {
+            if depths.contains_key(&id) {
+                continue;
+            }
+            depths.insert(id, depth);
+            if let Some(StepRef::Compound(index)) = self.steps.get(&id) {
+                for subtask in self.plan.decompositions[*index].subtasks.iter() {
+                    queue.push_back((*subtask, depth + 1));
+                }
+            }
+        }
+        let steps = self
+            .plan
+            .steps
+            .iter()
+            .map(|step| (step.id, step.action, step.position))
+            .chain(
+                self.plan
+                    .decompositions
+                    .iter()
+                    .map(|d| (d.id, d.task, d.position)),
+            );
+        for (id, name, position) in steps {
+            if !depths.contains_key(&id) {
+                errors.push(PlanErrorType::OrphanStep(PlanStepError {
+                    step: Some(id),
+                    symbol: name.to_string(),
+                    detail: String::new(),
+                    position,
+                }));
+            }
+        }
+        depths
+    }
+
+    fn step_position(&self, id: u32) -> TokenPosition {
+        match self.steps[&id] {
+            StepRef::Primitive(index) => self.plan.steps[index].position,
+            StepRef::Compound(index) => self.plan.decompositions[index].position,
+        }
+    }
+
+    // indices of the first and the last primitive step of the decomposition of a step
+    fn execution_span(
+        &self,
+        id: u32,
+        spans: &mut HashMap<u32, Option<(usize, usize)>>,
+        visiting: &mut HashSet<u32>,
+    ) -> Option<(usize, usize)> {
+        if let Some(span) = spans.get(&id) {
+            return *span;
+        }
+        // cyclic decompositions
+        if !visiting.insert(id) {
+            return None;
+        }
+        let span = match self.steps.get(&id) {
+            Some(StepRef::Primitive(index)) => Some((*index, *index)),
+            Some(StepRef::Compound(index)) => self.plan.decompositions[*index]
+                .subtasks
+                .iter()
+                .filter_map(|subtask| self.execution_span(*subtask, spans, visiting))
+                .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1))),
+            None => None,
+        };
+        spans.insert(id, span);
+        span
+    }
+
+    fn check_orderings(
+        &self,
+        instance: &NetworkInstance<'a>,
+        spans: &mut HashMap<u32, Option<(usize, usize)>>,
+        errors: &mut Vec<PlanErrorType>,
+    ) {
+        for (before, after) in orderings(instance) {
+            let (before, after) = (instance.assignment[before], instance.assignment[after]);
+            let before_span = self.execution_span(before, spans, &mut HashSet::new());
+            let after_span = self.execution_span(after, spans, &mut HashSet::new());
+            if let (Some(before_span), Some(after_span)) = (before_span, after_span) {
+                if before_span.1 >= after_span.0 {
+                    let detail = format!("step {} must be executed before step {}", before, after);
+                    errors.push(self.network_error(instance, detail, PlanErrorType::OrderingViolation));
+                }
+            }
+        }
+    }
+
+    // index of the primitive step before which a step is applied (i.e. the number
+    // of steps if it is applied after the last one)
+    fn start_index(
+        &self,
+        id: u32,
+        instances: &[NetworkInstance<'a>],
+        parents: &HashMap<u32, (usize, usize)>,
+        spans: &mut HashMap<u32, Option<(usize, usize)>>,
+        visiting: &mut HashSet<u32>,
+    ) -> usize {
+        if let Some((first, _)) = self.execution_span(id, spans, &mut HashSet::new()) {
+            return first;
+        }
+        // cyclic decompositions or orderings
+        if !visiting.insert(id) {
+            return 0;
+        }
+        let (index, position) = match parents.get(&id) {
+            Some(parent) => *parent,
+            None => return 0,
+        };
+        let instance = &instances[index];
+        // after the application of the parent, and after its predecessors
+        let mut start = match instance.step {
+            Some(parent) => self.start_index(parent, instances, parents, spans, visiting),
+            None => 0,
+        };
+        for (before, _) in orderings(instance).into_iter().filter(|(_, after)| *after == position) {
+            let predecessor = instance.assignment[before];
+            let end = match self.execution_span(predecessor, spans, &mut HashSet::new()) {
+                Some((_, last)) => last + 1,
+                None => self.start_index(predecessor, instances, parents, spans, visiting),
+            };
+            start = start.max(end);
+        }
+        start
+    }
+
+    fn check_constraints(&self, instance: &NetworkInstance<'a>, errors: &mut Vec<PlanErrorType>) {
+        for constraint in instance.tn.constraints.iter().flatten() {
+            let (a, b, must_be_equal) = match constraint {
+                Constraint::Equal(a, b) => (a, b, true),
+                Constraint::NotEqual(a, b) => (a, b, false),
+                // plans are sequential, so temporal constraints are not checked
+                Constraint::Temporal(..) => continue,
+            };
+            // unbound variables can take any value
+            let is_unbound = |term: &str| {
+                instance.variables.contains(&term) && !instance.bindings.contains_key(term)
+            };
+            if is_unbound(a) || is_unbound(b) {
+                continue;
+            }
+            let (value_a, value_b) = (resolve(a, &instance.bindings), resolve(b, &instance.bindings));
+            if (value_a == value_b) != must_be_equal {
+                let relation = if must_be_equal { "must be equal" } else { "must differ" };
+                let detail = format!("{} ({}) and {} ({}) {}", a, value_a, b, value_b, relation);
+                errors.push(self.network_error(instance, detail, PlanErrorType::ConstraintViolation));
+            }
+        }
+    }
+
+    // errors of the initial task network are root mismatches
+    fn network_error(
+        &self,
+        instance: &NetworkInstance<'a>,
+        detail: String,
+        error_type: fn(PlanStepError) -> PlanErrorType,
+    ) -> PlanErrorType {
+        match instance.method {
+            Some(method) => error_type(PlanStepError {
+                step: instance.step,
+                symbol: method.name.name.to_string(),
+                detail,
+                position: instance.position,
+            }),
+            None => PlanErrorType::RootMismatch(PlanStepError {
+                step: None,
+                symbol: String::new(),
+                detail,
+                position: instance.position,
+            }),
+        }
+    }
+
+    // executes the primitive steps from the initial state, and stops at the first failure
+    fn simulate(
+        &self,
+        schedule: &HashMap<usize, Vec<&NetworkInstance<'a>>>,
+        errors: &mut Vec<PlanErrorType>,
+    ) {
+        let mut state = State::new(&self.problem.init_state, &self.problem.init_values);
+        for (index, step) in self.plan.steps.iter().enumerate() {
+            let applied = schedule.get(&index);
+            if !self.check_methods(&state, applied, &format!("before step {}", step.id), errors) {
+                return;
+            }
+            let action = match self.actions.get(&normalize(step.action)) {
+                Some(action) if action.parameters.len() == step.args.len() => action,
+                _ => return,
+            };
+            let bindings: Bindings = action
+                .parameters
+                .iter()
+                .map(|parameter| parameter.name)
+                .zip(step.args.iter().copied())
+                .collect();
+            if let Err(reason) = self.execute(&mut state, action, &bindings) {
+                errors.push(PlanErrorType::UnsatisfiedPrecondition(PlanStepError {
+                    step: Some(step.id),
+                    symbol: step.action.to_string(),
+                    detail: reason,
+                    position: step.position,
+                }));
+                return;
+            }
+        }
+        let applied = schedule.get(&self.plan.steps.len());
+        if !self.check_methods(&state, applied, "after the last step", errors) {
+            return;
+        }
+        if let Some(goal) = &self.problem.goal {
+            if let Err(reason) = state.check(goal, &Bindings::new(), &self.universe) {
+                errors.push(PlanErrorType::UnsatisfiedGoal(PlanStepError {
+                    step: None,
+                    symbol: String::new(),
+                    detail: reason,
+                    position: self.plan.end_pos,
+                }));
+            }
+        }
+    }
+
+    // the end of a durative action directly follows its start (plans are sequential),
+    // and its invariant has to hold at both of them
+    fn execute(&self, state: &mut State, action: &Action<'a>, bindings: &Bindings<'a>) -> Result<(), String> {
+        let Some(timing) = &action.timing else {
+            if let Some(precondition) = &action.preconditions {
+                state.check(precondition, bindings, &self.universe)?;
+            }
+            if let Some(effect) = &action.effects {
+                state.apply(effect, bindings, &self.universe);
+            }
+            return Ok(());
+        };
+        let invariant = timing.condition_at(TimeSpecifier::OverAll);
+        for time in [TimeSpecifier::AtStart, TimeSpecifier::AtEnd] {
+            for condition in [timing.condition_at(time), invariant.clone()] {
+                state
+                    .check(&condition, bindings, &self.universe)
+                    .map_err(|reason| format!("{} {}", reason, time))?;
+            }
+            state.apply(&timing.effect_at(time), bindings, &self.universe);
+        }
+        Ok(())
+    }
+
+    // whether the preconditions of the methods hold in the state
+    fn check_methods(
+        &self,
+        state: &State,
+        instances: Option<&Vec<&NetworkInstance<'a>>>,
+        when: &str,
+        errors: &mut Vec<PlanErrorType>,
+    ) -> bool {
+        for instance in instances.into_iter().flatten() {
+            let method = instance.method.unwrap();
+            if let Err(reason) = self.check_method_precondition(state, method, &instance.bindings) {
+                errors.push(PlanErrorType::UnsatisfiedMethodPrecondition(PlanStepError {
+                    step: instance.step,
+                    symbol: method.name.name.to_string(),
+                    detail: format!("{} {}", reason, when),
+                    position: instance.position,
+                }));
+                return false;
+            }
+        }
+        true
+    }
+
+    // parameters that are not bound by the task or the subtasks are existentially quantified
+    fn check_method_precondition(
+        &self,
+        state: &State,
+        method: &'a Method<'a>,
+        bindings: &Bindings<'a>,
+    ) -> Result<(), String> {
+        let precondition = match &method.precondition {
+            Some(precondition) => precondition,
+            None => return Ok(()),
+        };
+        let free: Vec<Symbol> = method
+            .params
+            .iter()
+            .filter(|parameter| !bindings.contains_key(parameter.name))
+            .cloned()
+            .collect();
+        if free.is_empty() {
+            state.check(precondition, bindings, &self.universe)
+        } else {
+            let quantified = Formula::Exists(free, Box::new(precondition.clone()));
+            state.check(&quantified, bindings, &self.universe)
+        }
+    }
+}
+
+// pairs of subtask indices, where the first one has to precede the second
+fn orderings(instance: &NetworkInstance) -> Vec<(usize, usize)> {
+    let subtasks = &instance.tn.subtasks;
+    match &instance.tn.orderings {
+        TaskOrdering::Total => (1..subtasks.len()).map(|i| (i - 1, i)).collect(),
+        TaskOrdering::Partial(orderings) => {
+            let index_of = |name: &str| {
+                subtasks
+                    .iter()
+                    .position(|subtask| subtask.id.as_ref().is_some_and(|id| id.name == name))
+            };
+            orderings
+                .iter()
+                .filter_map(|(a, b)| Some((index_of(a)?, index_of(b)?)))
+                .collect()
+        }
+    }
+}
+
+// binds the variables among the terms to the arguments (constants have to match exactly)
+fn unify<'a>(
+    terms: &Vec<Symbol<'a>>,
+    variables: &Vec<&'a str>,
+    args: &Vec<&'a str>,
+    bindings: &mut Bindings<'a>,
+) -> bool {
+    if terms.len() != args.len() {
+        return false;
+    }
+    for (term, arg) in terms.iter().zip(args.iter()) {
+        if variables.contains(&term.name) {
+            match bindings.get(term.name) {
+                Some(value) if normalize(value) != normalize(arg) => return false,
+                Some(_) => {}
+                None => {
+                    bindings.insert(term.name, arg);
+                }
+            }
+        } else if normalize(term.name) != normalize(arg) {
+            return false;
+        }
+    }
+    true
+}
@@ -1,11 +1,17 @@
 use std::collections::{HashMap, HashSet};
 
 use super::*;
-use crate::lexical_analyzer::RequirementType;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
 
 pub struct DomainSemanticAnalyzer<'a> {
     domain: &'a DomainAST<'a>,
     pub type_checker: DomainTypeChecker<'a>,
+    // whether missing requirements are errors (rather than warnings)
+    strict_requirements: bool,
+    // whether the domain must be classical (i.e. without tasks and methods)
+    classical: bool,
+    // whether predicates that no action changes are reported
+    immutable_predicates: bool,
 }
 
 impl<'a> DomainSemanticAnalyzer<'a> {
@@ -13,24 +19,75 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         DomainSemanticAnalyzer {
             domain,
             type_checker: DomainTypeChecker::new(&domain.types),
+            strict_requirements: false,
+            classical: false,
+            immutable_predicates: false,
         }
     }
 
-    pub fn verify_domain(&'a self) -> Result<SymbolTable<'a>, SemanticErrorType> {
-        // Assert there are no duplicate requirements
-        if let Some(duplicate) =
-            DomainSemanticAnalyzer::check_duplicate_requirements(&self.domain.requirements)
-        {
-            return Err(duplicate);
+    // reports the features that are used without their requirement as errors
+    pub fn with_strict_requirements(mut self, strict: bool) -> DomainSemanticAnalyzer<'a> {
+        self.strict_requirements = strict;
+        self
+    }
+
+    // reports compound tasks and methods as errors (i.e. for plain PDDL domains)
+    pub fn with_classical(mut self, classical: bool) -> DomainSemanticAnalyzer<'a> {
+        self.classical = classical;
+        self
+    }
+
+    // reports the predicates that are used in preconditions, but changed by no action
+    pub fn with_immutable_predicates(mut self, immutable_predicates: bool) -> DomainSemanticAnalyzer<'a> {
+        self.immutable_predicates = immutable_predicates;
+        self
+    }
+
+    // stops at the first semantic error
+    pub fn verify_domain(&self) -> Result<SymbolTable<'a>, SemanticErrorType> {
+        let (symbol_table, errors) = self.verify_domain_all();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(symbol_table),
         }
+    }
+
+    // keeps walking the domain after an error, and returns every semantic error
+    // (in the order they are encountered) alongside the symbol table
+    pub fn verify_domain_all(&self) -> (SymbolTable<'a>, Vec<SemanticErrorType>) {
+        let domain = self.domain;
+        let mut errors = vec![];
+        // Assert there are no duplicate requirements
+        errors.extend(DomainSemanticAnalyzer::check_duplicate_requirements(
+            &domain.requirements,
+            &domain.requirement_positions,
+        ));
         // Assert type hierarchy is acyclic
-        let _ = self.type_checker.verify_type_hierarchy()?;
+        if let Err(cycle) = self.type_checker.verify_type_hierarchy() {
+            errors.push(cycle);
+        }
+        // Assert a classical domain has no hierarchy
+        if self.classical {
+            for task in domain.compound_tasks.iter() {
+                errors.push(SemanticErrorType::NonClassicalConstruct(UndefinedSymbolError {
+                    symbol: format!("task {}", task.name),
+                    position: task.name_pos,
+                }));
+            }
+            for method in domain.methods.iter() {
+                errors.push(SemanticErrorType::NonClassicalConstruct(UndefinedSymbolError {
+                    symbol: format!("method {}", method.name.name),
+                    position: method.name.name_pos,
+                }));
+            }
+        }
         let mut warnings = vec![];
         // Domain declarations
-        let declared_predicates = self.verify_predicates()?;
-        let declared_tasks = self.verify_compound_tasks()?;
+        let declared_predicates = self.verify_predicates(&mut errors);
+        let declared_functions = self.verify_functions(&mut errors);
+        let declared_tasks = self.verify_compound_tasks(&mut errors);
         let mut declared_constants = HashSet::new();
-        match &self.domain.constants {
+        match &domain.constants {
             Some(constants) => {
                 for c in constants {
                     declared_constants.insert(c);
@@ -42,9 +99,9 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         // assert actions are correct
         let mut declared_actions = HashSet::new();
         let mut action_positions = HashMap::new();
-        for action in self.domain.actions.iter() {
+        for action in domain.actions.iter() {
             if !declared_actions.insert(action) {
-                return Err(SemanticErrorType::DuplicateActionDeclaration(
+                errors.push(SemanticErrorType::DuplicateActionDeclaration(
                     DuplicateError {
                         symbol: action.name.to_string(),
                         first_pos: *action_positions.get(action.name).unwrap(),
@@ -55,34 +112,28 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 action_positions.insert(action.name, action.name_pos);
             }
             // assert there is no duplicate paramter
-            let mut declared_params = HashSet::new();
-            let mut param_positions = HashMap::new();
-            for param in action.parameters.iter() {
-                if !declared_params.insert(param) {
-                    return Err(SemanticErrorType::DuplicateParameterDeclaration(
-                        DuplicateError {
-                            symbol: param.name.to_string(),
-                            first_pos: *param_positions.get(param.name).unwrap(),
-                            second_pos: param.name_pos,
-                        },
-                    ));
-                } else {
-                    param_positions.insert(param.name, param.name_pos);
-                }
-            }
+            errors.extend(DomainSemanticAnalyzer::check_duplicate_parameters(
+                &action.parameters,
+            ));
+            // assert parameter types are declared
+            errors.extend(self.type_checker.check_type_declarations(&action.parameters));
             // assert precondition predicates are declared
             match &action.preconditions {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &precond_predicates,
+                    errors.extend(self.check_formula(
+                        precondition,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !precondition.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryActionPrecondition(
+                        &declared_functions,
+                    ));
+                    // the conditions of a durative action are checked per time point
+                    let is_sat = match &action.timing {
+                        Some(timing) => timing.is_sat(),
+                        None => precondition.is_sat(),
+                    };
+                    if !is_sat {
+                        errors.push(SemanticErrorType::ComplementaryActionPrecondition(
                             action.name_pos
                         ));
                     }
@@ -92,30 +143,44 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             // assert effect predicates are declared
             match &action.effects {
                 Some(effect) => {
-                    check_predicate_declarations(effect, &self.domain.predicates)?;
-                    let eff_predicates = effect.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &eff_predicates,
+                    errors.extend(self.check_effect(
+                        effect,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !effect.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryActionEffect(
+                        &declared_functions,
+                    ));
+                    let is_consistent = match &action.timing {
+                        Some(timing) => timing.is_consistent(),
+                        None => effect.is_consistent(),
+                    };
+                    if !is_consistent {
+                        errors.push(SemanticErrorType::ComplementaryActionEffect(
                             action.name_pos
                         ));
                     }
                 }
                 _ => {}
             }
+            // assert the functions of the duration are declared
+            if let Some(timing) = &action.timing {
+                for constraint in timing.duration.iter() {
+                    errors.extend(self.type_checker.check_functions(
+                        &constraint.value.get_functions(),
+                        &action.parameters,
+                        &declared_constants,
+                        &declared_functions,
+                    ));
+                }
+            }
         }
 
         // assert methods are correct
         let mut declared_methods = HashSet::new();
         let mut method_positions = HashMap::new();
-        for method in self.domain.methods.iter() {
+        for method in domain.methods.iter() {
             if !declared_methods.insert(&method.name) {
-                return Err(SemanticErrorType::DuplicateMethodDeclaration(
+                errors.push(SemanticErrorType::DuplicateMethodDeclaration(
                     DuplicateError {
                         symbol: method.name.name.to_string(),
                         first_pos: *method_positions.get(&method.name).unwrap(),
@@ -126,34 +191,23 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 method_positions.insert(&method.name, method.name.name_pos);
             }
             // assert there is no duplicate paramter
-            let mut declared_params = HashSet::new();
-            let mut param_positions = HashMap::new();
-            for param in method.params.iter() {
-                if !declared_params.insert(param) {
-                    return Err(SemanticErrorType::DuplicateParameterDeclaration(
-                        DuplicateError {
-                            symbol: param.name.to_string(),
-                            first_pos: *param_positions.get(param.name).unwrap(),
-                            second_pos: param.name_pos,
-                        },
-                    ));
-                } else {
-                    param_positions.insert(param.name, param.name_pos);
-                }
-            }
+            errors.extend(DomainSemanticAnalyzer::check_duplicate_parameters(
+                &method.params,
+            ));
+            // assert parameter types are declared
+            errors.extend(self.type_checker.check_type_declarations(&method.params));
             // Assert preconditions are valid
             match &method.precondition {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &precond_predicates,
+                    errors.extend(self.check_formula(
+                        precondition,
                         &method.params,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
+                        &declared_functions,
+                    ));
                     if !precondition.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryMethodPrecondition(
+                        errors.push(SemanticErrorType::ComplementaryMethodPrecondition(
                             method.name.name_pos
                         ));
                     }
@@ -162,85 +216,242 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             }
             // Assert task is defined
             if !declared_tasks.contains(method.task.name) {
-                return Err(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
+                errors.push(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
                     symbol: method.task.name.to_string(),
                     position: method.task.name_pos,
                 }));
             } else {
                 // Assert task arity is consistent
-                for declared_compound_task in self.domain.compound_tasks.iter() {
-                    if method.task.name == declared_compound_task.name {
-                        if method.task_terms.len() != declared_compound_task.parameters.len() {
-                            return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
-                                symbol: method.task.name.to_string(),
-                                expected_arity: method.task_terms.len() as u32,
-                                found_arity: declared_compound_task.parameters.len() as u32,
-                                position: method.task.name_pos,
-                            }));
-                        } else {
-                            break;
-                        }
+                let declared_compound_task = declared_tasks.get(method.task.name).unwrap();
+                if method.task_terms.len() != declared_compound_task.parameters.len() {
+                    errors.push(SemanticErrorType::InconsistentTaskArity(ArityError {
+                        symbol: method.task.name.to_string(),
+                        expected_arity: method.task_terms.len() as u32,
+                        found_arity: declared_compound_task.parameters.len() as u32,
+                        position: method.task.name_pos,
+                    }));
+                } else {
+                    // Assert task type is consistent
+                    if let Err(error) = self.type_checker.is_task_consistent(
+                        &method.task,
+                        &method.task_terms,
+                        &method.params,
+                        &declared_constants,
+                        &declared_tasks,
+                        &HashSet::new(),
+                    ) {
+                        errors.push(error);
                     }
                 }
             }
 
-            // Assert task type is consistent
-            let _ = self.type_checker.is_task_consistent(
-                &method.task,
-                &method.task_terms,
-                &method.params,
-                &declared_constants,
-                &declared_tasks,
-                &HashSet::new(),
-            )?;
-
             // Assert subtask types are consistent
             for subtask in method.tn.subtasks.iter() {
-                let _ = self.type_checker.is_task_consistent(
+                if let Err(error) = self.type_checker.is_task_consistent(
                     &subtask.task,
                     &subtask.terms,
                     &method.params,
                     &declared_constants,
                     &declared_tasks,
                     &declared_actions,
-                )?;
+                ) {
+                    errors.push(error);
+                }
+            }
+            // Assert temporal constraints refer to the subtasks of the method
+            let subtask_ids: HashSet<&str> = method
+                .tn
+                .subtasks
+                .iter()
+                .filter_map(|subtask| subtask.id.as_ref().map(|id| id.name))
+                .collect();
+            for constraint in method.tn.constraints.iter().flatten() {
+                if let Constraint::Temporal(_, lhs, rhs) = constraint {
+                    for time_point in [lhs, rhs] {
+                        if !subtask_ids.contains(time_point.task_id.name) {
+                            errors.push(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
+                                symbol: time_point.task_id.name.to_string(),
+                                position: time_point.task_id.name_pos,
+                            }));
+                        }
+                    }
+                }
             }
             // Assert orderings are acyclic
             if !method.tn.orderings.is_acyclic() {
-                return Err(SemanticErrorType::CyclicOrderingDeclaration(
+                errors.push(SemanticErrorType::CyclicOrderingDeclaration(
                     method.tn.ordering_pos.unwrap(),
                 ));
             }
         }
         // Check whether all compound tasks can be refined to primitive ones
-        let tdg = TDG::new(self.domain);
-        for task in declared_tasks.iter() {
-            let reachables = tdg.reachable(&task.name);
-            if (reachables.primitives.len() == 0) && (reachables.nullable == false) {
-                warnings.push(WarningType::NoPrimitiveRefinement(WarningInfo {
-                    symbol: task.name.to_string(),
-                    position: task.name_pos,
-                }));
+        // (the TDG is only well-defined when every task reference is resolved)
+        if errors.is_empty() {
+            let tdg = TDG::new(domain);
+            for task in declared_tasks.iter() {
+                let reachables = tdg.reachable(&task.name);
+                if (reachables.primitives.len() == 0) && (reachables.nullable == false) {
+                    warnings.push(WarningType::NoPrimitiveRefinement(WarningInfo {
+                        symbol: task.name.to_string(),
+                        position: task.name_pos,
+                    }));
+                }
+            }
+        }
+        // Check for unused and redundant elements
+        warnings.extend(check_unused_types(domain));
+        warnings.extend(check_predicate_usage(domain).into_iter().filter(|warning| {
+            self.immutable_predicates || !matches!(warning, WarningType::ImmutablePredicate(_))
+        }));
+        warnings.extend(check_unused_parameters(domain));
+        warnings.extend(check_redundant_effects(domain));
+        // Check that the declared requirements match the features that the domain uses
+        for (requirement, position) in missing_requirements(domain) {
+            match self.strict_requirements {
+                true => errors.push(SemanticErrorType::UndeclaredRequirement(UndefinedSymbolError {
+                    symbol: requirement.to_string(),
+                    position,
+                })),
+                false => warnings.push(WarningType::MissingRequirement(WarningInfo {
+                    symbol: requirement.to_string(),
+                    position,
+                })),
             }
         }
+        for (requirement, position) in unused_requirements(domain) {
+            warnings.push(WarningType::UnusedRequirement(WarningInfo {
+                symbol: requirement.to_string(),
+                position,
+            }));
+        }
         let type_hierarchy = self.type_checker.get_type_hierarchy();
-        Ok(SymbolTable {
+        let symbol_table = SymbolTable {
             warnings: warnings,
             constants: declared_constants,
             predicates: declared_predicates,
+            functions: declared_functions,
             tasks: declared_tasks,
             actions: declared_actions,
             type_hierarchy: type_hierarchy,
-        })
+        };
+        (symbol_table, errors)
+    }
+
+    // checks that the predicates of a formula are declared, and then type checks
+    // those which are (so that an undefined predicate is reported only once)
+    fn check_formula(
+        &self,
+        formula: &Formula<'a>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&'a Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+        declared_functions: &HashSet<&'a Predicate<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = check_predicate_declarations(formula, &self.domain.predicates);
+        let declared_instances = formula
+            .get_propositional_predicates()
+            .into_iter()
+            .filter(|predicate| match declared_predicates.get(predicate) {
+                Some(definition) => definition.variables.len() == predicate.variables.len(),
+                None => false,
+            })
+            .collect();
+        errors.extend(self.type_checker.check_formula(
+            &declared_instances,
+            parameters,
+            declared_constants,
+            declared_predicates,
+        ));
+        errors.extend(self.type_checker.check_functions(
+            &formula.get_functions(),
+            parameters,
+            declared_constants,
+            declared_functions,
+        ));
+        errors
     }
 
-    // returns declared predicates (if there is no error)
-    fn verify_predicates(&'a self) -> Result<HashSet<&'a Predicate>, SemanticErrorType> {
+    // checks the predicates and functions of an effect, where the variables
+    // of universal effects are in the scope of their effects
+    fn check_effect(
+        &self,
+        effect: &Effect<'a>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&'a Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+        declared_functions: &HashSet<&'a Predicate<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        match effect {
+            Effect::Empty => vec![],
+            Effect::Add(predicate) | Effect::Delete(predicate) => self.check_formula(
+                &Formula::Atom(predicate.clone()),
+                parameters,
+                declared_constants,
+                declared_predicates,
+                declared_functions,
+            ),
+            Effect::And(effects) | Effect::OneOf(effects) => effects
+                .iter()
+                .flat_map(|e| {
+                    self.check_effect(
+                        e,
+                        parameters,
+                        declared_constants,
+                        declared_predicates,
+                        declared_functions,
+                    )
+                })
+                .collect(),
+            Effect::ForAll(variables, new_effect) => {
+                let mut errors = self.type_checker.check_type_declarations(variables);
+                let mut scope = parameters.clone();
+                scope.extend(variables.iter().cloned());
+                errors.extend(self.check_effect(
+                    new_effect,
+                    &scope,
+                    declared_constants,
+                    declared_predicates,
+                    declared_functions,
+                ));
+                errors
+            }
+            Effect::When(condition, new_effect) => {
+                let mut errors = self.check_formula(
+                    condition,
+                    parameters,
+                    declared_constants,
+                    declared_predicates,
+                    declared_functions,
+                );
+                errors.extend(self.check_effect(
+                    new_effect,
+                    parameters,
+                    declared_constants,
+                    declared_predicates,
+                    declared_functions,
+                ));
+                errors
+            }
+            Effect::Numeric(_, _, _) => self.type_checker.check_functions(
+                &effect.get_functions(),
+                parameters,
+                declared_constants,
+                declared_functions,
+            ),
+        }
+    }
+
+    // returns declared predicates
+    fn verify_predicates(
+        &self,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> HashSet<&'a Predicate<'a>> {
+        let domain = self.domain;
         let mut declared_predicates = HashSet::new();
         let mut predicate_positions = HashMap::new();
-        for predicate in self.domain.predicates.iter() {
+        for predicate in domain.predicates.iter() {
             if !declared_predicates.insert(predicate) {
-                return Err(SemanticErrorType::DuplicatePredicateDeclaration(
+                errors.push(SemanticErrorType::DuplicatePredicateDeclaration(
                     DuplicateError {
                         symbol: predicate.name.to_string(),
                         first_pos: *predicate_positions.get(predicate.name).unwrap(),
@@ -250,23 +461,53 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             } else {
                 predicate_positions.insert(predicate.name, predicate.name_pos);
             }
-            if let Some(error) = self
-                .type_checker
-                .check_type_declarations(&predicate.variables)
-            {
-                return Err(error);
+            errors.extend(
+                self.type_checker
+                    .check_type_declarations(&predicate.variables),
+            );
+        }
+        declared_predicates
+    }
+
+    // returns declared functions
+    fn verify_functions(
+        &self,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> HashSet<&'a Predicate<'a>> {
+        let domain = self.domain;
+        let mut declared_functions = HashSet::new();
+        let mut function_positions = HashMap::new();
+        for function in domain.functions.iter() {
+            if !declared_functions.insert(function) {
+                errors.push(SemanticErrorType::DuplicateFunctionDeclaration(
+                    DuplicateError {
+                        symbol: function.name.to_string(),
+                        first_pos: *function_positions.get(function.name).unwrap(),
+                        second_pos: function.name_pos,
+                    },
+                ));
+            } else {
+                function_positions.insert(function.name, function.name_pos);
             }
+            errors.extend(
+                self.type_checker
+                    .check_type_declarations(&function.variables),
+            );
         }
-        Ok(declared_predicates)
+        declared_functions
     }
 
-    // returns declared compound tasks (if there is no error)
-    fn verify_compound_tasks(&'a self) -> Result<HashSet<&Task<'a>>, SemanticErrorType> {
+    // returns declared compound tasks
+    fn verify_compound_tasks(
+        &self,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> HashSet<&'a Task<'a>> {
+        let domain = self.domain;
         let mut declared_tasks = HashSet::new();
         let mut task_positions = HashMap::new();
-        for task in self.domain.compound_tasks.iter() {
+        for task in domain.compound_tasks.iter() {
             if !declared_tasks.insert(task) {
-                return Err(SemanticErrorType::DuplicateCompoundTaskDeclaration(
+                errors.push(SemanticErrorType::DuplicateCompoundTaskDeclaration(
                     DuplicateError {
                         symbol: task.name.to_string(),
                         first_pos: *task_positions.get(task.name).unwrap(),
@@ -277,24 +518,53 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 task_positions.insert(task.name, task.name_pos);
             }
             // assert parameter types are declared
-            if let Some(error) = self.type_checker.check_type_declarations(&task.parameters) {
-                return Err(error);
+            errors.extend(self.type_checker.check_type_declarations(&task.parameters));
+        }
+        declared_tasks
+    }
+
+    fn check_duplicate_parameters(parameters: &Vec<Symbol<'a>>) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
+        let mut declared_params = HashSet::new();
+        let mut param_positions = HashMap::new();
+        for param in parameters.iter() {
+            if !declared_params.insert(param) {
+                errors.push(SemanticErrorType::DuplicateParameterDeclaration(
+                    DuplicateError {
+                        symbol: param.name.to_string(),
+                        first_pos: *param_positions.get(param.name).unwrap(),
+                        second_pos: param.name_pos,
+                    },
+                ));
+            } else {
+                param_positions.insert(param.name, param.name_pos);
             }
         }
-        Ok(declared_tasks)
+        errors
     }
 
     pub fn check_duplicate_requirements(
-        requirements: &'a Vec<RequirementType>,
-    ) -> Option<SemanticErrorType> {
-        let mut names = HashSet::new();
-        for req in requirements {
-            if !names.insert(req) {
-                return Some(SemanticErrorType::DuplicateRequirementDeclaration(*req));
+        requirements: &[RequirementType],
+        positions: &[TokenPosition],
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
+        let mut req_positions = HashMap::new();
+        for (req, pos) in requirements.iter().zip(positions.iter()) {
+            match req_positions.get(req) {
+                Some(first_pos) => {
+                    errors.push(SemanticErrorType::DuplicateRequirementDeclaration(
+                        DuplicateError {
+                            symbol: req.to_string(),
+                            first_pos: *first_pos,
+                            second_pos: *pos,
+                        },
+                    ));
+                }
+                None => {
+                    req_positions.insert(req, *pos);
+                }
             }
         }
-        None
+        errors
     }
-
-    // fn verify_formula(formula: &Formula<'a>, declared_predicates: HashSet<u>)
 }
@@ -1,9 +1,12 @@
 use super::*;
+use crate::lexical_analyzer::RequirementType;
 use std::collections::{HashMap, HashSet};
 
 pub struct ProblemSemanticAnalyzer<'a> {
     problem: &'a ProblemAST<'a>,
     type_checker: ProblemTypeChecker<'a>,
+    // whether the problem must be classical (i.e. have a goal, and no task network)
+    classical: bool,
 }
 
 impl<'a> ProblemSemanticAnalyzer<'a> {
@@ -14,44 +17,72 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
         ProblemSemanticAnalyzer {
             problem,
             type_checker: ProblemTypeChecker::new(domain_symbols, problem),
+            classical: false,
         }
     }
 
+    // reports the initial task network, and a missing goal as errors (i.e. for plain PDDL problems)
+    pub fn with_classical(mut self, classical: bool) -> ProblemSemanticAnalyzer<'a> {
+        self.classical = classical;
+        self
+    }
+
+    // stops at the first semantic error
     pub fn verify_problem(&self) -> Result<Vec<WarningType>, SemanticErrorType> {
-        if let Some(error) = self
-            .type_checker
-            .check_type_declarations(&self.problem.objects)
-        {
-            return Err(error);
+        let (warnings, errors) = self.verify_problem_all();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(warnings),
         }
+    }
+
+    // keeps walking the problem after an error, and returns every semantic error
+    // (in the order they are encountered) alongside the warnings
+    pub fn verify_problem_all(&self) -> (Vec<WarningType>, Vec<SemanticErrorType>) {
+        let mut errors = self
+            .type_checker
+            .check_type_declarations(&self.problem.objects);
 
         // check for duplicate objects
-        let mut object_types = HashMap::new();
+        let mut object_names = HashSet::new();
         let mut object_positions = HashMap::new();
         for obj in self.problem.objects.iter() {
-            if object_types.contains_key(obj.name) {
-                return Err(SemanticErrorType::DuplicateObjectDeclaration(
+            if !object_names.insert(obj.name) {
+                errors.push(SemanticErrorType::DuplicateObjectDeclaration(
                     DuplicateError {
                         symbol: obj.name.to_string(),
                         first_pos: *object_positions.get(obj.name).unwrap(),
                         second_pos: obj.name_pos,
-                    }, 
+                    },
                 ));
             } else {
-                object_types.insert(obj.name, obj.symbol_type);
                 object_positions.insert(obj.name, obj.name_pos);
             }
         }
 
         // check the consistency of init predicates
         for predicate in self.problem.init_state.iter() {
-            let _ = self.type_checker.check_predicate_instantiation(predicate)?;
+            if let Err(error) = self.type_checker.check_predicate_instantiation(predicate) {
+                errors.push(error);
+            }
+        }
+        // and of the initial values of functions
+        for value in self.problem.init_values.iter() {
+            if let Err(error) = self.type_checker.check_function_instantiation(&value.function) {
+                errors.push(error);
+            }
         }
 
         // check the initial task network
+        if let (true, Some(position)) = (self.classical, self.problem.init_tn_pos) {
+            errors.push(SemanticErrorType::NonClassicalConstruct(UndefinedSymbolError {
+                symbol: "initial task network".to_string(),
+                position,
+            }));
+        }
         if let Some(htn) = &self.problem.init_tn {
             if !htn.tn.orderings.is_acyclic() {
-                return Err(
+                errors.push(
                     SemanticErrorType::CyclicOrderingDeclaration(
                         htn.tn.ordering_pos.unwrap()
                     )
@@ -59,9 +90,12 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
             }
 
             for subtask in htn.tn.subtasks.iter() {
-                let _ = self
+                if let Err(error) = self
                     .type_checker
-                    .check_subtask_instantiation(subtask, &htn.parameters)?;
+                    .check_subtask_instantiation(subtask, &htn.parameters)
+                {
+                    errors.push(error);
+                }
             }
         }
 
@@ -69,18 +103,74 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
         match &self.problem.goal {
             Some(goal) => {
                 for predicate in goal.get_propositional_predicates() {
-                    let _ = self.type_checker.check_predicate_instantiation(predicate)?;
+                    if let Err(error) = self.type_checker.check_predicate_instantiation(predicate) {
+                        errors.push(error);
+                    }
                 }
+                for function in goal.get_functions() {
+                    if let Err(error) = self.type_checker.check_function_instantiation(function) {
+                        errors.push(error);
+                    }
+                }
+                if !goal.is_sat() {
+                    errors.push(SemanticErrorType::ComplementaryGoal(
+                        self.problem.goal_pos.unwrap_or(self.problem.domain_name_pos),
+                    ));
+                }
+            }
+            None if self.classical => {
+                errors.push(SemanticErrorType::MissingGoal(self.problem.domain_name_pos));
             }
             None => {}
         }
 
-        Ok(self
+        // check the plan metric ("total-time" is the length of the plan)
+        if let Some(metric) = &self.problem.metric {
+            for function in metric.expression.get_functions() {
+                if function.name == "total-time" && function.variables.is_empty() {
+                    continue;
+                }
+                if let Err(error) = self.type_checker.check_function_instantiation(function) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        // types of objects, and predicates and requirements of the goal are used by the problem
+        let object_types: HashSet<&str> = self
+            .problem
+            .objects
+            .iter()
+            .filter_map(|obj| obj.symbol_type)
+            .collect();
+        let goal_predicates: HashSet<&str> = match &self.problem.goal {
+            Some(goal) => goal
+                .get_propositional_predicates()
+                .iter()
+                .map(|predicate| predicate.name)
+                .collect(),
+            None => HashSet::new(),
+        };
+        let warnings = self
             .type_checker
             .symbol_table
             .warnings
             .iter()
+            .filter(|warning| match warning {
+                WarningType::UnusedType(info) => !object_types.contains(info.symbol.as_str()),
+                WarningType::UnusedPredicate(info) => {
+                    !goal_predicates.contains(info.symbol.as_str())
+                }
+                WarningType::UnusedRequirement(info) => match &self.problem.goal {
+                    Some(goal) => !RequirementType::ALL.iter().any(|requirement| {
+                        requirement.to_string() == info.symbol && is_used_by(requirement, goal)
+                    }),
+                    None => true,
+                },
+                _ => true,
+            })
             .cloned()
-            .collect())
+            .collect();
+        (warnings, errors)
     }
 }
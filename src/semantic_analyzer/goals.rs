@@ -0,0 +1,54 @@
+use crate::grounding::Grounder;
+
+use super::*;
+
+// the facts that the goal requires which cannot be reached from the initial
+// state, even if the deletes of actions are ignored (the domain and the problem
+// are expected to be verified)
+pub fn unreachable_goals<'a>(
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    symbol_table: &'a SymbolTable<'a>,
+) -> Vec<WarningType> {
+    let goal = match &problem.goal {
+        Some(goal) => goal,
+        None => return vec![],
+    };
+    let model = Grounder::new(domain, problem, symbol_table).ground();
+    let reachable = model.relaxed_reachable_facts();
+    let mut facts = vec![];
+    goal_facts(goal, &mut facts);
+    let mut warnings = vec![];
+    for predicate in facts {
+        let args: Vec<&str> = predicate.variables.iter().map(|arg| arg.name).collect();
+        let is_initial = problem.init_state.iter().any(|fact| {
+            fact.name == predicate.name && fact.variables.iter().map(|arg| arg.name).eq(args.iter().copied())
+        });
+        let is_reachable = model.facts.iter().any(|fact| {
+            fact.predicate == predicate.name && fact.args == args && reachable.contains(&fact.id)
+        });
+        if !is_initial && !is_reachable {
+            let mut atom = vec![predicate.name];
+            atom.extend(args);
+            warnings.push(WarningType::UnreachableGoal(WarningInfo {
+                symbol: format!("({})", atom.join(" ")),
+                position: predicate.name_pos,
+            }));
+        }
+    }
+    warnings
+}
+
+// the atoms that every state satisfying the goal contains (i.e. the ones
+// outside negations, disjunctions and quantifiers)
+fn goal_facts<'a, 'b>(formula: &'b Formula<'a>, facts: &mut Vec<&'b Predicate<'a>>) {
+    match formula {
+        Formula::Atom(predicate) => facts.push(predicate),
+        Formula::And(operands) => {
+            for operand in operands {
+                goal_facts(operand, facts);
+            }
+        }
+        _ => {}
+    }
+}
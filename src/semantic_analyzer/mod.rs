@@ -1,15 +1,24 @@
 mod undefined_elements;
+mod unused_elements;
 mod type_checker;
 mod tests;
 mod analyzers;
 mod tdg;
+mod requirements;
+mod goals;
+mod options;
 
 use crate::syntactic_analyzer::*;
 use crate::output::*;
 use undefined_elements::*;
+use unused_elements::*;
 use type_checker::*;
 
 extern crate petgraph;
 
 pub use analyzers::*;
-pub use tdg::TDG;
\ No newline at end of file
+pub use tdg::{TaskType, TDG};
+pub use type_checker::DomainTypeChecker;
+pub use requirements::{is_used_by, missing_requirements, unused_requirements};
+pub use goals::unreachable_goals;
+pub use options::VerifyOptions;
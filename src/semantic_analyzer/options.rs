@@ -0,0 +1,11 @@
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyOptions {
+    // report features used without their requirement as errors (rather than warnings)
+    pub strict_requirements: bool,
+    // verify a plain PDDL domain and problem (i.e. without tasks and methods, and
+    // with a goal), and report the goal facts that are not reachable
+    pub classical: bool,
+    // report the predicates that are used in preconditions, but changed by no
+    // action (these are usually static facts of the problems, e.g. types)
+    pub immutable_predicates: bool,
+}
@@ -0,0 +1,254 @@
+use crate::lexical_analyzer::{OperationType, RequirementType};
+use crate::TokenPosition;
+
+use super::*;
+
+// the requirements that the domain relies on without declaring them, with
+// the position of their first use
+pub fn missing_requirements(domain: &DomainAST) -> Vec<(RequirementType, TokenPosition)> {
+    let uses = requirement_uses(domain);
+    let declared = declared_requirements(domain);
+    RequirementType::ALL
+        .iter()
+        .filter(|requirement| !declared.contains(requirement))
+        .filter_map(|requirement| {
+            uses.required
+                .iter()
+                .find(|(used, _)| used == requirement)
+                .copied()
+        })
+        .collect()
+}
+
+// the declared requirements that no feature of the domain relies on (neither
+// directly nor through the requirements they imply), with their positions
+pub fn unused_requirements(domain: &DomainAST) -> Vec<(RequirementType, TokenPosition)> {
+    let uses = requirement_uses(domain);
+    domain
+        .requirements
+        .iter()
+        .zip(domain.requirement_positions.iter())
+        .filter(|(requirement, _)| is_checked(requirement))
+        .filter(|(requirement, _)| !uses.is_used(requirement))
+        .map(|(requirement, position)| (*requirement, *position))
+        .collect()
+}
+
+// the declared requirements, and those that they imply
+fn declared_requirements(domain: &DomainAST) -> Vec<RequirementType> {
+    let mut declared = domain.requirements.clone();
+    for requirement in domain.requirements.iter() {
+        declared.extend(requirement.implies());
+    }
+    declared
+}
+
+// whether the uses of the requirement are tracked (":strips" has no feature
+// of its own, and the remaining PDDL 2.1+ features are not analyzed)
+fn is_checked(requirement: &RequirementType) -> bool {
+    !matches!(
+        requirement,
+        RequirementType::STRIPS
+            | RequirementType::ObjectFluents
+            | RequirementType::Fluents
+            | RequirementType::ContinuousEffects
+            | RequirementType::DerivedPredicates
+            | RequirementType::TimedInitialLiterals
+            | RequirementType::Preferences
+            | RequirementType::Constraints
+    )
+}
+
+// whether the formula (e.g. the goal of a problem) relies on the requirement,
+// either directly or through the requirements it implies
+pub fn is_used_by(requirement: &RequirementType, formula: &Formula) -> bool {
+    let mut uses = RequirementUses::default();
+    precondition_uses(formula, &mut uses);
+    uses.is_used(requirement)
+}
+
+#[derive(Default)]
+struct RequirementUses {
+    // the features that need a requirement (in the order of the domain)
+    required: Vec<(RequirementType, TokenPosition)>,
+    // the features that can also be written with another requirement, so
+    // they justify declaring one without requiring it (e.g. a negated
+    // equality is covered by ":equality" alone)
+    optional: Vec<RequirementType>,
+}
+
+impl RequirementUses {
+    fn push(&mut self, requirement_use: (RequirementType, TokenPosition)) {
+        self.required.push(requirement_use);
+    }
+
+    fn is_used(&self, requirement: &RequirementType) -> bool {
+        self.required
+            .iter()
+            .map(|(used, _)| used)
+            .chain(self.optional.iter())
+            .any(|used| used == requirement || requirement.implies().contains(used))
+    }
+}
+
+// every use of a feature that needs a requirement
+fn requirement_uses(domain: &DomainAST) -> RequirementUses {
+    let mut uses = RequirementUses::default();
+    let typed = |symbols: &Vec<Symbol>, uses: &mut RequirementUses| {
+        for symbol in symbols {
+            if let Some(type_pos) = symbol.type_pos {
+                uses.push((RequirementType::TypedObjects, type_pos));
+            }
+        }
+    };
+    if let Some(declared_type) = domain.types.iter().flatten().next() {
+        uses.push((RequirementType::TypedObjects, declared_type.name_pos));
+    }
+    typed(domain.constants.as_ref().unwrap_or(&vec![]), &mut uses);
+    for predicate in domain.predicates.iter() {
+        typed(&predicate.variables, &mut uses);
+    }
+    for function in domain.functions.iter() {
+        typed(&function.variables, &mut uses);
+        // (numeric fluents imply action costs)
+        if function.name != "total-cost" {
+            uses.push((RequirementType::NumericFluents, function.name_pos));
+        } else {
+            uses.push((RequirementType::ActionCosts, function.name_pos));
+        }
+    }
+    if let Some(task) = domain.compound_tasks.first() {
+        uses.push((RequirementType::Hierarchy, task.name_pos));
+    }
+    for task in domain.compound_tasks.iter() {
+        typed(&task.parameters, &mut uses);
+    }
+    for method in domain.methods.iter() {
+        uses.push((RequirementType::Hierarchy, method.name.name_pos));
+        typed(&method.params, &mut uses);
+        if let Some(precondition) = &method.precondition {
+            if !matches!(precondition, Formula::Empty) {
+                uses.push((RequirementType::MethodPreconditions, method.name.name_pos));
+            }
+            precondition_uses(precondition, &mut uses);
+        }
+    }
+    for action in domain.actions.iter() {
+        typed(&action.parameters, &mut uses);
+        if let Some(timing) = &action.timing {
+            uses.push((RequirementType::DurativeActions, action.name_pos));
+            for constraint in timing.duration.iter() {
+                if constraint.operation != OperationType::Equal {
+                    uses.push((RequirementType::DurationInequalities, constraint.position));
+                }
+            }
+        }
+        if let Some(precondition) = &action.preconditions {
+            precondition_uses(precondition, &mut uses);
+        }
+        if let Some(effect) = &action.effects {
+            effect_uses(effect, &mut uses);
+        }
+    }
+    uses
+}
+
+fn effect_uses(effect: &Effect, uses: &mut RequirementUses) {
+    match effect {
+        Effect::Empty | Effect::Add(_) | Effect::Delete(_) | Effect::Numeric(_, _, _) => {}
+        Effect::And(effects) | Effect::OneOf(effects) => {
+            for e in effects {
+                effect_uses(e, uses);
+            }
+        }
+        Effect::ForAll(variables, new_effect) => {
+            if let Some(variable) = variables.first() {
+                uses.push((RequirementType::ConditionalEffects, variable.name_pos));
+            }
+            for variable in variables {
+                if let Some(type_pos) = variable.type_pos {
+                    uses.push((RequirementType::TypedObjects, type_pos));
+                }
+            }
+            effect_uses(new_effect, uses);
+        }
+        Effect::When(condition, new_effect) => {
+            let position = first_atom(condition).or(new_effect.get_predicates().first().copied());
+            if let Some(predicate) = position {
+                uses.push((RequirementType::ConditionalEffects, predicate.name_pos));
+            }
+            precondition_uses(condition, uses);
+            effect_uses(new_effect, uses);
+        }
+    }
+}
+
+fn precondition_uses(formula: &Formula, uses: &mut RequirementUses) {
+    match formula {
+        Formula::Empty | Formula::Atom(_) => {}
+        Formula::Not(new_formula) => {
+            // a negated equality only needs ":equality", but it is still a use
+            // of ":negative-preconditions" when that is declared (e.g. Hiking)
+            if let Some(atom) = first_atom(new_formula) {
+                uses.push((RequirementType::NegativePreconditions, atom.name_pos));
+            } else if matches!(**new_formula, Formula::Equals(_, _)) {
+                uses.optional.push(RequirementType::NegativePreconditions);
+            }
+            precondition_uses(new_formula, uses);
+        }
+        Formula::And(new_formula) => {
+            for f in new_formula {
+                precondition_uses(f, uses);
+            }
+        }
+        Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            if let Some(atom) = first_atom(formula) {
+                uses.push((RequirementType::DisjunctivePreconditions, atom.name_pos));
+            }
+            for f in new_formula {
+                precondition_uses(f, uses);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            if let Some(atom) = first_atom(formula) {
+                uses.push((RequirementType::DisjunctivePreconditions, atom.name_pos));
+            }
+            for f in ps.iter().chain(qs.iter()) {
+                precondition_uses(f, uses);
+            }
+        }
+        Formula::ForAll(variables, new_formula) | Formula::Exists(variables, new_formula) => {
+            let requirement = match formula {
+                Formula::ForAll(_, _) => RequirementType::UniversalPreconditions,
+                _ => RequirementType::ExistentialPreconditions,
+            };
+            if let Some(variable) = variables.first() {
+                uses.push((requirement, variable.name_pos));
+            }
+            for variable in variables {
+                if let Some(type_pos) = variable.type_pos {
+                    uses.push((RequirementType::TypedObjects, type_pos));
+                }
+            }
+            precondition_uses(new_formula, uses);
+        }
+        Formula::Equals(left, _) => uses.push((RequirementType::Equality, left.name_pos)),
+        // the functions are declared with their requirement
+        Formula::Comparison(_, _, _) => {}
+    }
+}
+
+// the first predicate of the formula (including the quantified ones)
+fn first_atom<'a, 'b>(formula: &'b Formula<'a>) -> Option<&'b Predicate<'a>> {
+    match formula {
+        Formula::Empty | Formula::Equals(_, _) | Formula::Comparison(_, _, _) => None,
+        Formula::Atom(predicate) => Some(predicate),
+        Formula::Not(new_formula)
+        | Formula::ForAll(_, new_formula)
+        | Formula::Exists(_, new_formula) => first_atom(new_formula),
+        Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            new_formula.iter().find_map(|f| first_atom(f))
+        }
+        Formula::Imply(ps, qs) => ps.iter().chain(qs.iter()).find_map(|f| first_atom(f)),
+    }
+}
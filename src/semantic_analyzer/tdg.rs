@@ -68,6 +68,13 @@ impl<'a> TDG<'a> {
         }
     }
 
+    pub fn task_type(&self, task_name: &str) -> Option<TaskType> {
+        self.tasks
+            .iter()
+            .find(|(name, _)| *name == task_name)
+            .map(|(_, task_type)| *task_type)
+    }
+
     pub fn reachable(&self, task_name: &str) -> ReachableSet {
         let mut reach_t = HashSet::new();
         let task_index = match self
@@ -518,8 +525,8 @@ impl<'a> TDG<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-enum TaskType {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskType {
     Primitive,
     Compound,
 }
@@ -0,0 +1,133 @@
+use super::*;
+
+fn get_domain() -> Vec<u8> {
+    "(define (domain logistics)
+        (:requirements :typing :negative-preconditions)
+        (:types location truck)
+        (:predicates (road ?a ?b - location) (at ?t - truck ?l - location) (loaded ?t - truck))
+        (:action drive
+            :parameters (?t - truck ?a ?b - location)
+            :precondition (and (at ?t ?a) (road ?a ?b))
+            :effect (and (not (at ?t ?a)) (at ?t ?b)))
+        (:action load
+            :parameters (?t - truck)
+            :precondition (not (loaded ?t))
+            :effect (loaded ?t))
+    ) "
+    .as_bytes()
+    .to_vec()
+}
+
+fn get_problem(goal: &str) -> Vec<u8> {
+    format!(
+        "(define (problem p1)
+            (:domain logistics)
+            (:objects l1 l2 l3 - location t1 - truck)
+            (:init (road l1 l2) (road l2 l1) (at t1 l1))
+            {}
+        ) ",
+        goal
+    )
+    .into_bytes()
+}
+
+// the errors of the problem, and the unreachable goal facts if there is none
+fn verify_classical(domain: &Vec<u8>, problem: &Vec<u8>) -> (Vec<SemanticErrorType>, Vec<WarningType>) {
+    let domain_parser = Parser::new(LexicalAnalyzer::new(domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let symbol_table = DomainSemanticAnalyzer::new(&d)
+                .with_classical(true)
+                .verify_domain()
+                .unwrap();
+            let (_, errors) = ProblemSemanticAnalyzer::new(&p, symbol_table.clone())
+                .with_classical(true)
+                .verify_problem_all();
+            if !errors.is_empty() {
+                return (errors, vec![]);
+            }
+            (errors, unreachable_goals(&d, &p, &symbol_table))
+        }
+        _ => panic!("expected a domain and a problem"),
+    }
+}
+
+#[test]
+pub fn reachable_goal_test() {
+    let problem = get_problem("(:goal (and (at t1 l2) (loaded t1) (road l1 l2)))");
+    let (errors, warnings) = verify_classical(&get_domain(), &problem);
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+pub fn unreachable_goal_test() {
+    // there is no road to l3, and (road l2 l3) is static
+    let problem = get_problem("(:goal (and (at t1 l2) (at t1 l3) (road l2 l3)))");
+    let (errors, warnings) = verify_classical(&get_domain(), &problem);
+    assert!(errors.is_empty(), "{:?}", errors);
+    let unreachable: Vec<_> = warnings
+        .iter()
+        .map(|warning| match warning {
+            WarningType::UnreachableGoal(info) => (info.symbol.as_str(), info.position.line),
+            warning => panic!("{:?}", warning),
+        })
+        .collect();
+    assert_eq!(unreachable, vec![("(at t1 l3)", 5), ("(road l2 l3)", 5)]);
+}
+
+#[test]
+pub fn complementary_goal_test() {
+    let problem = get_problem("(:goal\n (and (at t1 l2) (not (at t1 l2))))");
+    let (errors, _) = verify_classical(&get_domain(), &problem);
+    match &errors[..] {
+        [SemanticErrorType::ComplementaryGoal(position)] => assert_eq!(position.line, 5),
+        errors => panic!("{:?}", errors),
+    }
+}
+
+#[test]
+pub fn non_classical_problem_test() {
+    let problem = get_problem("(:htn :parameters () :subtasks (and (t1 (load t1))))");
+    let (errors, _) = verify_classical(&get_domain(), &problem);
+    match &errors[..] {
+        [SemanticErrorType::NonClassicalConstruct(construct), SemanticErrorType::MissingGoal(position)] => {
+            assert_eq!(construct.symbol, "initial task network");
+            assert_eq!(construct.position.line, 5);
+            assert_eq!(position.line, 2);
+        }
+        errors => panic!("{:?}", errors),
+    }
+}
+
+#[test]
+pub fn non_classical_domain_test() {
+    let domain = "(define (domain d)
+        (:requirements :hierarchy)
+        (:task deliver :parameters ())
+        (:method m_deliver
+            :parameters ()
+            :task (deliver)
+            :subtasks ())
+    ) "
+    .as_bytes()
+    .to_vec();
+    let parser = Parser::new(LexicalAnalyzer::new(&domain));
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let (_, errors) = DomainSemanticAnalyzer::new(&d).with_classical(true).verify_domain_all();
+            let constructs: Vec<_> = errors
+                .iter()
+                .map(|error| match error {
+                    SemanticErrorType::NonClassicalConstruct(info) => (info.symbol.as_str(), info.position.line),
+                    error => panic!("{:?}", error),
+                })
+                .collect();
+            assert_eq!(constructs, vec![("task deliver", 3), ("method m_deliver", 4)]);
+            // hierarchical domains are valid otherwise
+            assert!(DomainSemanticAnalyzer::new(&d).verify_domain().is_ok());
+        }
+        _ => panic!(),
+    }
+}
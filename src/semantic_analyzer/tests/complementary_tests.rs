@@ -156,4 +156,85 @@ pub fn unsat_method_prec_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn conditional_effect_complementary_test () {
+    let domain = |effect: &str| {
+        format!(
+            "(define (domain bal)
+                (:predicates (at ?l) (free ?l))
+                (:action move
+                    :parameters (?l1 ?l2)
+                    :effect {}
+                )
+            ) ",
+            effect
+        )
+        .into_bytes()
+    };
+    let is_complementary = |program: Vec<u8>| {
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        match parser.parse().unwrap() {
+            AbstractSyntaxTree::Domain(d) => {
+                let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+                match semantic_analyzer.verify_domain() {
+                    Err(SemanticErrorType::ComplementaryActionEffect(position)) => {
+                        assert_eq!(position.line, 3);
+                        true
+                    }
+                    Err(error) => panic!("{:?}", error),
+                    Ok(_) => false,
+                }
+            }
+            _ => panic!()
+        }
+    };
+    // the conditional effect contradicts the unconditional one whenever it applies
+    assert!(is_complementary(domain("(and (at ?l2) (when (free ?l1) (not (at ?l2))))")));
+    assert!(is_complementary(domain("(when (free ?l1) (and (at ?l2) (not (at ?l2))))")));
+    // the conditions are not contradictory effects
+    assert!(!is_complementary(domain("(and (at ?l2) (when (not (at ?l2)) (free ?l1)))")));
+    assert!(!is_complementary(domain("(and (when (free ?l1) (at ?l2)) (when (free ?l2) (not (at ?l2))))")));
+}
+
+#[test]
+pub fn durative_action_consistency_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:predicates (at ?l) (busy))
+            (:durative-action move
+                :parameters (?a ?b)
+                :duration (= ?duration 1)
+                :condition (and (at start (at ?a)) (at end (not (at ?a))))
+                :effect (and (at start (not (at ?a))) (at end (at ?a)))
+            )
+            (:durative-action wait
+                :parameters (?a)
+                :duration (= ?duration 1)
+                :condition (and (at start (at ?a)) (over all (not (at ?a))))
+                :effect (and (at end (busy)) (at end (not (busy))))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            // conditions and effects only conflict at the same time point
+            let (_, errors) = DomainSemanticAnalyzer::new(&d).verify_domain_all();
+            let lines: Vec<_> = errors
+                .iter()
+                .map(|error| match error {
+                    SemanticErrorType::ComplementaryActionPrecondition(position) => ("precondition", position.line),
+                    SemanticErrorType::ComplementaryActionEffect(position) => ("effect", position.line),
+                    error => panic!("{:?}", error),
+                })
+                .collect();
+            assert_eq!(lines, vec![("precondition", 9), ("effect", 9)]);
+        }
+        _ => panic!(),
+    }
+}
@@ -84,8 +84,8 @@ pub fn cyclic_types_test() {
                 }
                 Err(error) => {
                     match error {
-                        SemanticErrorType::CyclicTypeDeclaration => {
-                            // TODO: assert locality in future
+                        SemanticErrorType::CyclicTypeDeclaration(pos) => {
+                            assert!(pos.line >= 3 && pos.line <= 6);
                         }
                         _ => {
                             panic!("caught wrong error")
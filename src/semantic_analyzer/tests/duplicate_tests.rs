@@ -84,8 +84,9 @@ pub fn requirements_duplicate_test() {
                 Err(error) => {
                     match error {
                         SemanticErrorType::DuplicateRequirementDeclaration(x) => {
-                            assert!(matches!(x, RequirementType::Hierarchy))
-                            // TODO: assert locality in future
+                            assert_eq!(x.symbol, ":hierarchy");
+                            assert_eq!(x.first_pos.column, 28);
+                            assert_eq!(x.second_pos.column, 61);
                         }
                         _ => {
                             panic!("caught wrong error")
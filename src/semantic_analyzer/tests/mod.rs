@@ -6,6 +6,12 @@ mod tdg_tests;
 mod problem_test;
 mod warning_tests;
 mod complementary_tests;
+#[cfg(test)]
+mod numeric_tests;
+#[cfg(test)]
+mod requirement_tests;
+#[cfg(test)]
+mod classical_tests;
 
 use super::*;
 use crate::syntactic_analyzer::*;
@@ -0,0 +1,180 @@
+use super::*;
+
+fn get_domain(precondition: &str, effect: &str) -> Vec<u8> {
+    format!(
+        "(define (domain transport)
+            (:requirements :typing :numeric-fluents)
+            (:types location vehicle)
+            (:predicates (at ?v - vehicle ?l - location))
+            (:functions
+                (road-length ?l1 ?l2 - location) - number
+                (fuel ?v - vehicle) - number
+                (total-cost) - number
+            )
+            (:action drive
+                :parameters (?v - vehicle ?from ?to - location)
+                :precondition (and (at ?v ?from) {})
+                :effect (and (not (at ?v ?from)) (at ?v ?to) {})
+            )
+        ) ",
+        precondition, effect
+    )
+    .into_bytes()
+}
+
+#[test]
+pub fn consistent_functions_test() {
+    let program = get_domain(
+        "(>= (fuel ?v) (road-length ?from ?to))",
+        "(decrease (fuel ?v) (road-length ?from ?to)) (increase (total-cost) 1)",
+    );
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (symbol_table, errors) = semantic_parser.verify_domain_all();
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert_eq!(symbol_table.functions.len(), 3);
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn undefined_function_test() {
+    let program = get_domain("(> (capacity ?v) 0)", "");
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            match semantic_parser.verify_domain() {
+                Err(SemanticErrorType::UndefinedFunction(error)) => {
+                    assert_eq!(error.symbol, "capacity");
+                    assert_eq!(error.position.line, 12);
+                }
+                result => panic!("{:?}", result.err()),
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn inconsistent_function_test() {
+    let program = get_domain("(> (fuel ?v ?to) 0)", "(increase (road-length ?to ?v) 1)");
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.verify_domain_all();
+            assert_eq!(errors.len(), 2, "{:?}", errors);
+            match &errors[0] {
+                SemanticErrorType::InconsistentFunctionArity(error) => {
+                    assert_eq!(error.symbol, "fuel");
+                    assert_eq!(error.expected_arity, 1);
+                    assert_eq!(error.found_arity, 2);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &errors[1] {
+                SemanticErrorType::InconsistentFunctionArgType(error) => {
+                    assert_eq!(error.var_name, "v");
+                    assert_eq!(error.expected.as_ref().unwrap(), "location");
+                    assert_eq!(error.found.as_ref().unwrap(), "vehicle");
+                    assert_eq!(error.position.line, 13);
+                }
+                error => panic!("{:?}", error),
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn duplicate_function_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:functions (cost) - number (cost))
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            match semantic_parser.verify_domain() {
+                Err(SemanticErrorType::DuplicateFunctionDeclaration(error)) => {
+                    assert_eq!(error.symbol, "cost");
+                    assert_eq!(error.first_pos.line, 2);
+                    assert_eq!(error.second_pos.line, 2);
+                }
+                result => panic!("{:?}", result.err()),
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn problem_function_values_test() {
+    let domain = get_domain("", "(increase (total-cost) (road-length ?from ?to))");
+    let lexer = LexicalAnalyzer::new(&domain);
+    let parser = Parser::new(lexer);
+    let d = match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => d,
+        _ => panic!(),
+    };
+    let semantic_parser = DomainSemanticAnalyzer::new(&d);
+    let symbol_table = semantic_parser.verify_domain().unwrap();
+
+    let problem = String::from(
+        "(define (problem p) (:domain transport)
+            (:objects truck - vehicle a b - location)
+            (:init
+                (at truck a)
+                (= (road-length a b) 10)
+                (= (fuel a) 5)
+                (= (speed truck) 1)
+                (= (total-cost) 0)
+            )
+            (:goal (and (at truck b) (< (road-length a) 20)))
+            (:metric minimize (+ (total-cost) (total-time)))
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&problem);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Problem(p) => {
+            let semantic_parser = ProblemSemanticAnalyzer::new(&p, symbol_table);
+            let (_, errors) = semantic_parser.verify_problem_all();
+            assert_eq!(errors.len(), 3, "{:?}", errors);
+            match &errors[0] {
+                SemanticErrorType::InconsistentFunctionArgType(error) => {
+                    assert_eq!(error.var_name, "fuel");
+                    assert_eq!(error.position.line, 6);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &errors[1] {
+                SemanticErrorType::UndefinedFunction(error) => {
+                    assert_eq!(error.symbol, "speed");
+                    assert_eq!(error.position.line, 7);
+                }
+                error => panic!("{:?}", error),
+            }
+            match &errors[2] {
+                SemanticErrorType::InconsistentFunctionArity(error) => {
+                    assert_eq!(error.symbol, "road-length");
+                    assert_eq!(error.position.line, 10);
+                }
+                error => panic!("{:?}", error),
+            }
+        }
+        _ => panic!(),
+    }
+}
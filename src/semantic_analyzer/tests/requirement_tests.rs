@@ -0,0 +1,162 @@
+use super::*;
+
+fn get_domain(requirements: &str) -> Vec<u8> {
+    format!(
+        "(define (domain d)
+            (:requirements {})
+            (:types location)
+            (:predicates (at ?l - location) (road ?a ?b - location))
+            (:action move
+                :parameters (?a ?b - location)
+                :precondition (and (at ?a) (or (road ?a ?b) (exists (?c - location) (road ?c ?b))))
+                :effect (and (not (at ?a)) (at ?b))
+            )
+        ) ",
+        requirements
+    )
+    .into_bytes()
+}
+
+// the missing and the unused requirements that the analyzer warns about
+fn requirement_warnings(program: &Vec<u8>) -> (Vec<String>, Vec<String>) {
+    let lexer = LexicalAnalyzer::new(program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let (symbol_table, errors) = DomainSemanticAnalyzer::new(&d).verify_domain_all();
+            assert!(errors.is_empty(), "{:?}", errors);
+            let mut missing = vec![];
+            let mut unused = vec![];
+            for warning in symbol_table.warnings.iter() {
+                match warning {
+                    WarningType::MissingRequirement(info) => missing.push(info.symbol.clone()),
+                    WarningType::UnusedRequirement(info) => unused.push(info.symbol.clone()),
+                    _ => {}
+                }
+            }
+            (missing, unused)
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn missing_and_unused_requirements_test() {
+    let program = get_domain(":typing :equality :hierarchy");
+    let (missing, unused) = requirement_warnings(&program);
+    assert_eq!(missing, vec![":existential-preconditions", ":disjunctive-preconditions"]);
+    assert_eq!(unused, vec![":equality", ":hierarchy"]);
+}
+
+#[test]
+pub fn implied_requirements_test() {
+    let program = get_domain(":adl");
+    assert_eq!(requirement_warnings(&program), (vec![], vec![]));
+    let program = get_domain(":typing :quantified-preconditions :disjunctive-preconditions");
+    assert_eq!(requirement_warnings(&program), (vec![], vec![]));
+    // the unchecked requirements are never unused
+    let program = get_domain(":strips :adl :constraints");
+    assert_eq!(requirement_warnings(&program), (vec![], vec![]));
+}
+
+#[test]
+pub fn strict_requirements_test() {
+    let program = get_domain(":typing :disjunctive-preconditions");
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d).with_strict_requirements(true);
+            match semantic_analyzer.verify_domain() {
+                Err(SemanticErrorType::UndeclaredRequirement(undefined)) => {
+                    assert_eq!(undefined.symbol, ":existential-preconditions");
+                    assert_eq!(undefined.position.line, 7);
+                }
+                result => panic!("{:?}", result.map(|_| ())),
+            }
+        }
+        _ => panic!(),
+    }
+}
+
+#[test]
+pub fn durative_requirements_test() {
+    let program = String::from(
+        "(define (domain d)
+            (:requirements :typing :duration-inequalities)
+            (:types location)
+            (:predicates (at ?l - location))
+            (:durative-action move
+                :parameters (?a ?b - location)
+                :duration (<= ?duration 10)
+                :condition (at start (at ?a))
+                :effect (and (at start (not (at ?a))) (at end (at ?b)))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let (missing, unused) = requirement_warnings(&program);
+    assert_eq!(missing, vec![":durative-actions"]);
+    assert!(unused.is_empty());
+}
+
+#[test]
+pub fn negated_equality_requirements_test() {
+    let program = String::from(
+        "(define (domain d)
+            (:requirements :typing :equality :negative-preconditions)
+            (:types location)
+            (:predicates (at ?l - location))
+            (:action move
+                :parameters (?a ?b - location)
+                :precondition (and (at ?a) (not (= ?a ?b)))
+                :effect (and (not (at ?a)) (at ?b))
+            )
+        ) ",
+    )
+    .into_bytes();
+    assert_eq!(requirement_warnings(&program), (vec![], vec![]));
+    // but a negated equality does not need ":negative-preconditions"
+    let program = String::from_utf8(program)
+        .unwrap()
+        .replace(" :negative-preconditions", "")
+        .into_bytes();
+    assert_eq!(requirement_warnings(&program), (vec![], vec![]));
+}
+
+#[test]
+pub fn negated_goal_requirements_test() {
+    let domain = get_domain(
+        ":typing :disjunctive-preconditions :existential-preconditions :negative-preconditions",
+    );
+    let problem = String::from(
+        "(define (problem p)
+            (:domain d)
+            (:objects l1 l2 - location)
+            (:init (at l1) (road l1 l2))
+            (:goal (not (at l1)))
+        ) ",
+    )
+    .into_bytes();
+    // the domain alone does not use negation
+    let (_, unused) = requirement_warnings(&domain);
+    assert_eq!(unused, vec![":negative-preconditions"]);
+    let domain_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let (symbol_table, _) = DomainSemanticAnalyzer::new(&d).verify_domain_all();
+            let (warnings, errors) =
+                ProblemSemanticAnalyzer::new(&p, symbol_table).verify_problem_all();
+            assert!(errors.is_empty(), "{:?}", errors);
+            assert!(
+                !warnings
+                    .iter()
+                    .any(|warning| matches!(warning, WarningType::UnusedRequirement(_))),
+                "{:?}",
+                warnings
+            );
+        }
+        _ => panic!(),
+    }
+}
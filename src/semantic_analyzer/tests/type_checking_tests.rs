@@ -284,4 +284,43 @@ pub fn method_subtask_checking_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn effect_scope_type_checking_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:types t1 t2)
+            (:predicates
+                (at ?l - t1)
+                (on ?l - t2)
+            )
+            (:action test1
+            :parameters(?l1 - t1)
+            :effect (forall (?l2 - t2)
+                (when (on ?l2) (and (at ?l2) (at ?l1))))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.verify_domain_all();
+            // the quantified variable is in the scope of the conditional effect
+            assert_eq!(errors.len(), 1, "{:?}", errors);
+            match &errors[0] {
+                SemanticErrorType::InconsistentPredicateArgType(t_err) => {
+                    assert_eq!(t_err.var_name, "l2");
+                    assert_eq!(t_err.found.as_ref().unwrap(), "t2");
+                    assert_eq!(t_err.expected.as_ref().unwrap(), "t1");
+                    assert_eq!(t_err.position.line, 10);
+                }
+                error => panic!("{:?}", error),
+            }
+        }
+        _ => panic!()
+    }
+}
@@ -501,4 +501,97 @@ pub fn undefined_method_task_test() {
         _ => panic!()
     }
     
-}
\ No newline at end of file
+}
+#[test]
+pub fn multiple_undefined_elements_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:types t_1 - object)
+            (:predicates 
+                (hold ?a_1 - t_1)
+                (at ?a_1 - t_5)
+            )
+            (:action a_1
+             :parameters (?p_1 - t_1 ?p_2 - t_1)
+             :precondition (and (not (hold ?p_1)) (pred_5 ?p_2))
+             :effect (and (pred_6) (hold ?p_2))
+            )
+         ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.verify_domain_all();
+            assert_eq!(errors.len(), 3);
+            match &errors[0] {
+                SemanticErrorType::UndefinedType(x) => {
+                    assert_eq!(x.symbol, "t_5");
+                    assert_eq!(x.position.line, 5);
+                }
+                token => panic!("{:?}", token)
+            }
+            match &errors[1] {
+                SemanticErrorType::UndefinedPredicate(x) => {
+                    assert_eq!(x.symbol, "pred_5");
+                    assert_eq!(x.position.line, 9);
+                }
+                token => panic!("{:?}", token)
+            }
+            match &errors[2] {
+                SemanticErrorType::UndefinedPredicate(x) => {
+                    assert_eq!(x.symbol, "pred_6");
+                    assert_eq!(x.position.line, 10);
+                }
+                token => panic!("{:?}", token)
+            }
+            // verify_domain still reports the first one
+            match semantic_parser.verify_domain() {
+                Err(SemanticErrorType::UndefinedType(x)) => assert_eq!(x.symbol, "t_5"),
+                _ => panic!("errors are not caught")
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn undefined_temporal_constraint_task_test() {
+    let program = String::from(
+        "(define (domain bal)
+                (:predicates (at ?a_1))
+                (:task c_1 :parameters (?p_1))
+                (:durative-action a_1
+                    :parameters (?p_1)
+                    :duration (= ?duration 1)
+                    :effect (at end (at ?p_1))
+                )
+                (:method m_1
+                    :parameters (?p1)
+                    :task (c_1 ?p1)
+                    :subtasks (and (t1 (a_1 ?p1)) (t2 (a_1 ?p1)))
+                    :constraints (< (end t1)
+                        (start t3))
+                )
+             ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            match semantic_parser.verify_domain() {
+                Err(SemanticErrorType::UndefinedSubtask(x)) => {
+                    assert_eq!(x.symbol, "t3");
+                    assert_eq!(x.position.line, 14)
+                }
+                result => panic!("{:?}", result.map(|_| ())),
+            }
+        }
+        _ => panic!()
+    }
+}
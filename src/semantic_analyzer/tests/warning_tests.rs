@@ -42,8 +42,13 @@ pub fn primitive_refinement_test () {
             let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
             match semantic_analyzer.verify_domain() {
                 Ok(sym_table) => {
-                    assert_eq!(sym_table.warnings.len(), 1);
-                    match &sym_table.warnings[0] {
+                    let warnings: Vec<_> = sym_table
+                        .warnings
+                        .iter()
+                        .filter(|w| matches!(w, WarningType::NoPrimitiveRefinement(_)))
+                        .collect();
+                    assert_eq!(warnings.len(), 1);
+                    match warnings[0] {
                         WarningType::NoPrimitiveRefinement(info) => {
                             assert_eq!(info.symbol, "abs_3");
                             assert_eq!(info.position.line, 15);
@@ -56,4 +61,131 @@ pub fn primitive_refinement_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+#[test]
+pub fn unused_elements_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:types truck package - object location)
+            (:predicates 
+                (at ?p - package ?l - location)
+                (road ?l1 ?l2 - location)
+                (broken ?t - truck)
+            )
+            (:task deliver :parameters (?p - package ?l - location))
+            (:action drive
+             :parameters (?t - truck ?l1 ?l2 - location)
+             :precondition (and (road ?l1 ?l2) (at ?l1 ?l1))
+             :effect (and (at ?l1 ?l1) (not (at ?l2 ?l2)))
+            )
+            (:method m_1
+                :parameters (?p - package ?l ?l2 - location ?t - truck)
+                :task (deliver ?p ?l)
+                :ordered-subtasks (and
+                    (t1 (drive ?t ?l ?l))
+                )
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d).with_immutable_predicates(true);
+            let (sym_table, _) = semantic_analyzer.verify_domain_all();
+            let mut unused_types = vec![];
+            let mut unused_predicates = vec![];
+            let mut immutable_predicates = vec![];
+            let mut unused_parameters = vec![];
+            let mut redundant_effects = vec![];
+            for warning in sym_table.warnings.iter() {
+                match warning {
+                    WarningType::UnusedType(info) => unused_types.push(info),
+                    WarningType::UnusedPredicate(info) => unused_predicates.push(info),
+                    WarningType::ImmutablePredicate(info) => immutable_predicates.push(info),
+                    WarningType::UnusedParameter(info) => unused_parameters.push(info),
+                    WarningType::RedundantEffect(info) => redundant_effects.push(info),
+                    WarningType::NoPrimitiveRefinement(_)
+                    | WarningType::MissingRequirement(_)
+                    | WarningType::UnusedRequirement(_)
+                    | WarningType::UnreachableGoal(_) => {}
+                }
+            }
+            // "object" is only a super type, and not declared
+            assert_eq!(unused_types.len(), 0);
+            assert_eq!(unused_predicates.len(), 1);
+            assert_eq!(unused_predicates[0].symbol, "broken");
+            assert_eq!(unused_predicates[0].position.line, 6);
+            assert_eq!(immutable_predicates.len(), 1);
+            assert_eq!(immutable_predicates[0].symbol, "road");
+            assert_eq!(unused_parameters.len(), 2);
+            assert_eq!(unused_parameters[0].symbol, "t");
+            assert_eq!(unused_parameters[0].position.line, 10);
+            assert_eq!(unused_parameters[1].symbol, "l2");
+            assert_eq!(unused_parameters[1].position.line, 15);
+            assert_eq!(redundant_effects.len(), 1);
+            assert_eq!(redundant_effects[0].symbol, "at");
+            assert_eq!(redundant_effects[0].position.line, 12);
+            assert_eq!(redundant_effects[0].position.column, 28);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn unused_type_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:types vehicle - object truck - vehicle city)
+            (:predicates (at ?t - truck))
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            let (sym_table, _) = semantic_analyzer.verify_domain_all();
+            let unused_types: Vec<_> = sym_table
+                .warnings
+                .iter()
+                .filter_map(|w| match w {
+                    WarningType::UnusedType(info) => Some(info.symbol.as_str()),
+                    _ => None
+                })
+                .collect();
+            // vehicle is the super type of truck
+            assert_eq!(unused_types, vec!["city"]);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn static_predicates_test() {
+    // the static predicates of benchmark domains (e.g. the parts of an assembly)
+    // are facts of the problems, and are only reported on request
+    let program = std::fs::read("tests/ipc/AssemblyHierarchical/domain.hddl").unwrap();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let immutable_predicates = |semantic_analyzer: DomainSemanticAnalyzer| {
+                let (sym_table, _) = semantic_analyzer.verify_domain_all();
+                sym_table
+                    .warnings
+                    .iter()
+                    .filter(|w| matches!(w, WarningType::ImmutablePredicate(_)))
+                    .count()
+            };
+            assert_eq!(immutable_predicates(DomainSemanticAnalyzer::new(&d)), 0);
+            assert!(immutable_predicates(DomainSemanticAnalyzer::new(&d).with_immutable_predicates(true)) > 0);
+        }
+        _ => panic!()
+    }
+}
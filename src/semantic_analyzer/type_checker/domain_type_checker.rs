@@ -16,14 +16,14 @@ impl<'a> DomainTypeChecker<'a> {
         }
     }
 
-    pub fn get_type_hierarchy(&'a self) -> GraphMap<&'a str, (), Directed> {
+    pub fn get_type_hierarchy(&self) -> GraphMap<&'a str, (), Directed> {
         self.generic_type_checker.type_hierarchy.clone()
     }
 
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
         self.generic_type_checker
             .check_type_declarations(parameters)
     }
@@ -33,25 +33,42 @@ impl<'a> DomainTypeChecker<'a> {
     }
 
     // TODO: Add support for "universal qunatification" parameters
+    // Note: parameter types are expected to be verified by the caller
     pub fn check_formula(
         &self,
         formula: &Vec<&Predicate<'a>>,
         parameters: &Vec<Symbol<'a>>,
         declared_constants: &HashSet<&Symbol<'a>>,
         declared_predicates: &HashSet<&'a Predicate<'a>>,
-    ) -> Result<(), SemanticErrorType> {
-        // Assert all types are declared
-        if let Some(undeclared_type) = self
-            .generic_type_checker
-            .check_type_declarations(parameters)
-        {
-            return Err(undeclared_type);
-        }
+    ) -> Vec<SemanticErrorType> {
+        self.check_instances(formula, parameters, declared_constants, declared_predicates, &PREDICATE_ERRORS)
+    }
+
+    // type checks the functions of numeric expressions (and effects)
+    pub fn check_functions(
+        &self,
+        functions: &Vec<&Predicate<'a>>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_functions: &HashSet<&'a Predicate<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        self.check_instances(functions, parameters, declared_constants, declared_functions, &FUNCTION_ERRORS)
+    }
+
+    fn check_instances(
+        &self,
+        formula: &Vec<&Predicate<'a>>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+        instance_errors: &InstanceErrors,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         // Store parameter types
         let par_types: HashMap<&str, Option<&str>> =
             HashMap::from_iter(parameters.iter().map(|par| (par.name, par.symbol_type)));
         // Assert predicate typing correctness
-        for instantiated_predicate in formula {
+        'predicates: for instantiated_predicate in formula {
             match declared_predicates.get(instantiated_predicate) {
                 Some(predicate_definition) => {
                     let mut instantiated_vars = vec![];
@@ -65,35 +82,37 @@ impl<'a> DomainTypeChecker<'a> {
                                     instantiated_vars.push((var, &constant.symbol_type))
                                 }
                                 None => {
-                                    return Err(SemanticErrorType::UndefinedParameter(
+                                    errors.push(SemanticErrorType::UndefinedParameter(
                                         UndefinedSymbolError {
                                             symbol: var.name.to_string(),
                                             position: var.name_pos,
                                         },
                                     ));
+                                    continue 'predicates;
                                 }
                             },
                         }
                     }
-                    let mut expected_list: Vec<&Option<&str>> = predicate_definition
+                    let expected_list: Vec<&Option<&str>> = predicate_definition
                         .variables
                         .iter()
                         .map(|x| &x.symbol_type)
                         .collect();
                     // Assert args have the same arity
                     if &instantiated_vars.len() != &expected_list.len() {
-                        return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                        errors.push((instance_errors.arity)(ArityError {
                             symbol: instantiated_predicate.name.to_string(),
                             expected_arity: expected_list.len() as u32,
                             found_arity: instantiated_vars.len() as u32,
                             position: instantiated_predicate.name_pos,
                         }));
+                        continue 'predicates;
                     }
                     for ((var, f), e) in
                         instantiated_vars.into_iter().zip(expected_list.into_iter())
                     {
                         if !self.generic_type_checker.is_var_type_consistent(*f, *e) {
-                            return Err(SemanticErrorType::InconsistentPredicateArgType(
+                            errors.push((instance_errors.arg_type)(
                                 TypeError {
                                     expected: e.map(|inner| inner.to_string()),
                                     found: f.map(|inner| inner.to_string()),
@@ -105,7 +124,7 @@ impl<'a> DomainTypeChecker<'a> {
                     }
                 }
                 None => {
-                    return Err(SemanticErrorType::UndefinedPredicate(
+                    errors.push((instance_errors.undefined)(
                         UndefinedSymbolError {
                             symbol: instantiated_predicate.name.to_string(),
                             position: instantiated_predicate.name_pos,
@@ -114,7 +133,7 @@ impl<'a> DomainTypeChecker<'a> {
                 }
             }
         }
-        Ok(())
+        errors
     }
 
     pub fn is_task_consistent(
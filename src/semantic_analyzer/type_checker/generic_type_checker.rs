@@ -1,8 +1,13 @@
+use std::collections::HashMap;
+
 use super::*;
+use crate::lexical_analyzer::TokenPosition;
 
 #[derive(Clone)]
 pub struct TypeChecker<'a> {
     pub type_hierarchy: GraphMap<&'a str, (), Directed>,
+    // where each type is (first) declared
+    pub type_positions: HashMap<&'a str, TokenPosition>,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -10,11 +15,16 @@ impl<'a> TypeChecker<'a> {
         match &types {
             None => TypeChecker {
                 type_hierarchy: GraphMap::new(),
+                type_positions: HashMap::new(),
             },
             Some(type_deps) => {
                 let mut type_graph: GraphMap<&str, (), Directed> =
                     GraphMap::<_, (), Directed>::new();
+                let mut type_positions = HashMap::new();
                 for delcared_type in type_deps {
+                    type_positions
+                        .entry(delcared_type.name)
+                        .or_insert(delcared_type.name_pos);
                     if !type_graph.contains_node(delcared_type.name) {
                         type_graph.add_node(delcared_type.name);
                     }
@@ -30,6 +40,7 @@ impl<'a> TypeChecker<'a> {
                 }
                 return TypeChecker {
                     type_hierarchy: type_graph,
+                    type_positions,
                 };
             }
         }
@@ -38,8 +49,10 @@ impl<'a> TypeChecker<'a> {
     pub fn verify_type_hierarchy(&self) -> Result<(), SemanticErrorType> {
         match toposort(&self.type_hierarchy, None) {
             Ok(_) => Ok(()),
-            Err(_) => {
-                return Err(SemanticErrorType::CyclicTypeDeclaration);
+            Err(cycle) => {
+                // every type in a cycle has a parent, so it is declared
+                let position = self.type_positions[cycle.node_id()];
+                Err(SemanticErrorType::CyclicTypeDeclaration(position))
             }
         }
     }
@@ -47,18 +60,19 @@ impl<'a> TypeChecker<'a> {
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         for parameter in parameters.iter() {
             if let Some(t) = parameter.symbol_type {
                 if !self.type_hierarchy.contains_node(t) {
-                    return Some(SemanticErrorType::UndefinedType(UndefinedSymbolError {
+                    errors.push(SemanticErrorType::UndefinedType(UndefinedSymbolError {
                         symbol: parameter.symbol_type.unwrap().to_string(),
                         position: parameter.type_pos.unwrap(),
                     }));
                 }
             }
         }
-        None
+        errors
     }
 
     pub fn is_var_type_consistent(
@@ -89,3 +103,22 @@ impl<'a> TypeChecker<'a> {
         }
     }
 }
+
+// the errors of the instances of predicates (or functions)
+pub struct InstanceErrors {
+    pub undefined: fn(UndefinedSymbolError) -> SemanticErrorType,
+    pub arity: fn(ArityError) -> SemanticErrorType,
+    pub arg_type: fn(TypeError) -> SemanticErrorType,
+}
+
+pub const PREDICATE_ERRORS: InstanceErrors = InstanceErrors {
+    undefined: SemanticErrorType::UndefinedPredicate,
+    arity: SemanticErrorType::InconsistentPredicateArity,
+    arg_type: SemanticErrorType::InconsistentPredicateArgType,
+};
+
+pub const FUNCTION_ERRORS: InstanceErrors = InstanceErrors {
+    undefined: SemanticErrorType::UndefinedFunction,
+    arity: SemanticErrorType::InconsistentFunctionArity,
+    arg_type: SemanticErrorType::InconsistentFunctionArgType,
+};
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::*;
 
@@ -20,6 +20,8 @@ impl<'a> ProblemTypeChecker<'a> {
         ProblemTypeChecker {
             generic_type_checker: TypeChecker {
                 type_hierarchy: symbol_table.type_hierarchy.clone(),
+                // the type hierarchy is already verified by the domain analyzer
+                type_positions: HashMap::new(),
             },
             symbol_table,
             objects,
@@ -28,7 +30,7 @@ impl<'a> ProblemTypeChecker<'a> {
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
         self.generic_type_checker
             .check_type_declarations(parameters)
     }
@@ -37,10 +39,26 @@ impl<'a> ProblemTypeChecker<'a> {
         &self,
         predicate: &'a Predicate<'a>,
     ) -> Result<(), SemanticErrorType> {
-        match &self.symbol_table.predicates.get(predicate) {
+        self.check_instantiation(predicate, &self.symbol_table.predicates, &PREDICATE_ERRORS)
+    }
+
+    pub fn check_function_instantiation(
+        &self,
+        function: &'a Predicate<'a>,
+    ) -> Result<(), SemanticErrorType> {
+        self.check_instantiation(function, &self.symbol_table.functions, &FUNCTION_ERRORS)
+    }
+
+    fn check_instantiation(
+        &self,
+        predicate: &'a Predicate<'a>,
+        definitions: &HashSet<&'a Predicate<'a>>,
+        instance_errors: &InstanceErrors,
+    ) -> Result<(), SemanticErrorType> {
+        match definitions.get(predicate) {
             Some(definition) => {
                 if definition.variables.len() != predicate.variables.len() {
-                    return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                    return Err((instance_errors.arity)(ArityError {
                         symbol: predicate.name.to_string(),
                         expected_arity: definition.variables.len() as u32,
                         found_arity: predicate.variables.len() as u32,
@@ -55,7 +73,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                 .generic_type_checker
                                 .is_var_type_consistent(*object_type, expected.symbol_type);
                             if !is_consistent {
-                                return Err(SemanticErrorType::InconsistentPredicateArgType(
+                                return Err((instance_errors.arg_type)(
                                     TypeError {
                                         expected: expected.symbol_type.map(String::from),
                                         found: found.symbol_type.map(String::from),
@@ -73,7 +91,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                         expected.symbol_type,
                                     );
                                 if !is_consistent {
-                                    return Err(SemanticErrorType::InconsistentPredicateArgType(
+                                    return Err((instance_errors.arg_type)(
                                         TypeError {
                                             expected: expected.symbol_type.map(String::from),
                                             found: constant.symbol_type.map(String::from),
@@ -97,12 +115,12 @@ impl<'a> ProblemTypeChecker<'a> {
                 return Ok(());
             }
             None => {
-                return Err(SemanticErrorType::UndefinedPredicate(
+                Err((instance_errors.undefined)(
                     UndefinedSymbolError {
                         symbol: predicate.name.to_string(),
                         position: predicate.name_pos,
                     },
-                ));
+                ))
             }
         }
     }
@@ -1,20 +1,22 @@
 use super::*;
 
+// returns every undefined predicate and arity mismatch in the formula
 pub fn check_predicate_declarations<'a>(
     formula: &Formula<'a>,
     declared_predicates: &Vec<Predicate<'a>>,
-) -> Result<(), SemanticErrorType> {
+) -> Vec<SemanticErrorType> {
+    let mut errors = vec![];
     match &*formula {
         Formula::Empty => {}
         Formula::Atom(predicate) => {
-            for declared_predicate in declared_predicates {
-                // Assert same name
-                if predicate.name == declared_predicate.name {
+            match declared_predicates
+                .iter()
+                .find(|declared_predicate| predicate.name == declared_predicate.name)
+            {
+                Some(declared_predicate) => {
                     // Assert same arity
-                    if predicate.variables.len() == declared_predicate.variables.len() {
-                        return Ok(());
-                    } else {
-                        return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                    if predicate.variables.len() != declared_predicate.variables.len() {
+                        errors.push(SemanticErrorType::InconsistentPredicateArity(ArityError {
                             symbol: predicate.name.to_string(),
                             expected_arity: declared_predicate.variables.len() as u32,
                             found_arity: predicate.variables.len() as u32,
@@ -22,30 +24,35 @@ pub fn check_predicate_declarations<'a>(
                         }));
                     }
                 }
+                None => {
+                    errors.push(SemanticErrorType::UndefinedPredicate(
+                        UndefinedSymbolError {
+                            symbol: predicate.name.to_string(),
+                            position: predicate.name_pos,
+                        },
+                    ));
+                }
             }
-            return Err(SemanticErrorType::UndefinedPredicate(
-                UndefinedSymbolError {
-                    symbol: predicate.name.to_string(),
-                    position: predicate.name_pos,
-                },
-            ));
         }
         Formula::Not(new_formula) => {
-            return check_predicate_declarations(&*new_formula, declared_predicates);
+            errors.extend(check_predicate_declarations(new_formula, declared_predicates));
         }
         Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
             for f in new_formula {
-                check_predicate_declarations(&*f, declared_predicates)?;
+                errors.extend(check_predicate_declarations(f, declared_predicates));
             }
         }
-        Formula::ForAll(_, new_formula) => {
-            return check_predicate_declarations(&*new_formula, declared_predicates);
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                errors.extend(check_predicate_declarations(f, declared_predicates));
+            }
         }
-        Formula::Equals(_, _) => {}
-        // TODO: add support for imply, and exists
-        _ => {
-            panic!()
+        Formula::ForAll(_, new_formula) | Formula::Exists(_, new_formula) => {
+            errors.extend(check_predicate_declarations(new_formula, declared_predicates));
         }
+        Formula::Equals(_, _) => {}
+        // functions are checked separately
+        Formula::Comparison(_, _, _) => {}
     }
-    return Ok(());
+    errors
 }
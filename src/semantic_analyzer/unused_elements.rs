@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// returns every predicate of the formula (including the quantified ones)
+fn collect_predicates<'a, 'b>(formula: &'b Formula<'a>, predicates: &mut Vec<&'b Predicate<'a>>) {
+    match formula {
+        Formula::Empty | Formula::Equals(_, _) | Formula::Comparison(_, _, _) => {}
+        Formula::Atom(predicate) => predicates.push(predicate),
+        Formula::Not(new_formula)
+        | Formula::ForAll(_, new_formula)
+        | Formula::Exists(_, new_formula) => {
+            collect_predicates(new_formula, predicates);
+        }
+        Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            for f in new_formula {
+                collect_predicates(f, predicates);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_predicates(f, predicates);
+            }
+        }
+    }
+}
+
+// returns the names of the terms that appear in the formula
+fn collect_terms<'a>(formula: &Formula<'a>, terms: &mut HashSet<&'a str>) {
+    let mut predicates = vec![];
+    collect_predicates(formula, &mut predicates);
+    for predicate in predicates {
+        for var in predicate.variables.iter() {
+            terms.insert(var.name);
+        }
+    }
+    collect_equalities(formula, terms);
+}
+
+fn collect_equalities<'a>(formula: &Formula<'a>, terms: &mut HashSet<&'a str>) {
+    match formula {
+        Formula::Equals(a, b) => {
+            terms.insert(a.name);
+            terms.insert(b.name);
+        }
+        Formula::Not(new_formula)
+        | Formula::ForAll(_, new_formula)
+        | Formula::Exists(_, new_formula) => {
+            collect_equalities(new_formula, terms);
+        }
+        Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            for f in new_formula {
+                collect_equalities(f, terms);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_equalities(f, terms);
+            }
+        }
+        // the arguments of the functions
+        Formula::Comparison(_, _, _) => {
+            for function in formula.get_functions() {
+                for var in function.variables.iter() {
+                    terms.insert(var.name);
+                }
+            }
+        }
+        Formula::Empty | Formula::Atom(_) => {}
+    }
+}
+
+// returns the quantified variables of the formula
+fn collect_quantified_vars<'a, 'b>(formula: &'b Formula<'a>, vars: &mut Vec<&'b Symbol<'a>>) {
+    match formula {
+        Formula::ForAll(new_vars, new_formula) | Formula::Exists(new_vars, new_formula) => {
+            vars.extend(new_vars.iter());
+            collect_quantified_vars(new_formula, vars);
+        }
+        Formula::Not(new_formula) => collect_quantified_vars(new_formula, vars),
+        Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+            for f in new_formula {
+                collect_quantified_vars(f, vars);
+            }
+        }
+        Formula::Imply(ps, qs) => {
+            for f in ps.iter().chain(qs.iter()) {
+                collect_quantified_vars(f, vars);
+            }
+        }
+        Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) | Formula::Comparison(_, _, _) => {}
+    }
+}
+
+// returns the names of the terms that appear in the effect (including its conditions)
+fn collect_effect_terms<'a>(effect: &Effect<'a>, terms: &mut HashSet<&'a str>) {
+    match effect {
+        Effect::Empty => {}
+        Effect::Add(predicate) | Effect::Delete(predicate) => {
+            terms.extend(predicate.variables.iter().map(|var| var.name));
+        }
+        Effect::And(effects) | Effect::OneOf(effects) => {
+            for e in effects {
+                collect_effect_terms(e, terms);
+            }
+        }
+        Effect::ForAll(_, new_effect) => collect_effect_terms(new_effect, terms),
+        Effect::When(condition, new_effect) => {
+            collect_terms(condition, terms);
+            collect_effect_terms(new_effect, terms);
+        }
+        Effect::Numeric(_, _, _) => {
+            for function in effect.get_functions() {
+                terms.extend(function.variables.iter().map(|var| var.name));
+            }
+        }
+    }
+}
+
+// returns the variables of the universal effects, and the quantified variables of the conditions
+fn collect_effect_vars<'a, 'b>(effect: &'b Effect<'a>, vars: &mut Vec<&'b Symbol<'a>>) {
+    match effect {
+        Effect::Empty | Effect::Add(_) | Effect::Delete(_) | Effect::Numeric(_, _, _) => {}
+        Effect::And(effects) | Effect::OneOf(effects) => {
+            for e in effects {
+                collect_effect_vars(e, vars);
+            }
+        }
+        Effect::ForAll(new_vars, new_effect) => {
+            vars.extend(new_vars.iter());
+            collect_effect_vars(new_effect, vars);
+        }
+        Effect::When(condition, new_effect) => {
+            collect_quantified_vars(condition, vars);
+            collect_effect_vars(new_effect, vars);
+        }
+    }
+}
+
+// the literals of a conjunction (i.e. those that hold whenever the formula holds)
+fn conjunctive_literals<'a, 'b>(formula: &'b Formula<'a>) -> Vec<(bool, &'b Predicate<'a>)> {
+    match formula {
+        Formula::Atom(predicate) => vec![(true, predicate)],
+        Formula::Not(new_formula) => match &**new_formula {
+            Formula::Atom(predicate) => vec![(false, predicate)],
+            _ => vec![],
+        },
+        Formula::And(new_formula) => new_formula
+            .iter()
+            .flat_map(|f| conjunctive_literals(f))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// types that are declared but never used (neither as the type of a symbol, nor as a super type)
+pub fn check_unused_types(domain: &DomainAST) -> Vec<WarningType> {
+    let declared_types = match &domain.types {
+        Some(types) => types,
+        None => return vec![],
+    };
+    let mut used_types = HashSet::new();
+    let mut symbols: Vec<&Symbol> = declared_types.iter().collect();
+    if let Some(constants) = &domain.constants {
+        symbols.extend(constants.iter());
+    }
+    for predicate in domain.predicates.iter().chain(domain.functions.iter()) {
+        symbols.extend(predicate.variables.iter());
+    }
+    for task in domain.compound_tasks.iter() {
+        symbols.extend(task.parameters.iter());
+    }
+    let mut formulas = vec![];
+    for action in domain.actions.iter() {
+        symbols.extend(action.parameters.iter());
+        formulas.extend(action.preconditions.iter());
+        if let Some(effect) = &action.effects {
+            collect_effect_vars(effect, &mut symbols);
+        }
+    }
+    for method in domain.methods.iter() {
+        symbols.extend(method.params.iter());
+        formulas.extend(method.precondition.iter());
+    }
+    for formula in formulas {
+        collect_quantified_vars(formula, &mut symbols);
+    }
+    for symbol in symbols {
+        if let Some(symbol_type) = symbol.symbol_type {
+            used_types.insert(symbol_type);
+        }
+    }
+    let mut warnings = vec![];
+    let mut reported = HashSet::new();
+    for declared_type in declared_types.iter() {
+        if !used_types.contains(declared_type.name) && reported.insert(declared_type.name) {
+            warnings.push(WarningType::UnusedType(WarningInfo {
+                symbol: declared_type.name.to_string(),
+                position: declared_type.name_pos,
+            }));
+        }
+    }
+    warnings
+}
+
+// predicates that do not appear in any precondition or effect, and predicates
+// that appear in preconditions, but in the effect of no action
+pub fn check_predicate_usage(domain: &DomainAST) -> Vec<WarningType> {
+    let mut in_preconditions = vec![];
+    let mut in_effects = vec![];
+    for action in domain.actions.iter() {
+        if let Some(precondition) = &action.preconditions {
+            collect_predicates(precondition, &mut in_preconditions);
+        }
+        if let Some(effect) = &action.effects {
+            in_effects.extend(effect.get_predicates());
+            // the conditions of conditional effects are checked before the action
+            for condition in effect.get_conditions() {
+                collect_predicates(condition, &mut in_preconditions);
+            }
+        }
+    }
+    for method in domain.methods.iter() {
+        if let Some(precondition) = &method.precondition {
+            collect_predicates(precondition, &mut in_preconditions);
+        }
+    }
+    let in_preconditions: HashSet<&str> = in_preconditions.iter().map(|p| p.name).collect();
+    let in_effects: HashSet<&str> = in_effects.iter().map(|p| p.name).collect();
+    let mut warnings = vec![];
+    let mut reported = HashSet::new();
+    for predicate in domain.predicates.iter() {
+        if !reported.insert(predicate.name) {
+            continue;
+        }
+        let info = WarningInfo {
+            symbol: predicate.name.to_string(),
+            position: predicate.name_pos,
+        };
+        match (
+            in_preconditions.contains(predicate.name),
+            in_effects.contains(predicate.name),
+        ) {
+            (false, false) => warnings.push(WarningType::UnusedPredicate(info)),
+            (true, false) => warnings.push(WarningType::ImmutablePredicate(info)),
+            _ => {}
+        }
+    }
+    warnings
+}
+
+// action parameters that appear in neither the precondition nor the effect, and
+// method parameters that appear in neither the task, the precondition, the subtasks, nor the constraints
+pub fn check_unused_parameters(domain: &DomainAST) -> Vec<WarningType> {
+    let mut warnings = vec![];
+    for action in domain.actions.iter() {
+        let mut terms = HashSet::new();
+        if let Some(precondition) = &action.preconditions {
+            collect_terms(precondition, &mut terms);
+        }
+        if let Some(effect) = &action.effects {
+            collect_effect_terms(effect, &mut terms);
+        }
+        warnings.extend(unused_parameters(&action.parameters, &terms));
+    }
+    for method in domain.methods.iter() {
+        let mut terms = HashSet::new();
+        terms.extend(method.task_terms.iter().map(|term| term.name));
+        if let Some(precondition) = &method.precondition {
+            collect_terms(precondition, &mut terms);
+        }
+        for subtask in method.tn.subtasks.iter() {
+            terms.extend(subtask.terms.iter().map(|term| term.name));
+        }
+        if let Some(constraints) = &method.tn.constraints {
+            for constraint in constraints {
+                match constraint {
+                    Constraint::Equal(a, b) | Constraint::NotEqual(a, b) => {
+                        terms.insert(a);
+                        terms.insert(b);
+                    }
+                    // refers to subtask ids, not to parameters
+                    Constraint::Temporal(..) => {}
+                }
+            }
+        }
+        warnings.extend(unused_parameters(&method.params, &terms));
+    }
+    warnings
+}
+
+fn unused_parameters(parameters: &Vec<Symbol>, terms: &HashSet<&str>) -> Vec<WarningType> {
+    parameters
+        .iter()
+        .filter(|parameter| !terms.contains(parameter.name))
+        .map(|parameter| {
+            WarningType::UnusedParameter(WarningInfo {
+                symbol: parameter.name.to_string(),
+                position: parameter.name_pos,
+            })
+        })
+        .collect()
+}
+
+// effects that re-assert a literal of the (conjunctive) precondition of their action
+// (for durative actions, a literal of the condition at the same time)
+pub fn check_redundant_effects(domain: &DomainAST) -> Vec<WarningType> {
+    let mut warnings = vec![];
+    for action in domain.actions.iter() {
+        let (precondition, effect) = match (&action.preconditions, &action.effects) {
+            (Some(precondition), Some(effect)) => (precondition, effect),
+            _ => continue,
+        };
+        let pairs = match &action.timing {
+            Some(timing) => [TimeSpecifier::AtStart, TimeSpecifier::AtEnd]
+                .into_iter()
+                .map(|time| (timing.condition_at(time), timing.effect_at(time)))
+                .collect(),
+            None => vec![(precondition.clone(), effect.clone())],
+        };
+        for (precondition, effect) in pairs.iter() {
+            warnings.extend(redundant_effects(precondition, effect));
+        }
+    }
+    warnings
+}
+
+fn redundant_effects(precondition: &Formula, effect: &Effect) -> Vec<WarningType> {
+    let mut warnings = vec![];
+    let preconditions = conjunctive_literals(precondition);
+    for (polarity, predicate) in effect.get_unconditional_literals() {
+        let is_redundant = preconditions.iter().any(|(pre_polarity, pre_predicate)| {
+            *pre_polarity == polarity
+                && pre_predicate.name == predicate.name
+                && pre_predicate.variables.len() == predicate.variables.len()
+                && pre_predicate
+                    .variables
+                    .iter()
+                    .zip(predicate.variables.iter())
+                    .all(|(a, b)| a.name == b.name)
+        });
+        if is_redundant {
+            warnings.push(WarningType::RedundantEffect(WarningInfo {
+                symbol: predicate.name.to_string(),
+                position: predicate.name_pos,
+            }));
+        }
+    }
+    warnings
+}
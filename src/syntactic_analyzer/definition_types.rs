@@ -1,3 +1,4 @@
+use crate::TokenPosition;
 
 pub enum DefinitionType <'a> {
     Domain(&'a str),
@@ -6,7 +7,8 @@ pub enum DefinitionType <'a> {
 
 pub struct ProblemDefinition <'a> {
     pub problem_name: &'a str,
-    pub domain_name: &'a str
+    pub domain_name: &'a str,
+    pub domain_name_pos: TokenPosition
 }
 
 pub enum FileVariant {
@@ -1,7 +1,7 @@
 use super::*;
 
 impl <'a> Parser <'a> {
-    pub fn parse_action(&'a self) -> Result<Action, ParsingError> {
+    pub fn parse_action(&self) -> Result<Action<'a>, ParsingError> {
         let task = self.parse_task()?;
         let mut preconditions = None;
         let mut effects = None;
@@ -29,9 +29,7 @@ impl <'a> Parser <'a> {
             Token::Keyword(KeywordName::Effect) => {
                 // skip effects keyword
                 let _ = self.tokenizer.get_token();
-                let formula = self.parse_formula()?;
-                let s = formula.clone().to_string();
-                effects = Some(formula);
+                effects = Some(self.parse_effect()?);
             },
             // action has no effects
             Token::Punctuator(PunctuationType::RParentheses) => {}
@@ -62,7 +60,8 @@ impl <'a> Parser <'a> {
             name_pos: task.name_pos,
             parameters: task.parameters,
             preconditions: preconditions,
-            effects: effects
+            effects,
+            timing: None,
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,332 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    // parses the body of "(:durative-action ...)" (HDDL 2.1)
+    pub fn parse_durative_action(&self) -> Result<Action<'a>, ParsingError> {
+        let task = self.parse_task()?;
+        let mut duration = vec![];
+        let mut conditions = vec![];
+        let mut effects = vec![];
+        if let Token::Keyword(KeywordName::Duration) = self.tokenizer.lookahead()? {
+            // skip duration keyword
+            let _ = self.tokenizer.get_token();
+            duration = self.parse_duration()?;
+        }
+        let has_conditions = match self.tokenizer.lookahead()? {
+            Token::Keyword(KeywordName::Condition) => {
+                // skip condition keyword
+                let _ = self.tokenizer.get_token();
+                conditions = self.parse_timed(|parser| parser.parse_formula(), true)?;
+                true
+            }
+            Token::Keyword(KeywordName::Effect) | Token::Punctuator(PunctuationType::RParentheses) => false,
+            token => {
+                let error = SyntacticError {
+                    expected: format!("(potentially empty) conditions of {}", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let has_effects = match self.tokenizer.lookahead()? {
+            Token::Keyword(KeywordName::Effect) => {
+                // skip effects keyword
+                let _ = self.tokenizer.get_token();
+                effects = self.parse_timed(|parser| parser.parse_effect(), false)?;
+                true
+            }
+            Token::Punctuator(PunctuationType::RParentheses) => false,
+            token => {
+                let error = SyntacticError {
+                    expected: format!("(potentially empty) effects of {}", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        // skip action block's closing parantheses
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {}
+            token => {
+                let error = SyntacticError {
+                    expected: format!("closing the scope of {} using ')'", task.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        let preconditions = match has_conditions {
+            true => Some(Formula::And(
+                conditions.iter().map(|(_, condition)| Box::new(condition.clone())).collect(),
+            )),
+            false => None,
+        };
+        let untimed_effects = match has_effects {
+            true => Some(Effect::And(effects.iter().map(|(_, effect)| effect.clone()).collect())),
+            false => None,
+        };
+        Ok(Action {
+            name: task.name,
+            name_pos: task.name_pos,
+            parameters: task.parameters,
+            preconditions,
+            effects: untimed_effects,
+            timing: Some(Timing {
+                duration,
+                conditions,
+                effects,
+            }),
+        })
+    }
+
+    // parses "()", "(op ?duration value)", or a conjunction of them
+    fn parse_duration(&self) -> Result<Vec<DurationConstraint<'a>>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => {}
+            token => {
+                let error = SyntacticError {
+                    expected: "'(' to start the duration".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        match self.tokenizer.lookahead()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                let _ = self.tokenizer.get_token();
+                Ok(vec![])
+            }
+            Token::Operator(OperationType::And) => {
+                let _ = self.tokenizer.get_token();
+                let mut constraints = vec![];
+                loop {
+                    match self.tokenizer.get_token()? {
+                        Token::Punctuator(PunctuationType::LParentheses) => {
+                            constraints.push(self.parse_duration_constraint()?);
+                        }
+                        Token::Punctuator(PunctuationType::RParentheses) => {
+                            return Ok(constraints);
+                        }
+                        token => {
+                            let error = SyntacticError {
+                                expected: "a duration constraint".to_string(),
+                                found: token.to_string(),
+                                position: self.tokenizer.get_last_token_position(),
+                            };
+                            return Err(ParsingError::Syntactic(error));
+                        }
+                    }
+                }
+            }
+            _ => Ok(vec![self.parse_duration_constraint()?]),
+        }
+    }
+
+    // parses "op ?duration value)" after '('
+    fn parse_duration_constraint(&self) -> Result<DurationConstraint<'a>, ParsingError> {
+        let operation = match self.tokenizer.get_token()? {
+            Token::Operator(
+                operation @ (OperationType::Equal
+                | OperationType::LessThan
+                | OperationType::LessThanOrEqual
+                | OperationType::GreaterThan
+                | OperationType::GreaterThanOrEqual),
+            ) => operation,
+            token => {
+                let error = SyntacticError {
+                    expected: "a comparison operator to constrain the duration".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let position = match self.tokenizer.get_token()? {
+            Token::Identifier("duration") => self.tokenizer.get_last_token_position(),
+            token => {
+                let error = SyntacticError {
+                    expected: "?duration".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let value = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(DurationConstraint {
+                operation,
+                value,
+                position,
+            }),
+            token => {
+                let error = SyntacticError {
+                    expected: "')' to close the duration constraint".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses "()", "(time element)", or a conjunction of them, where "over all"
+    // is only allowed for conditions
+    fn parse_timed<T>(
+        &self,
+        parse_element: impl Fn(&Self) -> Result<T, ParsingError>,
+        is_condition: bool,
+    ) -> Result<Vec<(TimeSpecifier, T)>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => {}
+            token => {
+                let error = SyntacticError {
+                    expected: "'(' to start the timed conditions or effects".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+        match self.tokenizer.lookahead()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                let _ = self.tokenizer.get_token();
+                Ok(vec![])
+            }
+            Token::Operator(OperationType::And) => {
+                let _ = self.tokenizer.get_token();
+                let mut elements = vec![];
+                loop {
+                    match self.tokenizer.get_token()? {
+                        Token::Punctuator(PunctuationType::LParentheses) => {
+                            elements.push(self.parse_timed_element(&parse_element, is_condition)?);
+                        }
+                        Token::Punctuator(PunctuationType::RParentheses) => {
+                            return Ok(elements);
+                        }
+                        token => {
+                            let error = SyntacticError {
+                                expected: "'(' to start a timed condition or effect".to_string(),
+                                found: token.to_string(),
+                                position: self.tokenizer.get_last_token_position(),
+                            };
+                            return Err(ParsingError::Syntactic(error));
+                        }
+                    }
+                }
+            }
+            _ => Ok(vec![self.parse_timed_element(&parse_element, is_condition)?]),
+        }
+    }
+
+    // parses "at start element)", "at end element)" or "over all element)" after '('
+    fn parse_timed_element<T>(
+        &self,
+        parse_element: &impl Fn(&Self) -> Result<T, ParsingError>,
+        is_condition: bool,
+    ) -> Result<(TimeSpecifier, T), ParsingError> {
+        let time = match (self.tokenizer.get_token()?, self.tokenizer.get_token()?) {
+            (Token::Identifier("at"), Token::Identifier("start")) => TimeSpecifier::AtStart,
+            (Token::Identifier("at"), Token::Identifier("end")) => TimeSpecifier::AtEnd,
+            (Token::Identifier("over"), Token::Identifier("all")) if is_condition => TimeSpecifier::OverAll,
+            (_, token) => {
+                let expected = match is_condition {
+                    true => "'at start', 'at end' or 'over all'",
+                    false => "'at start' or 'at end'",
+                };
+                let error = SyntacticError {
+                    expected: expected.to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let element = parse_element(self)?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok((time, element)),
+            token => {
+                let error = SyntacticError {
+                    expected: format!("')' to close '{}'", time),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses "start t1)" or "end t1)" after '('
+    pub fn parse_time_point(&self) -> Result<TimePoint<'a>, ParsingError> {
+        let time = match self.tokenizer.get_token()? {
+            Token::Identifier("start") => TimeSpecifier::AtStart,
+            Token::Identifier("end") => TimeSpecifier::AtEnd,
+            token => {
+                let error = SyntacticError {
+                    expected: "either 'start' or 'end' of a subtask".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let task_id = match self.tokenizer.get_token()? {
+            Token::Identifier(id) => Symbol::new(id, self.tokenizer.get_last_token_position(), None, None),
+            token => {
+                let error = SyntacticError {
+                    expected: "a task identifier".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(TimePoint { time, task_id }),
+            token => {
+                let error = SyntacticError {
+                    expected: format!("')' after {}", task_id.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses the rest of "(op (start t1) (end t2))" after its first time point
+    pub fn parse_temporal_constraint(
+        &self,
+        operation: OperationType,
+        lhs: TimePoint<'a>,
+    ) -> Result<Constraint<'a>, ParsingError> {
+        let rhs = match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => self.parse_time_point()?,
+            token => {
+                let error = SyntacticError {
+                    expected: "'(' to start the second time point".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                Ok(Constraint::Temporal(operation, lhs, rhs))
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "')' to close the temporal constraint".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
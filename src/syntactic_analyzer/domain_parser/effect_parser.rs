@@ -0,0 +1,166 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    pub fn parse_effect(&self) -> Result<Effect<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                Ok(Effect::Empty)
+            }
+            Token::Punctuator(PunctuationType::LParentheses) => {
+                match self.tokenizer.get_token()? {
+                    // Delete Effect
+                    Token::Operator(OperationType::Not) => {
+                        let predicate = match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::LParentheses) => {
+                                match self.tokenizer.get_token()? {
+                                    Token::Identifier(name) => Predicate {
+                                        name,
+                                        name_pos: self.tokenizer.get_last_token_position(),
+                                        variables: self.parse_args()?,
+                                    },
+                                    token => {
+                                        let error = SyntacticError {
+                                            expected: "the predicate to delete".to_string(),
+                                            found: token.to_string(),
+                                            position: self.tokenizer.get_last_token_position(),
+                                        };
+                                        return Err(ParsingError::Syntactic(error));
+                                    }
+                                }
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "'(' to start the deleted predicate".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                return Err(ParsingError::Syntactic(error));
+                            }
+                        };
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => {
+                                Ok(Effect::Delete(predicate))
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "closing the not operator with ')'".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                Err(ParsingError::Syntactic(error))
+                            }
+                        }
+                    }
+                    // Conjunctive Effect
+                    Token::Operator(OperationType::And) => {
+                        let mut effects = vec![];
+                        loop {
+                            let effect = self.parse_effect()?;
+                            if let Effect::Empty = effect {
+                                return Ok(Effect::And(effects));
+                            } else {
+                                effects.push(effect);
+                            }
+                        }
+                    }
+                    // Non-deterministic Effect
+                    Token::Operator(OperationType::Xor) => {
+                        let mut effects = vec![];
+                        loop {
+                            let effect = self.parse_effect()?;
+                            if let Effect::Empty = effect {
+                                return Ok(Effect::OneOf(effects));
+                            } else {
+                                effects.push(effect);
+                            }
+                        }
+                    }
+                    // Universal Effect
+                    Token::Operator(OperationType::ForAll) => match self.tokenizer.get_token()? {
+                        Token::Punctuator(PunctuationType::LParentheses) => {
+                            let params = self.parse_args()?;
+                            let effect = Box::new(self.parse_effect()?);
+                            match self.tokenizer.get_token()? {
+                                Token::Punctuator(PunctuationType::RParentheses) => {
+                                    Ok(Effect::ForAll(params, effect))
+                                }
+                                token => {
+                                    let error = SyntacticError {
+                                        expected: "')' to close the forall statement".to_string(),
+                                        found: token.to_string(),
+                                        position: self.tokenizer.get_last_token_position(),
+                                    };
+                                    Err(ParsingError::Syntactic(error))
+                                }
+                            }
+                        }
+                        token => {
+                            let error = SyntacticError {
+                                expected: "'(' after forall keyword".to_string(),
+                                found: token.to_string(),
+                                position: self.tokenizer.get_last_token_position(),
+                            };
+                            Err(ParsingError::Syntactic(error))
+                        }
+                    },
+                    // Conditional Effect
+                    Token::Operator(OperationType::When) => {
+                        let condition = self.parse_formula()?;
+                        let effect = Box::new(self.parse_effect()?);
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => {
+                                Ok(Effect::When(condition, effect))
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "')' to close the when statement".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                Err(ParsingError::Syntactic(error))
+                            }
+                        }
+                    }
+                    // Numeric Effects
+                    Token::Operator(
+                        operation @ (OperationType::Increase
+                        | OperationType::Decrease
+                        | OperationType::Assign
+                        | OperationType::ScaleUp
+                        | OperationType::ScaleDown),
+                    ) => {
+                        self.parse_numeric_effect(operation)
+                    }
+                    // Add Effect
+                    Token::Identifier(name) => {
+                        let predicate = Predicate {
+                            name,
+                            name_pos: self.tokenizer.get_last_token_position(),
+                            variables: self.parse_args()?,
+                        };
+                        Ok(Effect::Add(predicate))
+                    }
+                    Token::Punctuator(PunctuationType::RParentheses) => {
+                        Ok(Effect::Empty)
+                    }
+                    token => {
+                        let error = SyntacticError {
+                            expected: "an effect".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        Err(ParsingError::Syntactic(error))
+                    }
+                }
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "a (potentially empty) effect definition".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> Parser<'a> {
-    pub fn parse_formula(&'a self) -> Result<Formula, ParsingError> {
+    pub fn parse_formula(&self) -> Result<Formula<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::RParentheses) => {
                 return Ok(Formula::Empty);
@@ -61,31 +61,52 @@ impl<'a> Parser<'a> {
                             }
                         }
                     }
+                    // Numeric Equality
+                    Token::Operator(OperationType::Equal)
+                        if !matches!(self.tokenizer.lookahead()?, Token::Identifier(_)) =>
+                    {
+                        self.parse_comparison(OperationType::Equal)
+                    }
+                    // Numeric Comparisons
+                    Token::Operator(
+                        operation @ (OperationType::LessThan
+                        | OperationType::GreaterThan
+                        | OperationType::LessThanOrEqual
+                        | OperationType::GreaterThanOrEqual),
+                    ) => {
+                        self.parse_comparison(operation)
+                    }
                     // Equality
                     Token::Operator(OperationType::Equal) => match self.tokenizer.get_token()? {
-                        Token::Identifier(p1) => match self.tokenizer.get_token()? {
-                            Token::Identifier(p2) => match self.tokenizer.get_token()? {
-                                Token::Punctuator(PunctuationType::RParentheses) => {
-                                    return Ok(Formula::Equals(p1, p2));
+                        Token::Identifier(lhs) => {
+                            let lhs = Symbol::new(lhs, self.tokenizer.get_last_token_position(), None, None);
+                            match self.tokenizer.get_token()? {
+                                Token::Identifier(rhs) => {
+                                    let rhs = Symbol::new(rhs, self.tokenizer.get_last_token_position(), None, None);
+                                    match self.tokenizer.get_token()? {
+                                        Token::Punctuator(PunctuationType::RParentheses) => {
+                                            Ok(Formula::Equals(lhs, rhs))
+                                        }
+                                        token => {
+                                            let error = SyntacticError {
+                                                expected: "equality's closing parenthesis".to_string(),
+                                                found: token.to_string(),
+                                                position: self.tokenizer.get_last_token_position(),
+                                            };
+                                            Err(ParsingError::Syntactic(error))
+                                        }
+                                    }
                                 }
                                 token => {
                                     let error = SyntacticError {
-                                        expected: "equality's closing parenthesis".to_string(),
+                                        expected: "right hand side of the equality".to_string(),
                                         found: token.to_string(),
                                         position: self.tokenizer.get_last_token_position(),
                                     };
                                     return Err(ParsingError::Syntactic(error));
                                 }
-                            },
-                            token => {
-                                let error = SyntacticError {
-                                    expected: "right hand side of the equality".to_string(),
-                                    found: token.to_string(),
-                                    position: self.tokenizer.get_last_token_position(),
-                                };
-                                return Err(ParsingError::Syntactic(error));
                             }
-                        },
+                        }
                         token => {
                             let error = SyntacticError {
                                 expected: "left hand side of the equality".to_string(),
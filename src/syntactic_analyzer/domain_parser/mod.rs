@@ -2,7 +2,10 @@ mod predicate_parser;
 mod compound_task_parser;
 mod method_parser;
 mod action_parser;
+mod durative_action_parser;
 mod formula_parser;
+mod effect_parser;
+mod numeric_parser;
 mod router;
 
 use super::*;
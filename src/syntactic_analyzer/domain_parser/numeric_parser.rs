@@ -0,0 +1,173 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    // parses the body of "(:functions ...)", where every function is optionally
+    // followed by its type (which is always "number")
+    pub fn parse_functions(&self) -> Result<Vec<Predicate<'a>>, ParsingError> {
+        let mut functions = vec![];
+        loop {
+            match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    functions.push(self.parse_predicate_definition()?);
+                }
+                Token::Punctuator(PunctuationType::Dash) => match self.tokenizer.get_token()? {
+                    Token::Identifier("number") if !functions.is_empty() => {}
+                    token => {
+                        let error = SyntacticError {
+                            expected: "'number' as the type of the function".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
+                },
+                Token::Punctuator(PunctuationType::RParentheses) => {
+                    return Ok(functions);
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "function definition".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            }
+        }
+    }
+
+    pub fn parse_numeric_expression(&self) -> Result<NumericExpression<'a>, ParsingError> {
+        match self.tokenizer.get_token()? {
+            Token::Number(value) => Ok(NumericExpression::Number(
+                value,
+                self.tokenizer.get_last_token_position(),
+            )),
+            // a negative number, e.g. "-1"
+            Token::Punctuator(PunctuationType::Dash) => match self.tokenizer.get_token()? {
+                Token::Number(value) => Ok(NumericExpression::Operation(
+                    OperationType::Minus,
+                    vec![NumericExpression::Number(
+                        value,
+                        self.tokenizer.get_last_token_position(),
+                    )],
+                )),
+                token => {
+                    let error = SyntacticError {
+                        expected: "a number after '-'".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+            },
+            Token::Punctuator(PunctuationType::LParentheses) => {
+                let operation = match self.tokenizer.get_token()? {
+                    Token::Identifier(name) => {
+                        return Ok(NumericExpression::Function(self.parse_function(name)?));
+                    }
+                    Token::Punctuator(PunctuationType::Dash) => OperationType::Minus,
+                    Token::Operator(
+                        operation @ (OperationType::Plus | OperationType::Times | OperationType::Divide),
+                    ) => operation,
+                    token => {
+                        let error = SyntacticError {
+                            expected: "either a function or an arithmetic operator".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
+                };
+                let mut operands = vec![];
+                while self.tokenizer.lookahead()? != Token::Punctuator(PunctuationType::RParentheses) {
+                    operands.push(self.parse_numeric_expression()?);
+                }
+                let _ = self.tokenizer.get_token();
+                if operands.is_empty() {
+                    let error = SyntacticError {
+                        expected: format!("the operands of '{}'", operation),
+                        found: Token::Punctuator(PunctuationType::RParentheses).to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+                Ok(NumericExpression::Operation(operation, operands))
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "a numeric expression".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses a function call after its name (including the closing ')')
+    pub fn parse_function(&self, name: &'a str) -> Result<Predicate<'a>, ParsingError> {
+        Ok(Predicate {
+            name,
+            name_pos: self.tokenizer.get_last_token_position(),
+            variables: self.parse_args()?,
+        })
+    }
+
+    // parses a comparison after its operator, e.g. "(f ?x) 3)"
+    pub fn parse_comparison(&self, operation: OperationType) -> Result<Formula<'a>, ParsingError> {
+        let lhs = self.parse_numeric_expression()?;
+        let rhs = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                Ok(Formula::Comparison(operation, lhs, rhs))
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "comparison's closing parenthesis".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses a numeric effect after its operator, e.g. "(total-cost) 1)"
+    pub fn parse_numeric_effect(&self, operation: OperationType) -> Result<Effect<'a>, ParsingError> {
+        let function = match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => match self.tokenizer.get_token()? {
+                Token::Identifier(name) => self.parse_function(name)?,
+                token => {
+                    let error = SyntacticError {
+                        expected: "a function name".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            },
+            token => {
+                let error = SyntacticError {
+                    expected: format!("'(' to start the function that '{}' updates", operation),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let value = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => {
+                Ok(Effect::Numeric(operation, function, value))
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: format!("closing the {} effect with ')'", operation),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
@@ -1,16 +1,46 @@
+use std::cell::RefCell;
+
 use self::definition_types::ProblemDefinition;
 
 use super::*;
 
 pub struct Parser<'a> {
     pub tokenizer: LexicalAnalyzer<'a>,
+    // syntax errors that the parser has recovered from
+    recovered_errors: RefCell<Vec<ParsingError>>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokenizer: LexicalAnalyzer<'a>) -> Parser<'a> {
-        Parser { tokenizer }
+        Parser {
+            tokenizer,
+            recovered_errors: RefCell::new(vec![]),
+        }
+    }
+
+    // stops at the first syntax error
+    pub fn parse(&self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
+        let (ast, errors) = self.parse_partial();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(ast.unwrap()),
+        }
+    }
+
+    // recovers from syntax errors at block boundaries, and returns every
+    // syntax error alongside the (possibly partial) syntax tree
+    pub fn parse_partial(&self) -> (Option<AbstractSyntaxTree<'a>>, Vec<ParsingError>) {
+        let ast = match self.parse_document() {
+            Ok(ast) => Some(ast),
+            Err(error) => {
+                self.recovered_errors.borrow_mut().push(error);
+                None
+            }
+        };
+        (ast, self.recovered_errors.take())
     }
-    pub fn parse(&'a self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
+
+    fn parse_document(&self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
         // match opening '('
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
@@ -18,11 +48,11 @@ impl<'a> Parser<'a> {
                 match self.parse_document_type()? {
                     // Domain Definition
                     DefinitionType::Domain(domain_name) => {
-                        Ok(self.parse_domain(domain_name)?.into())
+                        Ok(self.parse_domain(domain_name).into())
                     }
                     // Problem Definition
                     DefinitionType::Problem(problem_definition) => {
-                        Ok(self.parse_problem(problem_definition)?.into())
+                        Ok(self.parse_problem(problem_definition).into())
                     }
                 }
             }
@@ -55,7 +85,23 @@ impl<'a> Parser<'a> {
         return variant;
     }
 
-    fn parse_document_type(&self) -> Result<DefinitionType, ParsingError> {
+    // the name of the domain that the document defines (or refers to, if it is
+    // a problem), without parsing the rest of the document
+    pub fn domain_name(&self) -> Option<&'a str> {
+        self.tokenizer.reset_cursor();
+        let mut domain_name = None;
+        if let Ok(Token::Punctuator(PunctuationType::LParentheses)) = self.tokenizer.get_token() {
+            domain_name = match self.parse_document_type() {
+                Ok(DefinitionType::Domain(name)) => Some(name),
+                Ok(DefinitionType::Problem(definition)) => Some(definition.domain_name),
+                Err(_) => None,
+            };
+        }
+        self.tokenizer.reset_cursor();
+        domain_name
+    }
+
+    fn parse_document_type(&self) -> Result<DefinitionType<'a>, ParsingError> {
         // match keyword 'define'
         match self.tokenizer.get_token()? {
             Token::Keyword(KeywordName::Define) => {
@@ -101,7 +147,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_domain_header(&self) -> Result<DefinitionType, ParsingError> {
+    fn parse_domain_header(&self) -> Result<DefinitionType<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Identifier(domain_name) => {
                 // match closing paranthesis
@@ -130,7 +176,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_problem_header(&self) -> Result<DefinitionType, ParsingError> {
+    pub fn parse_problem_header(&self) -> Result<DefinitionType<'a>, ParsingError> {
         // match problem name
         match self.tokenizer.get_token()? {
             Token::Identifier(problem_name) => {
@@ -144,6 +190,7 @@ impl<'a> Parser<'a> {
                                     Token::Keyword(KeywordName::Domain) => {
                                         match self.tokenizer.get_token()? {
                                             Token::Identifier(domain_name) => {
+                                                let domain_name_pos = self.tokenizer.get_last_token_position();
                                                 match self.tokenizer.get_token()? {
                                                     Token::Punctuator(
                                                         PunctuationType::RParentheses,
@@ -151,6 +198,7 @@ impl<'a> Parser<'a> {
                                                         return Ok(DefinitionType::Problem(
                                                             ProblemDefinition {
                                                                 domain_name,
+                                                                domain_name_pos,
                                                                 problem_name,
                                                             },
                                                         ));
@@ -218,13 +266,42 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_requirements(&self) -> Result<Vec<RequirementType>, ParsingError> {
+    // panic-mode recovery: records the error, and skips tokens until the
+    // start of the next block (i.e. a '(' followed by one of the keywords).
+    // Returns false if the end of file is reached first.
+    pub fn synchronize(&self, error: ParsingError, keywords: &[KeywordName]) -> bool {
+        self.recovered_errors.borrow_mut().push(error);
+        loop {
+            match self.tokenizer.get_token() {
+                Ok(Token::EOF) => return false,
+                Ok(Token::Punctuator(PunctuationType::LParentheses)) => {
+                    if let Ok(Token::Keyword(keyword)) = self.tokenizer.lookahead() {
+                        if keywords.contains(&keyword) {
+                            return true;
+                        }
+                    }
+                }
+                // lexical errors are skipped as well
+                _ => {}
+            }
+        }
+    }
+
+    // returns the requirements along with their positions
+    pub fn parse_requirements(&self) -> Result<Vec<(RequirementType, TokenPosition)>, ParsingError> {
         let mut requirements = vec![];
         let mut finished = false;
         while !finished {
             match self.tokenizer.get_token()? {
                 Token::Requirement(req) => {
-                    requirements.push(req);
+                    requirements.push((req, self.tokenizer.get_last_token_position()));
+                }
+                // ":constraints" is also the keyword of the task network's constraints
+                Token::Keyword(KeywordName::Constraints) => {
+                    requirements.push((
+                        RequirementType::Constraints,
+                        self.tokenizer.get_last_token_position(),
+                    ));
                 }
                 Token::Punctuator(PunctuationType::RParentheses) => {
                     finished = true;
@@ -0,0 +1,106 @@
+use super::*;
+
+impl<'a> Parser<'a> {
+    // parses the body of "(:init ...)", i.e. the facts and the initial values of
+    // the functions
+    pub fn parse_init(&self) -> Result<(Vec<Predicate<'a>>, Vec<FunctionValue<'a>>), ParsingError> {
+        let mut facts = vec![];
+        let mut values = vec![];
+        loop {
+            match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    if let Token::Operator(OperationType::Equal) = self.tokenizer.lookahead()? {
+                        let _ = self.tokenizer.get_token();
+                        values.push(self.parse_function_value()?);
+                    } else {
+                        facts.push(self.parse_predicate_definition()?);
+                    }
+                }
+                Token::Punctuator(PunctuationType::RParentheses) => {
+                    return Ok((facts, values));
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "an initial fact or function value".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
+                }
+            }
+        }
+    }
+
+    // parses "(f o) 3)" after "(="
+    fn parse_function_value(&self) -> Result<FunctionValue<'a>, ParsingError> {
+        let function = match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::LParentheses) => self.parse_predicate_definition()?,
+            token => {
+                let error = SyntacticError {
+                    expected: "'(' to start the function".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let value = match self.tokenizer.get_token()? {
+            Token::Number(value) => value,
+            token => {
+                let error = SyntacticError {
+                    expected: format!("the initial value of {}", function.name),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let value_pos = self.tokenizer.get_last_token_position();
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(FunctionValue {
+                function,
+                value,
+                value_pos,
+            }),
+            token => {
+                let error = SyntacticError {
+                    expected: "')' to close the function value".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+
+    // parses the body of "(:metric ...)"
+    pub fn parse_metric(&self) -> Result<Metric<'a>, ParsingError> {
+        let optimization = match self.tokenizer.get_token()? {
+            Token::Identifier("minimize") => Optimization::Minimize,
+            Token::Identifier("maximize") => Optimization::Maximize,
+            token => {
+                let error = SyntacticError {
+                    expected: "either 'minimize' or 'maximize'".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        };
+        let expression = self.parse_numeric_expression()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(PunctuationType::RParentheses) => Ok(Metric {
+                optimization,
+                expression,
+            }),
+            token => {
+                let error = SyntacticError {
+                    expected: "')' to close the metric".to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                Err(ParsingError::Syntactic(error))
+            }
+        }
+    }
+}
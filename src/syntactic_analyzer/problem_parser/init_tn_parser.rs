@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> Parser<'a> {
-    pub fn parse_initial_tn(&'a self) -> Result<InitialTaskNetwork<'a>, ParsingError> {
+    pub fn parse_initial_tn(&self) -> Result<InitialTaskNetwork<'a>, ParsingError> {
         loop {
             match self.tokenizer.lookahead()? {
                 Token::Keyword(KeywordName::Parameters) => {
@@ -53,7 +53,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_htn(&'a self) -> Result<HTN<'a>, ParsingError> {
+    pub fn parse_htn(&self) -> Result<HTN<'a>, ParsingError> {
         let mut subtasks = vec![];
         let mut orderings = vec![];
         let mut constraints = None;
@@ -197,12 +197,24 @@ impl<'a> Parser<'a> {
                 match self.tokenizer.get_token()? {
                     Token::Keyword(KeywordName::Constraints) => {
                         constraints = Some(self.parse_constraints()?);
-                        return Ok(HTN {
-                            subtasks,
-                            ordering_pos,
-                            orderings: TaskOrdering::Total,
-                            constraints,
-                        });
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => {
+                                Ok(HTN {
+                                    subtasks,
+                                    ordering_pos,
+                                    orderings: TaskOrdering::Total,
+                                    constraints,
+                                })
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "closing ')' after task network definition".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                Err(ParsingError::Syntactic(error))
+                            }
+                        }
                     }
                     Token::Punctuator(PunctuationType::RParentheses) => {
                         return Ok(HTN {
@@ -234,7 +246,7 @@ impl<'a> Parser<'a> {
     }
 
     // parse a single ordering constraint
-    fn parse_ordering(&'a self) -> Result<Vec<(&'a str, &'a str)>, ParsingError> {
+    fn parse_ordering(&self) -> Result<Vec<(&'a str, &'a str)>, ParsingError> {
         let mut orderings: Vec<(&str, &str)> = vec![];
         match self.tokenizer.get_token()? {
             Token::Operator(OperationType::LessThan) => match self.tokenizer.get_token()? {
@@ -277,7 +289,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_subtasks(&self) -> Result<Vec<Subtask>, ParsingError> {
+    fn parse_subtasks(&self) -> Result<Vec<Subtask<'a>>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
                 match self.tokenizer.lookahead()? {
@@ -336,7 +348,7 @@ impl<'a> Parser<'a> {
     }
 
     // parses a single subtask
-    fn parse_subtask(&'a self) -> Result<Subtask, ParsingError> {
+    fn parse_subtask(&self) -> Result<Subtask<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Identifier(id) => {
                 let id_symbol = Symbol::new(
@@ -479,7 +491,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_constraints(&'a self) -> Result<Vec<Constraint<'a>>, ParsingError> {
+    pub fn parse_constraints(&self) -> Result<Vec<Constraint<'a>>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
                 let mut constraints = vec![];
@@ -514,7 +526,14 @@ impl<'a> Parser<'a> {
                         }
                     },
                     // single constraint declaration
-                    Token::Operator(OperationType::Not) | Token::Operator(OperationType::Equal) => {
+                    Token::Operator(
+                        OperationType::Not
+                        | OperationType::Equal
+                        | OperationType::LessThan
+                        | OperationType::LessThanOrEqual
+                        | OperationType::GreaterThan
+                        | OperationType::GreaterThanOrEqual,
+                    ) => {
                         constraints.push(self.parse_constraint()?);
                         return Ok(constraints);
                     }
@@ -539,7 +558,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_constraint(&'a self) -> Result<Constraint<'a>, ParsingError> {
+    pub fn parse_constraint(&self) -> Result<Constraint<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Operator(OperationType::Not) => match self.tokenizer.get_token()? {
                 Token::Punctuator(PunctuationType::LParentheses) => {
@@ -621,6 +640,11 @@ impl<'a> Parser<'a> {
                 }
             },
             Token::Operator(OperationType::Equal) => match self.tokenizer.get_token()? {
+                // temporal equality, e.g. (= (start t1) (start t2))
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    let lhs = self.parse_time_point()?;
+                    self.parse_temporal_constraint(OperationType::Equal, lhs)
+                }
                 Token::Identifier(t1) => match self.tokenizer.get_token()? {
                     Token::Identifier(t2) => match self.tokenizer.get_token()? {
                         Token::Punctuator(PunctuationType::RParentheses) => {
@@ -653,6 +677,26 @@ impl<'a> Parser<'a> {
                     return Err(ParsingError::Syntactic(error));
                 }
             },
+            // temporal ordering, e.g. (< (end t1) (start t2))
+            Token::Operator(
+                operation @ (OperationType::LessThan
+                | OperationType::LessThanOrEqual
+                | OperationType::GreaterThan
+                | OperationType::GreaterThanOrEqual),
+            ) => match self.tokenizer.get_token()? {
+                Token::Punctuator(PunctuationType::LParentheses) => {
+                    let lhs = self.parse_time_point()?;
+                    self.parse_temporal_constraint(operation, lhs)
+                }
+                token => {
+                    let error = SyntacticError {
+                        expected: "'(' to start a time point".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+            },
             token => {
                 let error = SyntacticError {
                     expected: "either an equalilty or non-equality constraint".to_string(),
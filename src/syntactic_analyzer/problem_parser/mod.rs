@@ -1,5 +1,6 @@
 mod list_parser;
 mod init_tn_parser;
+mod init_parser;
 mod router;
 
 use super::*;
\ No newline at end of file
@@ -1,75 +1,134 @@
 use super::*;
 
+// blocks at which the parser resumes after a syntax error
+const PROBLEM_SYNC_KEYWORDS: [KeywordName; 6] = [
+    KeywordName::Requirements,
+    KeywordName::Objects,
+    KeywordName::HTN,
+    KeywordName::Goal,
+    KeywordName::Init,
+    KeywordName::Metric,
+];
+
 impl <'a> Parser<'a> {
-    pub fn parse_problem(&'a self, meta_data: ProblemDefinition<'a>) -> Result<ProblemAST<'a>, ParsingError> {
-        let mut syntax_tree = ProblemAST::new();
+    // syntax errors are recorded in the parser, and the returned AST contains
+    // the blocks that were parsed successfully
+    pub fn parse_problem(&self, meta_data: ProblemDefinition<'a>) -> ProblemAST<'a> {
+        let mut syntax_tree = ProblemAST::new(
+            meta_data.problem_name.to_string(),
+            meta_data.domain_name.to_string(),
+            meta_data.domain_name_pos,
+        );
         loop {
-            match self.tokenizer.get_token()? {
-                Token::Punctuator(PunctuationType::LParentheses) => {
-                    // match declaration type
-                    match self.tokenizer.get_token()? {
-                        // requirement declaration
-                        Token::Keyword(KeywordName::Requirements) => {
-                            let requirements = self.parse_requirements()?;
-                            for requirement in requirements {
-                                syntax_tree.add_requirement(requirement);
-                            }
-                        }
-                        // objects declaration
-                        Token::Keyword(KeywordName::Objects) => {
-                            let objects = self.parse_args()?;
-                            for object in objects {
-                                match object.symbol_type {
-                                    Some(t) => {
-                                        syntax_tree.add_typed_object(
-                                            object.name,
-                                            object.name_pos,
-                                            t,
-                                            object.type_pos.unwrap(),
-                                        );
-                                    }
-                                    None => {
-                                        syntax_tree.add_object(
-                                            object.name,
-                                            object.name_pos,
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        // initial task network declaration
-                        Token::Keyword(KeywordName::HTN) => {
-                            let init_tn = self.parse_initial_tn()?;
-                            syntax_tree.add_init_tn(init_tn);
-                        }
-                        // goal state (optional)
-                        Token::Keyword(KeywordName::Goal) => {
-                            let goal = self.parse_formula()?;
-                            syntax_tree.add_goal(goal)
-                        }
-                        // initial state
-                        Token::Keyword(KeywordName::Init) => {
-                            let init_state = self.parse_predicates()?;
-                            syntax_tree.add_init_state(init_state)
+            let mut result = match self.tokenizer.get_token() {
+                Ok(Token::Punctuator(PunctuationType::LParentheses)) => {
+                    self.parse_problem_block(&mut syntax_tree)
+                }
+                Ok(Token::EOF | Token::Punctuator(PunctuationType::RParentheses)) => {
+                    return syntax_tree;
+                }
+                Ok(token) => {
+                    let error = SyntacticError {
+                        expected: format!("either ')' to close the definition of {}, or '(' to start defining new components", meta_data.problem_name),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    Err(ParsingError::Syntactic(error))
+                }
+                Err(lexical_error) => Err(lexical_error.into()),
+            };
+            while let Err(error) = result {
+                if !self.synchronize(error, &PROBLEM_SYNC_KEYWORDS) {
+                    return syntax_tree;
+                }
+                result = self.parse_problem_block(&mut syntax_tree);
+            }
+        }
+    }
+
+    // parses a block of the problem after its opening '('
+    fn parse_problem_block(&self, syntax_tree: &mut ProblemAST<'a>) -> Result<(), ParsingError> {
+        // match declaration type
+        match self.tokenizer.get_token()? {
+            // requirement declaration
+            Token::Keyword(KeywordName::Requirements) => {
+                let requirements = self.parse_requirements()?;
+                for (requirement, position) in requirements {
+                    syntax_tree.add_requirement(requirement, position);
+                }
+            }
+            // objects declaration
+            Token::Keyword(KeywordName::Objects) => {
+                let objects = self.parse_args()?;
+                for object in objects {
+                    match object.symbol_type {
+                        Some(t) => {
+                            syntax_tree.add_typed_object(
+                                object.name,
+                                object.name_pos,
+                                t,
+                                object.type_pos.unwrap(),
+                            );
                         }
-                        token => {
-                            let error = SyntacticError {
-                                expected: "a keyword for block definition"
-                                    .to_string(),
-                                found: token.to_string(),
-                                position: self.tokenizer.get_last_token_position(),
-                            };
-                            return Err(ParsingError::Syntactic(error));
+                        None => {
+                            syntax_tree.add_object(
+                                object.name,
+                                object.name_pos,
+                            );
                         }
                     }
                 }
-                Token::EOF | Token::Punctuator(PunctuationType::RParentheses) => {
-                    return Ok(syntax_tree);
+            }
+            // initial task network declaration
+            Token::Keyword(KeywordName::HTN) => {
+                let init_tn_pos = self.tokenizer.get_last_token_position();
+                let init_tn = self.parse_initial_tn()?;
+                syntax_tree.add_init_tn(init_tn, init_tn_pos);
+            }
+            // goal state (optional)
+            Token::Keyword(KeywordName::Goal) => {
+                let goal_pos = self.tokenizer.get_last_token_position();
+                // an empty goal, i.e. "(:goal)"
+                if let Token::Punctuator(PunctuationType::RParentheses) = self.tokenizer.lookahead()? {
+                    let _ = self.tokenizer.get_token();
+                    syntax_tree.add_goal(Formula::Empty, goal_pos);
+                    return Ok(());
                 }
-                err => {
-                    panic!("unexpected token {:?}", err)
+                let goal = self.parse_formula()?;
+                syntax_tree.add_goal(goal, goal_pos);
+                match self.tokenizer.get_token()? {
+                    Token::Punctuator(PunctuationType::RParentheses) => {}
+                    token => {
+                        let error = SyntacticError {
+                            expected: "')' to close the goal definition".to_string(),
+                            found: token.to_string(),
+                            position: self.tokenizer.get_last_token_position(),
+                        };
+                        return Err(ParsingError::Syntactic(error));
+                    }
                 }
             }
+            // initial state
+            Token::Keyword(KeywordName::Init) => {
+                let (init_state, init_values) = self.parse_init()?;
+                syntax_tree.add_init_state(init_state);
+                syntax_tree.add_init_values(init_values);
+            }
+            // plan metric (optional)
+            Token::Keyword(KeywordName::Metric) => {
+                let metric = self.parse_metric()?;
+                syntax_tree.add_metric(metric);
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: "a keyword for block definition"
+                        .to_string(),
+                    found: token.to_string(),
+                    position: self.tokenizer.get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
         }
+        Ok(())
     }
-}
\ No newline at end of file
+}
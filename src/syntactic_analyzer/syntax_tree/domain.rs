@@ -1,4 +1,5 @@
 use super::*;
+use crate::TokenPosition;
 
 #[derive(Debug, Serialize)]
 pub struct DomainAST<'a> {
@@ -6,7 +7,12 @@ pub struct DomainAST<'a> {
     pub types: Option<Vec<Symbol<'a>>>,
     pub constants: Option<Vec<Symbol<'a>>>,
     pub requirements: Vec<RequirementType>,
+    // positions of the requirements (in the same order)
+    #[serde(skip)]
+    pub requirement_positions: Vec<TokenPosition>,
     pub predicates: Vec<Predicate<'a>>,
+    // numeric functions (their values are always numbers)
+    pub functions: Vec<Predicate<'a>>,
     pub compound_tasks: Vec<Task<'a>>,
     pub methods: Vec<Method<'a>>,
     pub actions: Vec<Action<'a>>,
@@ -19,21 +25,28 @@ impl<'a> DomainAST<'a> {
             types: None,
             constants: None,
             requirements: vec![],
+            requirement_positions: vec![],
             predicates: vec![],
+            functions: vec![],
             compound_tasks: vec![],
             methods: vec![],
             actions: vec![],
         }
     }
 
-    pub fn add_requirement(&mut self, req: RequirementType) {
+    pub fn add_requirement(&mut self, req: RequirementType, req_pos: TokenPosition) {
         self.requirements.push(req);
+        self.requirement_positions.push(req_pos);
     }
 
     pub fn add_predicate(&mut self, predicate: Predicate<'a>) {
         self.predicates.push(predicate);
     }
 
+    pub fn add_function(&mut self, function: Predicate<'a>) {
+        self.functions.push(function);
+    }
+
     pub fn add_compound_task(&mut self, task: Task<'a>) {
         self.compound_tasks.push(task);
     }
@@ -12,8 +12,11 @@ pub struct Action<'a> {
     pub name: &'a str,
     pub name_pos: TokenPosition,
     pub parameters: Vec<Symbol<'a>>,
+    // (for a durative action, all of its conditions and effects regardless of their time)
     pub preconditions: Option<Formula<'a>>,
-    pub effects: Option<Formula<'a>>
+    pub effects: Option<Effect<'a>>,
+    // only durative actions have a duration
+    pub timing: Option<Timing<'a>>,
 }
 
 impl <'a> Hash for Action<'a> {
@@ -0,0 +1,155 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::lexical_analyzer::OperationType;
+
+use super::*;
+
+#[derive(Clone, Debug, Serialize)]
+pub enum Effect<'a> {
+    Empty,
+    // the predicate holds after the action
+    Add(Predicate<'a>),
+    // the predicate does not hold after the action
+    Delete(Predicate<'a>),
+    And(Vec<Effect<'a>>),
+    // exactly one of the effects applies (i.e. a non-deterministic effect)
+    OneOf(Vec<Effect<'a>>),
+    // ∀vars: effect
+    ForAll(Vec<Symbol<'a>>, Box<Effect<'a>>),
+    // the effect only applies if the condition holds (before the action)
+    When(Formula<'a>, Box<Effect<'a>>),
+    // (op function expression), where op is one of "increase decrease assign scale-up scale-down"
+    Numeric(OperationType, Predicate<'a>, NumericExpression<'a>),
+}
+
+impl<'a> Effect<'a> {
+    // the predicates that are added or deleted (including the quantified and conditional ones)
+    pub fn get_predicates(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            Effect::Empty | Effect::Numeric(_, _, _) => vec![],
+            Effect::Add(predicate) | Effect::Delete(predicate) => vec![predicate],
+            Effect::And(effects) | Effect::OneOf(effects) => {
+                effects.iter().flat_map(|e| e.get_predicates()).collect()
+            }
+            Effect::ForAll(_, new_effect) | Effect::When(_, new_effect) => new_effect.get_predicates(),
+        }
+    }
+
+    // the conditions of the conditional effects
+    pub fn get_conditions(&self) -> Vec<&Formula<'a>> {
+        match self {
+            Effect::Empty | Effect::Add(_) | Effect::Delete(_) | Effect::Numeric(_, _, _) => vec![],
+            Effect::And(effects) | Effect::OneOf(effects) => {
+                effects.iter().flat_map(|e| e.get_conditions()).collect()
+            }
+            Effect::ForAll(_, new_effect) => new_effect.get_conditions(),
+            Effect::When(condition, new_effect) => {
+                let mut conditions = vec![condition];
+                conditions.extend(new_effect.get_conditions());
+                conditions
+            }
+        }
+    }
+
+    // the functions of the numeric effects and of the conditions (outside quantifiers)
+    pub fn get_functions(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            Effect::Empty | Effect::Add(_) | Effect::Delete(_) | Effect::ForAll(_, _) => vec![],
+            Effect::And(effects) | Effect::OneOf(effects) => {
+                effects.iter().flat_map(|e| e.get_functions()).collect()
+            }
+            Effect::When(condition, new_effect) => {
+                let mut functions = condition.get_functions();
+                functions.extend(new_effect.get_functions());
+                functions
+            }
+            Effect::Numeric(_, function, value) => {
+                let mut functions = vec![function];
+                functions.extend(value.get_functions());
+                functions
+            }
+        }
+    }
+
+    // the literals that the effect asserts whenever it is applied (i.e. outside
+    // conditional, non-deterministic and universal effects), with true for additions
+    pub fn get_unconditional_literals(&self) -> Vec<(bool, &Predicate<'a>)> {
+        match self {
+            Effect::Add(predicate) => vec![(true, predicate)],
+            Effect::Delete(predicate) => vec![(false, predicate)],
+            Effect::And(effects) => effects
+                .iter()
+                .flat_map(|e| e.get_unconditional_literals())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    // an effect is contradictory if it both adds and deletes a predicate, either
+    // unconditionally, or whenever one of its conditional (or non-deterministic) effects applies
+    pub fn is_consistent(&self) -> bool {
+        let unconditional = self.get_unconditional_literals();
+        let is_sat = |literals: &Vec<(bool, &Predicate<'a>)>| {
+            Formula::And(
+                literals
+                    .iter()
+                    .map(|(polarity, predicate)| {
+                        let atom = Formula::Atom((*predicate).clone());
+                        match polarity {
+                            true => Box::new(atom),
+                            false => Box::new(Formula::Not(Box::new(atom))),
+                        }
+                    })
+                    .collect(),
+            )
+            .is_sat()
+        };
+        if !is_sat(&unconditional) {
+            return false;
+        }
+        self.top_level_branches().iter().all(|new_effect| {
+            let mut literals = unconditional.clone();
+            literals.extend(new_effect.get_unconditional_literals());
+            is_sat(&literals)
+        })
+    }
+
+    // the effects of the conditional effects and the outcomes of the
+    // non-deterministic effects that are not quantified
+    fn top_level_branches(&self) -> Vec<&Effect<'a>> {
+        match self {
+            Effect::And(effects) => effects.iter().flat_map(|e| e.top_level_branches()).collect(),
+            Effect::OneOf(effects) => effects.iter().collect(),
+            Effect::When(_, new_effect) => vec![new_effect],
+            _ => vec![],
+        }
+    }
+}
+
+impl<'a> fmt::Display for Effect<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effect::Empty => write!(f, "∅"),
+            Effect::Add(predicate) => write!(f, "{}", predicate.to_string()),
+            Effect::Delete(predicate) => write!(f, "¬({})", predicate.to_string()),
+            Effect::And(effects) => {
+                let effects_str = effects.iter().map(|e| format!("{}", e)).collect::<Vec<_>>().join(" ∧ ");
+                write!(f, "({})", effects_str)
+            }
+            Effect::OneOf(effects) => {
+                let effects_str = effects.iter().map(|e| format!("{}", e)).collect::<Vec<_>>().join(" ⊕ ");
+                write!(f, "({})", effects_str)
+            }
+            Effect::ForAll(vars, inner) => {
+                let vars_str = vars.iter().map(|var| var.name.to_string()).collect::<Vec<_>>().join(", ");
+                write!(f, "∀{}: {}", vars_str, inner)
+            }
+            Effect::When(condition, inner) => write!(f, "({}) ▷ ({})", condition, inner),
+            Effect::Numeric(operation, function, value) => {
+                write!(f, "{} {} {}", operation, function.to_string(), value)
+            }
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::{collections::HashMap, fmt};
 extern crate Robinson;
 use serde::Serialize;
 
+use crate::lexical_analyzer::OperationType;
+
 use super::*;
 
 #[derive(Clone, Debug, Serialize)]
@@ -19,8 +21,10 @@ pub enum Formula<'a> {
     Exists(Vec<Symbol<'a>>, Box<Formula<'a>>),
     // ∀vars: formula
     ForAll(Vec<Symbol<'a>>, Box<Formula<'a>>),
-    // formula = formula'
-    Equals(&'a str, &'a str),
+    // term = term'
+    Equals(Symbol<'a>, Symbol<'a>),
+    // expression ◦ expression', where ◦ is one of "= < > <= >="
+    Comparison(OperationType, NumericExpression<'a>, NumericExpression<'a>),
 }
 
 impl<'a> Formula<'a> {
@@ -50,10 +54,29 @@ impl<'a> Formula<'a> {
             Formula::Equals(_, _) => {}
             // not propositional
             Formula::ForAll(_, _) | Formula::Exists(_, _) => {}
+            Formula::Comparison(_, _, _) => {}
         }
         return predicates;
     }
 
+    // the functions of the numeric comparisons (outside quantifiers)
+    pub fn get_functions(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => vec![],
+            Formula::ForAll(_, _) | Formula::Exists(_, _) => vec![],
+            Formula::Not(new_formula) => new_formula.get_functions(),
+            Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
+                new_formula.iter().flat_map(|f| f.get_functions()).collect()
+            }
+            Formula::Imply(ps, qs) => ps.iter().chain(qs.iter()).flat_map(|f| f.get_functions()).collect(),
+            Formula::Comparison(_, lhs, rhs) => {
+                let mut functions = lhs.get_functions();
+                functions.extend(rhs.get_functions());
+                functions
+            }
+        }
+    }
+
     pub fn to_cnf(&self) -> Formula<'a> {
         let simplified = self.simplify();
         let nnf = simplified.to_nnf();
@@ -71,8 +94,8 @@ impl<'a> Formula<'a> {
                     },
                     Formula::Equals(a, b) => {
                         Formula::Xor(vec![
-                            Box::new(Formula::Atom(Predicate::new_dummy(a))),
-                            Box::new(Formula::Atom(Predicate::new_dummy(b))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(a.name))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(b.name))),
                         ]).simplify()
                     }
                     _ => {
@@ -149,10 +172,10 @@ impl<'a> Formula<'a> {
             Formula::Equals(a, b) => {
                 // a = b -> (a ^ b) v (~a ^ ~b)
                 let pred_a = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&a))
+                    Formula::Atom(Predicate::new_dummy(a.name))
                 );
                 let pred_b = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&b))
+                    Formula::Atom(Predicate::new_dummy(b.name))
                 );
                 let pos_conjunct = Formula::And(vec![pred_a.clone(), pred_b.clone()]);
                 let not_a = Box::new(Formula::Not(pred_a));
@@ -160,13 +183,14 @@ impl<'a> Formula<'a> {
                 let neg_conjunct = Formula::And(vec![not_a, not_b]);
                 Formula::Or(vec![Box::new(pos_conjunct), Box::new(neg_conjunct)])
             },
+            Formula::Comparison(_, _, _) => self.clone(),
         }
     }
 
     fn to_nnf(&self) -> Formula<'a> {
         match self {
             Formula::Empty => Formula::Empty,
-            Formula::Atom(_) => self.clone(),
+            Formula::Atom(_) | Formula::Comparison(_, _, _) => self.clone(),
             Formula::Not(f) => match &**f {
                 Formula::Empty => self.clone(),
                 Formula::Atom(p) => self.clone(),
@@ -190,6 +214,7 @@ impl<'a> Formula<'a> {
                     Box::new(Formula::Not(Box::new(f.to_nnf()))),
                 ),
                 //
+                Formula::Comparison(_, _, _) => self.clone(),
                 Formula::Xor(_) | Formula::Imply(_, _) | Formula::Equals(_, _) => unreachable!("not simplified")
             },
             Formula::And(fs) => Formula::And(fs.iter().map(|f| Box::new(f.to_nnf())).collect()),
@@ -207,6 +232,7 @@ impl<'a> Formula<'a> {
     fn distribute_disjunction(&self) -> Formula<'a> {
         match self {
             Formula::Empty | Formula::Atom(_) | Formula::Not(_) => self.clone(),
+            Formula::Comparison(_, _, _) => self.clone(),
             Formula::And(fs) => Formula::And(
                 fs.iter()
                     .map(|f| Box::new(f.distribute_disjunction()))
@@ -256,12 +282,17 @@ impl<'a> Formula<'a> {
             Formula::Empty => {},
             Formula::Atom(_) => {},
             Formula::Not(f) => {
-                return Formula::Not(Box::new(f.drop_quantifiers()))
+                return match f.drop_quantifiers() {
+                    // the negation of a dropped formula is dropped as well
+                    Formula::Empty => Formula::Empty,
+                    f => Formula::Not(Box::new(f)),
+                }
             }
             Formula::And(fs) => {
+                // dropped conjuncts do not constrain the others
                 return Formula::And(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
+                    f.drop_quantifiers()
+                }).filter(|f| !matches!(f, Formula::Empty)).map(Box::new).collect());
             }
             Formula::Or(fs) => {
                 return Formula::Or(fs.iter().map(|f| {
@@ -284,6 +315,8 @@ impl<'a> Formula<'a> {
             }
             Formula::Equals(_, _) => {}
             Formula::ForAll(_, _) | Formula::Exists(_, _) => {return Formula::Empty}
+            // numeric constraints are not propositional
+            Formula::Comparison(_, _, _) => {return Formula::Empty}
         }
         self.clone()
     }
@@ -301,7 +334,8 @@ impl<'a> Formula<'a> {
                 for f in subformula {
                     let mut clause: Vec<i32> = vec![];
                     match *f {
-                        Formula::Empty => {}
+                        // a dropped quantifier constrains nothing
+                        Formula::Empty => continue,
                         Formula::Atom(predicate) => {
                             if !literal_ids.contains_key(&predicate.to_string()) {
                                 literal_ids.insert(predicate.to_string(), count);
@@ -321,6 +355,10 @@ impl<'a> Formula<'a> {
                             }
                         }
                         Formula::Or(disjuncts) => {
+                            // the clause holds whenever the dropped quantifier does
+                            if disjuncts.iter().any(|disjunct| matches!(**disjunct, Formula::Empty)) {
+                                continue;
+                            }
                             for disjunct in disjuncts {
                                 match *disjunct {
                                     Formula::Atom(predicate) => {
@@ -440,7 +478,8 @@ impl<'a> fmt::Display for Formula<'a> {
                 let vars_str = vars.iter().map(|var| format!("{}", var.name)).collect::<Vec<_>>().join(", ");
                 write!(f, "∀{}: {}", vars_str, inner)
             }
-            Formula::Equals(lhs, rhs) => write!(f, "{} = {}", lhs, rhs),
+            Formula::Equals(lhs, rhs) => write!(f, "{} = {}", lhs.name, rhs.name),
+            Formula::Comparison(operation, lhs, rhs) => write!(f, "{} {} {}", lhs, operation, rhs),
         }
     }
 }
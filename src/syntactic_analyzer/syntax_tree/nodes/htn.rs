@@ -3,6 +3,7 @@ use petgraph::algo::toposort;
 use petgraph::Directed;
 use serde::Serialize;
 
+use crate::lexical_analyzer::OperationType;
 use crate::TokenPosition;
 use super::*;
 
@@ -31,7 +32,9 @@ pub struct Subtask<'a> {
 #[derive(Debug, Clone, Serialize)]
 pub enum Constraint<'a> {
     Equal(&'a str, &'a str),
-    NotEqual(&'a str, &'a str)
+    NotEqual(&'a str, &'a str),
+    // (op time_point time_point), where op is one of "= < <= > >="
+    Temporal(OperationType, TimePoint<'a>, TimePoint<'a>),
 }
 
 #[derive(Debug, Clone, Serialize)]
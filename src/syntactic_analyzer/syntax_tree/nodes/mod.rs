@@ -5,6 +5,9 @@ mod htn;
 mod method;
 mod action;
 mod formula;
+mod effect;
+mod numeric;
+mod temporal;
 
 pub use predicate::Predicate;
 pub use task::Task;
@@ -13,3 +16,6 @@ pub use htn::*;
 pub use method::*;
 pub use action::*;
 pub use formula::*;
+pub use effect::*;
+pub use numeric::*;
+pub use temporal::*;
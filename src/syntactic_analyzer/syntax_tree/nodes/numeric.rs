@@ -0,0 +1,113 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::lexical_analyzer::{OperationType, TokenPosition};
+
+use super::*;
+
+#[derive(Clone, Debug, Serialize)]
+pub enum NumericExpression<'a> {
+    Number(&'a str, TokenPosition),
+    // the value of a function, e.g. "(distance ?a ?b)"
+    Function(Predicate<'a>),
+    // (op expression+), where op is one of "+ - * /"
+    Operation(OperationType, Vec<NumericExpression<'a>>),
+}
+
+impl<'a> NumericExpression<'a> {
+    // the functions that the expression refers to
+    pub fn get_functions(&self) -> Vec<&Predicate<'a>> {
+        match self {
+            NumericExpression::Number(_, _) => vec![],
+            NumericExpression::Function(function) => vec![function],
+            NumericExpression::Operation(_, operands) => operands
+                .iter()
+                .flat_map(|operand| operand.get_functions())
+                .collect(),
+        }
+    }
+
+    // the value of the expression, given the values of the (ground) functions
+    // (none if one of the functions has no value)
+    pub fn evaluate(&self, value_of: &dyn Fn(&Predicate<'a>) -> Option<f64>) -> Option<f64> {
+        match self {
+            NumericExpression::Number(value, _) => value.parse().ok(),
+            NumericExpression::Function(function) => value_of(function),
+            NumericExpression::Operation(operation, operands) => {
+                let values = operands
+                    .iter()
+                    .map(|operand| operand.evaluate(value_of))
+                    .collect::<Option<Vec<f64>>>()?;
+                let (first, rest) = values.split_first()?;
+                match operation {
+                    // a single operand is negated
+                    OperationType::Minus if rest.is_empty() => Some(-first),
+                    OperationType::Minus => Some(rest.iter().fold(*first, |lhs, rhs| lhs - rhs)),
+                    OperationType::Plus => Some(values.iter().sum()),
+                    OperationType::Times => Some(values.iter().product()),
+                    OperationType::Divide => Some(rest.iter().fold(*first, |lhs, rhs| lhs / rhs)),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// whether the values satisfy the comparison (one of "= < > <= >=")
+pub fn compare(operation: &OperationType, lhs: f64, rhs: f64) -> bool {
+    match operation {
+        OperationType::Equal => lhs == rhs,
+        OperationType::LessThan => lhs < rhs,
+        OperationType::GreaterThan => lhs > rhs,
+        OperationType::LessThanOrEqual => lhs <= rhs,
+        OperationType::GreaterThanOrEqual => lhs >= rhs,
+        _ => false,
+    }
+}
+
+impl<'a> fmt::Display for NumericExpression<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericExpression::Number(value, _) => write!(f, "{}", value),
+            NumericExpression::Function(function) => write!(f, "{}", function.to_string()),
+            NumericExpression::Operation(operation, operands) => {
+                let operands_str = operands
+                    .iter()
+                    .map(|operand| format!("{}", operand))
+                    .collect::<Vec<_>>()
+                    .join(&format!(" {} ", operation));
+                write!(f, "({})", operands_str)
+            }
+        }
+    }
+}
+
+// the initial value of a function, i.e. "(= (f o) 3)"
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionValue<'a> {
+    pub function: Predicate<'a>,
+    pub value: &'a str,
+    pub value_pos: TokenPosition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum Optimization {
+    Minimize,
+    Maximize,
+}
+
+impl fmt::Display for Optimization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Optimization::Minimize => write!(f, "minimize"),
+            Optimization::Maximize => write!(f, "maximize"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Metric<'a> {
+    pub optimization: Optimization,
+    pub expression: NumericExpression<'a>,
+}
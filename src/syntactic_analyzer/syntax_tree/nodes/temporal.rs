@@ -0,0 +1,103 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::lexical_analyzer::{OperationType, TokenPosition};
+
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum TimeSpecifier {
+    AtStart,
+    AtEnd,
+    OverAll,
+}
+
+impl fmt::Display for TimeSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSpecifier::AtStart => write!(f, "at start"),
+            TimeSpecifier::AtEnd => write!(f, "at end"),
+            TimeSpecifier::OverAll => write!(f, "over all"),
+        }
+    }
+}
+
+// (op ?duration value), where op is one of "= < <= > >="
+#[derive(Clone, Debug, Serialize)]
+pub struct DurationConstraint<'a> {
+    pub operation: OperationType,
+    pub value: NumericExpression<'a>,
+    // the position of "?duration"
+    pub position: TokenPosition,
+}
+
+impl<'a> fmt::Display for DurationConstraint<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({} ?duration {})", self.operation, self.value)
+    }
+}
+
+// the duration of a durative action, and when its conditions and effects apply
+#[derive(Clone, Debug, Serialize)]
+pub struct Timing<'a> {
+    pub duration: Vec<DurationConstraint<'a>>,
+    pub conditions: Vec<(TimeSpecifier, Formula<'a>)>,
+    pub effects: Vec<(TimeSpecifier, Effect<'a>)>,
+}
+
+impl<'a> Timing<'a> {
+    // the conjunction of the conditions that hold at the time
+    pub fn condition_at(&self, time: TimeSpecifier) -> Formula<'a> {
+        Formula::And(
+            self.conditions
+                .iter()
+                .filter(|(condition_time, _)| *condition_time == time)
+                .map(|(_, condition)| Box::new(condition.clone()))
+                .collect(),
+        )
+    }
+
+    // the conjunction of the effects that apply at the time
+    pub fn effect_at(&self, time: TimeSpecifier) -> Effect<'a> {
+        Effect::And(
+            self.effects
+                .iter()
+                .filter(|(effect_time, _)| *effect_time == time)
+                .map(|(_, effect)| effect.clone())
+                .collect(),
+        )
+    }
+
+    // the conditions that must hold at the start or at the end, together with
+    // the invariant, are satisfiable
+    pub fn is_sat(&self) -> bool {
+        [TimeSpecifier::AtStart, TimeSpecifier::AtEnd].into_iter().all(|time| {
+            let invariant = self.condition_at(TimeSpecifier::OverAll);
+            Formula::And(vec![Box::new(self.condition_at(time)), Box::new(invariant)]).is_sat()
+        })
+    }
+
+    // the effects of the start and of the end are each consistent
+    pub fn is_consistent(&self) -> bool {
+        [TimeSpecifier::AtStart, TimeSpecifier::AtEnd]
+            .into_iter()
+            .all(|time| self.effect_at(time).is_consistent())
+    }
+}
+
+// the start or the end of a subtask, e.g. "(end t1)"
+#[derive(Clone, Debug, Serialize)]
+pub struct TimePoint<'a> {
+    pub time: TimeSpecifier,
+    pub task_id: Symbol<'a>,
+}
+
+impl<'a> fmt::Display for TimePoint<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.time {
+            TimeSpecifier::AtEnd => write!(f, "(end {})", self.task_id.name),
+            _ => write!(f, "(start {})", self.task_id.name),
+        }
+    }
+}
@@ -3,20 +3,45 @@ use crate::TokenPosition;
 
 #[derive(Debug, Serialize)]
 pub struct ProblemAST<'a> {
+    pub name: String,
+    // the domain that the problem refers to
+    pub domain_name: String,
+    #[serde(skip)]
+    pub domain_name_pos: TokenPosition,
     pub requirements: Vec<RequirementType>,
+    // positions of the requirements (in the same order)
+    #[serde(skip)]
+    pub requirement_positions: Vec<TokenPosition>,
     pub init_tn: Option<InitialTaskNetwork<'a>>,
+    // position of the ":htn" keyword
+    #[serde(skip)]
+    pub init_tn_pos: Option<TokenPosition>,
     pub init_state: Vec<Predicate<'a>>,
+    // initial values of the numeric functions
+    pub init_values: Vec<FunctionValue<'a>>,
     pub goal: Option<Formula<'a>>,
+    // position of the ":goal" keyword
+    #[serde(skip)]
+    pub goal_pos: Option<TokenPosition>,
+    pub metric: Option<Metric<'a>>,
     pub objects: Vec<Symbol<'a>>,
 }
 
 impl <'a> ProblemAST<'a> {
-    pub fn new() -> ProblemAST<'a> {
+    pub fn new(name: String, domain_name: String, domain_name_pos: TokenPosition) -> ProblemAST<'a> {
         ProblemAST {
+            name,
+            domain_name,
+            domain_name_pos,
             requirements: vec![],
+            requirement_positions: vec![],
             init_tn: None,
+            init_tn_pos: None,
             init_state: vec![],
+            init_values: vec![],
             goal: None,
+            goal_pos: None,
+            metric: None,
             objects: vec![]
         }
     }
@@ -28,16 +53,25 @@ impl <'a> ProblemAST<'a> {
         let object = Symbol::new(name, name_pos, Some(object_type), Some(type_pos));
         self.objects.push(object);
     }
-    pub fn add_init_tn(&mut self, tn: InitialTaskNetwork<'a>) {
+    pub fn add_init_tn(&mut self, tn: InitialTaskNetwork<'a>, tn_pos: TokenPosition) {
         self.init_tn = Some(tn);
+        self.init_tn_pos = Some(tn_pos);
     }
     pub fn add_init_state(&mut self, state: Vec<Predicate<'a>>) {
         self.init_state = state;
     }
-    pub fn add_goal(&mut self, goal: Formula<'a>) {
+    pub fn add_init_values(&mut self, values: Vec<FunctionValue<'a>>) {
+        self.init_values = values;
+    }
+    pub fn add_metric(&mut self, metric: Metric<'a>) {
+        self.metric = Some(metric);
+    }
+    pub fn add_goal(&mut self, goal: Formula<'a>, goal_pos: TokenPosition) {
         self.goal = Some(goal);
+        self.goal_pos = Some(goal_pos);
     }
-    pub fn add_requirement(&mut self, req: RequirementType) {
+    pub fn add_requirement(&mut self, req: RequirementType, req_pos: TokenPosition) {
         self.requirements.push(req);
+        self.requirement_positions.push(req_pos);
     }
 }
\ No newline at end of file
@@ -292,10 +292,10 @@ mod tests {
                                 let neq = &*predicates[2];
                                 match neq {
                                     Formula::Not(equality) => {
-                                        match **equality {
+                                        match &**equality {
                                             Formula::Equals(a, b) => {
-                                                assert_eq!(a, "l1");
-                                                assert_eq!(b, "l2");
+                                                assert_eq!(a.name, "l1");
+                                                assert_eq!(b.name, "l2");
                                             }
                                             _ => { panic!("equality constraint not parsed successfully")}
                                         }
@@ -365,10 +365,10 @@ mod tests {
                                     }
                                     _ => { panic!("wrong parameter type") }
                                 }
-                                match **exp {
+                                match &**exp {
                                     Formula::Equals(a,b ) => {
-                                        assert_eq!(a, "l1");
-                                        assert_eq!(b, "l2");
+                                        assert_eq!(a.name, "l1");
+                                        assert_eq!(b.name, "l2");
                                     }
                                     _ => {
                                         panic!("wrong expression parsing")
@@ -643,21 +643,17 @@ mod tests {
                     _ => panic!("wrong formula"),
                 }
                 match &action.effects.as_ref().unwrap() {
-                    Formula::And(formula) => {
-                        assert_eq!(formula.len(), 2);
-                        if let Formula::Not(exp) = formula[0].as_ref() {
-                            if let Formula::Atom(pred) = exp.as_ref() {
-                                assert_eq!(pred.name, "hold");
-                                assert_eq!(pred.variables.len(), 2);
-                                assert_eq!(pred.variables[0].name, "p_2");
-                                assert_eq!(pred.variables[1].name, "p_3");
-                            } else {
-                                panic!("wrong formula")
-                            }
+                    Effect::And(effects) => {
+                        assert_eq!(effects.len(), 2);
+                        if let Effect::Delete(pred) = &effects[0] {
+                            assert_eq!(pred.name, "hold");
+                            assert_eq!(pred.variables.len(), 2);
+                            assert_eq!(pred.variables[0].name, "p_2");
+                            assert_eq!(pred.variables[1].name, "p_3");
                         } else {
                             panic!("wrong formula")
                         };
-                        if let Formula::Atom(pred) = formula[1].as_ref() {
+                        if let Effect::Add(pred) = &effects[1] {
                             assert_eq!(pred.name, "at");
                             assert_eq!(pred.variables.len(), 1);
                             assert_eq!(pred.variables[0].name, "p_2");
@@ -737,34 +733,30 @@ mod tests {
                     _ => panic!("wrong formula"),
                 }
                 match &action.effects.as_ref().unwrap() {
-                    Formula::And(formula) => {
-                        assert_eq!(formula.len(), 3);
-                        match formula[0].as_ref() {
-                            Formula::Not(exp) => {
-                                if let Formula::Atom(pred) = exp.as_ref() {
-                                    assert_eq!(pred.name, "hold");
-                                    assert_eq!(pred.variables.len(), 2);
-                                    assert_eq!(pred.variables[0].name, "p_2");
-                                    assert_eq!(pred.variables[1].name, "p_3");
-                                } else {
-                                    panic!("wrong formula")
-                                }
+                    Effect::And(effects) => {
+                        assert_eq!(effects.len(), 3);
+                        match &effects[0] {
+                            Effect::Delete(pred) => {
+                                assert_eq!(pred.name, "hold");
+                                assert_eq!(pred.variables.len(), 2);
+                                assert_eq!(pred.variables[0].name, "p_2");
+                                assert_eq!(pred.variables[1].name, "p_3");
                             }
                             _ => panic!()
                         }
-                        match formula[1].as_ref() {
-                            Formula::Atom(pred) => {
+                        match &effects[1] {
+                            Effect::Add(pred) => {
                                 assert_eq!(pred.name, "at");
                                 assert_eq!(pred.variables.len(), 1);
                                 assert_eq!(pred.variables[0].name, "p_2");
                             }
                             _ => panic!()
                         }
-                        match formula[2].as_ref() {
-                            Formula::ForAll(q, e) => {
+                        match &effects[2] {
+                            Effect::ForAll(q, e) => {
                                 assert_eq!(q.len(), 1);
                                 assert_eq!(q[0].name, "loc");
-                                assert_eq!(e.get_propositional_predicates().len(), 2);
+                                assert_eq!(e.get_predicates().len(), 2);
                             }
                             _ => panic!()
                         }
@@ -804,21 +796,17 @@ mod tests {
                 assert_eq!(a1_vars, vec!["p_1", "p_2", "p_3"]);
                 assert_eq!(a1_var_types, vec!["t1", "t1", "t2"]);
                 match &action.effects.as_ref().unwrap() {
-                    Formula::Xor(formula) => {
-                        assert_eq!(formula.len(), 2);
-                        if let Formula::Not(exp) = formula[0].as_ref() {
-                            if let Formula::Atom(pred) = exp.as_ref() {
-                                assert_eq!(pred.name, "hold");
-                                assert_eq!(pred.variables.len(), 2);
-                                assert_eq!(pred.variables[0].name, "p_2");
-                                assert_eq!(pred.variables[1].name, "p_3");
-                            } else {
-                                panic!("wrong formula")
-                            }
+                    Effect::OneOf(effects) => {
+                        assert_eq!(effects.len(), 2);
+                        if let Effect::Delete(pred) = &effects[0] {
+                            assert_eq!(pred.name, "hold");
+                            assert_eq!(pred.variables.len(), 2);
+                            assert_eq!(pred.variables[0].name, "p_2");
+                            assert_eq!(pred.variables[1].name, "p_3");
                         } else {
                             panic!("wrong formula")
                         };
-                        if let Formula::Atom(pred) = formula[1].as_ref() {
+                        if let Effect::Add(pred) = &effects[1] {
                             assert_eq!(pred.name, "at");
                             assert_eq!(pred.variables.len(), 1);
                             assert_eq!(pred.variables[0].name, "p_2");
@@ -1025,4 +1013,324 @@ mod tests {
             _ => panic!()
         }
     }
+
+    #[test]
+    pub fn domain_error_recovery_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:predicates (at ?x) (hold ?x ?y))
+                (:action a_1
+                 :parameters (?p_1)
+                 :precondition (at ?p_1
+                 :effect (not (at ?p_1))
+                )
+                (:task deliver :parameters (?p))
+                (:action a_2
+                 :parameters (?p_1 ?p_2)
+                 :effect (hold ?p_1 ?p_2))
+                (:method m_1
+                 :parameters (?p)
+                 :task (deliver ?p)
+                 :ordered-subtasks (a_2 ?p ?p)
+                 :unknown)
+                (:action a_3
+                 :parameters (?p_1)
+                 :effect (at ?p_1))
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let (ast, errors) = parser.parse_partial();
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 6),
+            token => panic!("{:?}", token)
+        }
+        match &errors[1] {
+            ParsingError::Lexiacal(error) => assert_eq!(error.position.line, 16),
+            token => panic!("{:?}", token)
+        }
+        match ast {
+            Some(AbstractSyntaxTree::Domain(d)) => {
+                assert_eq!(d.predicates.len(), 2);
+                assert_eq!(d.compound_tasks.len(), 1);
+                assert_eq!(d.compound_tasks[0].name, "deliver");
+                assert_eq!(d.actions.len(), 2);
+                assert_eq!(d.actions[0].name, "a_2");
+                assert_eq!(d.actions[1].name, "a_3");
+                assert_eq!(d.methods.len(), 0);
+            }
+            _ => panic!()
+        }
+    }
+
+    #[test]
+    pub fn problem_error_recovery_test() {
+        let program = String::from(
+            "(define (problem p1) (domain bal)
+                (:objects a b - t)
+                (:init (at a) at b)
+                (:htn :subtasks (deliver a))
+                c
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let (ast, errors) = parser.parse_partial();
+        assert_eq!(errors.len(), 2);
+        match &errors[0] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 3),
+            token => panic!("{:?}", token)
+        }
+        match &errors[1] {
+            ParsingError::Syntactic(error) => assert_eq!(error.position.line, 5),
+            token => panic!("{:?}", token)
+        }
+        match ast {
+            Some(AbstractSyntaxTree::Problem(p)) => {
+                assert_eq!(p.objects.len(), 2);
+                assert_eq!(p.init_state.len(), 0);
+                assert_eq!(p.init_tn.unwrap().tn.subtasks.len(), 1);
+            }
+            _ => panic!()
+        }
+        // parse still stops at the first error
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Err(ParsingError::Syntactic(error)) => assert_eq!(error.position.line, 3),
+            _ => panic!("errors are not caught")
+        }
+    }
+
+    #[test]
+    pub fn numeric_fluents_test() {
+        let program = String::from(
+            "(define (domain transport)
+                (:requirements :typing :numeric-fluents :action-costs)
+                (:types location vehicle)
+                (:predicates (at ?v - vehicle ?l - location))
+                (:functions
+                    (road-length ?l1 ?l2 - location) - number
+                    (fuel ?v - vehicle)
+                    (total-cost) - number
+                )
+                (:action drive
+                    :parameters (?v - vehicle ?from ?to - location)
+                    :precondition (and (at ?v ?from) (>= (fuel ?v) (* 2 (road-length ?from ?to))))
+                    :effect (and
+                        (not (at ?v ?from))
+                        (at ?v ?to)
+                        (decrease (fuel ?v) (road-length ?from ?to))
+                        (increase (total-cost) 1)
+                    )
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let domain = match parser.parse() {
+            Ok(AbstractSyntaxTree::Domain(domain)) => domain,
+            result => panic!("{:?}", result.err()),
+        };
+        assert_eq!(domain.functions.len(), 3);
+        assert_eq!(domain.functions[0].name, "road-length");
+        assert_eq!(domain.functions[0].variables.len(), 2);
+        assert_eq!(domain.functions[2].name, "total-cost");
+        match domain.actions[0].preconditions.as_ref().unwrap() {
+            Formula::And(conjuncts) => match &*conjuncts[1] {
+                Formula::Comparison(OperationType::GreaterThanOrEqual, lhs, rhs) => {
+                    assert_eq!(lhs.to_string(), "fuel(v,)");
+                    match rhs {
+                        NumericExpression::Operation(OperationType::Times, operands) => {
+                            assert_eq!(operands.len(), 2);
+                            assert!(matches!(operands[0], NumericExpression::Number("2", _)));
+                        }
+                        expression => panic!("{:?}", expression),
+                    }
+                }
+                formula => panic!("{:?}", formula),
+            },
+            formula => panic!("{:?}", formula),
+        }
+        match domain.actions[0].effects.as_ref().unwrap() {
+            Effect::And(effects) => {
+                assert_eq!(effects.len(), 4);
+                match &effects[3] {
+                    Effect::Numeric(OperationType::Increase, function, value) => {
+                        assert_eq!(function.name, "total-cost");
+                        assert!(matches!(value, NumericExpression::Number("1", _)));
+                    }
+                    formula => panic!("{:?}", formula),
+                }
+            }
+            formula => panic!("{:?}", formula),
+        }
+
+        let program = String::from(
+            "(define (problem p) (:domain transport)
+                (:objects truck - vehicle a b - location)
+                (:init (at truck a) (= (road-length a b) 10) (= (fuel truck) 2.5) (= (total-cost) 0))
+                (:goal (and (at truck b) (< (fuel truck) 1)))
+                (:metric minimize (+ (total-cost) (fuel truck)))
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        let problem = match parser.parse() {
+            Ok(AbstractSyntaxTree::Problem(problem)) => problem,
+            result => panic!("{:?}", result.err()),
+        };
+        assert_eq!(problem.init_state.len(), 1);
+        assert_eq!(problem.init_values.len(), 3);
+        assert_eq!(problem.init_values[0].function.name, "road-length");
+        assert_eq!(problem.init_values[1].value, "2.5");
+        assert_eq!(problem.init_values[1].value_pos.line, 3);
+        let metric = problem.metric.unwrap();
+        assert_eq!(metric.optimization, Optimization::Minimize);
+        match metric.expression {
+            NumericExpression::Operation(OperationType::Plus, operands) => assert_eq!(operands.len(), 2),
+            expression => panic!("{:?}", expression),
+        }
+    }
+
+    #[test]
+    pub fn conditional_effect_parsing_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:requirements :conditional-effects)
+                (:action a_1
+                 :parameters (?p - t1)
+                 :effect (and
+                    (at ?p)
+                    (forall (?q - t2)
+                        (when (and (hold ?p ?q) (not (busy ?q)))
+                            (and (busy ?q) (not (hold ?p ?q)))))
+                 )
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let domain = match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(domain)) => domain,
+            result => panic!("{:?}", result.err()),
+        };
+        assert_eq!(domain.requirements, vec![RequirementType::ConditionalEffects]);
+        let effects = match domain.actions[0].effects.as_ref().unwrap() {
+            Effect::And(effects) => effects,
+            effect => panic!("{:?}", effect),
+        };
+        assert_eq!(effects.len(), 2);
+        assert!(matches!(&effects[0], Effect::Add(predicate) if predicate.name == "at"));
+        let (variables, conditional) = match &effects[1] {
+            Effect::ForAll(variables, conditional) => (variables, conditional),
+            effect => panic!("{:?}", effect),
+        };
+        assert_eq!(variables[0].name, "q");
+        assert_eq!(variables[0].symbol_type, Some("t2"));
+        match conditional.as_ref() {
+            Effect::When(condition, effect) => {
+                assert_eq!(condition.get_propositional_predicates().len(), 2);
+                match effect.as_ref() {
+                    Effect::And(effects) => {
+                        assert!(matches!(&effects[0], Effect::Add(predicate) if predicate.name == "busy"));
+                        assert!(matches!(&effects[1], Effect::Delete(predicate) if predicate.name == "hold"));
+                    }
+                    effect => panic!("{:?}", effect),
+                }
+            }
+            effect => panic!("{:?}", effect),
+        }
+        // the conditional effects are kept apart from the unconditional ones
+        let literals = domain.actions[0].effects.as_ref().unwrap().get_unconditional_literals();
+        assert_eq!(literals.len(), 1);
+        assert_eq!(domain.actions[0].effects.as_ref().unwrap().get_conditions().len(), 1);
+    }
+
+    #[test]
+    pub fn durative_action_parsing_test() {
+        let program = String::from(
+            "(define (domain rovers)
+                (:requirements :durative-actions :duration-inequalities)
+                (:durative-action navigate
+                 :parameters (?r - rover ?a ?b - waypoint)
+                 :duration (and (>= ?duration 2) (<= ?duration (distance ?a ?b)))
+                 :condition (and
+                    (at start (at ?r ?a))
+                    (over all (can_traverse ?r ?a ?b)))
+                 :effect (and
+                    (at start (not (at ?r ?a)))
+                    (at end (at ?r ?b)))
+                )
+                (:method m_visit
+                 :parameters (?r - rover ?a ?b - waypoint)
+                 :task (visit ?r ?b)
+                 :subtasks (and (t1 (navigate ?r ?a ?b)) (t2 (sample ?r ?b)))
+                 :constraints (and (< (end t1) (start t2)) (= (start t1) (start t2)))
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let domain = match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(domain)) => domain,
+            result => panic!("{:?}", result.err()),
+        };
+        let action = &domain.actions[0];
+        assert_eq!(action.name, "navigate");
+        assert_eq!(action.parameters.len(), 3);
+        let timing = action.timing.as_ref().unwrap();
+        assert_eq!(timing.duration.len(), 2);
+        assert_eq!(timing.duration[0].operation, OperationType::GreaterThanOrEqual);
+        assert_eq!(timing.duration[1].operation, OperationType::LessThanOrEqual);
+        assert_eq!(timing.duration[1].value.get_functions()[0].name, "distance");
+        let times: Vec<_> = timing.conditions.iter().map(|(time, _)| *time).collect();
+        assert_eq!(times, vec![TimeSpecifier::AtStart, TimeSpecifier::OverAll]);
+        let times: Vec<_> = timing.effects.iter().map(|(time, _)| *time).collect();
+        assert_eq!(times, vec![TimeSpecifier::AtStart, TimeSpecifier::AtEnd]);
+        assert!(matches!(&timing.effects[1].1, Effect::Add(predicate) if predicate.name == "at"));
+        // the untimed projection of the conditions and effects
+        let precondition = action.preconditions.as_ref().unwrap();
+        assert_eq!(precondition.get_propositional_predicates().len(), 2);
+        let effect = action.effects.as_ref().unwrap();
+        assert_eq!(effect.get_unconditional_literals().len(), 2);
+        // temporal method constraints
+        let constraints = domain.methods[0].tn.constraints.as_ref().unwrap();
+        assert_eq!(constraints.len(), 2);
+        match &constraints[0] {
+            Constraint::Temporal(OperationType::LessThan, lhs, rhs) => {
+                assert_eq!((lhs.time, lhs.task_id.name), (TimeSpecifier::AtEnd, "t1"));
+                assert_eq!((rhs.time, rhs.task_id.name), (TimeSpecifier::AtStart, "t2"));
+            }
+            constraint => panic!("{:?}", constraint),
+        }
+        assert!(matches!(&constraints[1], Constraint::Temporal(OperationType::Equal, _, _)));
+    }
+
+    #[test]
+    pub fn durative_action_syntax_error_test() {
+        // effects cannot hold "over all"
+        let program = String::from(
+            "(define (domain d)
+                (:durative-action a
+                 :parameters ()
+                 :duration (= ?duration 1)
+                 :effect (over all (done))
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Err(ParsingError::Syntactic(error)) => {
+                assert_eq!(error.expected, "'at start' or 'at end'");
+                assert_eq!(error.position.line, 5);
+            }
+            result => panic!("{:?}", result.map(|_| ())),
+        }
+    }
 }
@@ -0,0 +1,51 @@
+extern crate hddl_analyzer;
+
+use hddl_analyzer::{FormatOptions, HDDLAnalyzer};
+use std::fs;
+
+// formats the domain and the first problem of each IPC benchmark
+#[test]
+pub fn ipc_round_trip_test() {
+    for folder in fs::read_dir("tests/ipc").unwrap() {
+        let path = folder.unwrap().path();
+        let domain_path = path.join("domain.hddl");
+        let mut problem_paths: Vec<_> = fs::read_dir(&path)
+            .unwrap()
+            .map(|file| file.unwrap().path())
+            .filter(|file| *file != domain_path)
+            .collect();
+        problem_paths.sort();
+        let domain = fs::read(&domain_path).unwrap();
+        let problem = fs::read(&problem_paths[0]).unwrap();
+        let formatted_domain = HDDLAnalyzer::format(&domain, FormatOptions::default()).unwrap();
+        let formatted_problem = HDDLAnalyzer::format(&problem, FormatOptions::default()).unwrap();
+        // formatting is idempotent
+        assert_eq!(
+            HDDLAnalyzer::format(&formatted_domain.clone().into_bytes(), FormatOptions::default()).unwrap(),
+            formatted_domain,
+            "{:?}",
+            domain_path
+        );
+        assert_eq!(
+            HDDLAnalyzer::format(&formatted_problem.clone().into_bytes(), FormatOptions::default()).unwrap(),
+            formatted_problem,
+            "{:?}",
+            problem_paths[0]
+        );
+        let formatted_domain = formatted_domain.into_bytes();
+        let formatted_problem = formatted_problem.into_bytes();
+        // the formatted files re-parse to the same model
+        assert_eq!(
+            HDDLAnalyzer::to_json(&domain, Some(&problem)).unwrap(),
+            HDDLAnalyzer::to_json(&formatted_domain, Some(&formatted_problem)).unwrap(),
+            "{:?}",
+            domain_path
+        );
+        assert_eq!(
+            HDDLAnalyzer::verify(&domain, Some(&problem)).is_ok(),
+            HDDLAnalyzer::verify(&formatted_domain, Some(&formatted_problem)).is_ok(),
+            "{:?}",
+            domain_path
+        );
+    }
+}